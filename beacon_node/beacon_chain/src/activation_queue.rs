@@ -0,0 +1,106 @@
+use crate::errors::BeaconChainError as Error;
+use types::{BeaconState, BeaconStateError, ChainSpec, Epoch, EthSpec};
+
+/// Estimates the epoch at which the validator at `validator_index` will activate, or returns its
+/// activation epoch if one has already been assigned.
+///
+/// Validators not yet in the activation queue are assumed to join it at the end of the current
+/// epoch. The estimate assumes the churn limit stays at its present value and ignores validators
+/// that join the queue later with an earlier eligibility epoch.
+///
+/// Requires the current epoch committee cache of `state` to be built.
+pub fn expected_activation_epoch<E: EthSpec>(
+    state: &BeaconState<E>,
+    validator_index: usize,
+    spec: &ChainSpec,
+) -> Result<Epoch, Error> {
+    let far_future_epoch = spec.far_future_epoch;
+    let current_epoch = state.current_epoch();
+
+    let validator = state
+        .validator_registry
+        .get(validator_index)
+        .ok_or(BeaconStateError::UnknownValidator)?;
+
+    if validator.activation_epoch != far_future_epoch {
+        return Ok(validator.activation_epoch);
+    }
+
+    let eligibility_epoch = if validator.activation_eligibility_epoch != far_future_epoch {
+        validator.activation_eligibility_epoch
+    } else if validator.effective_balance >= spec.max_effective_balance {
+        current_epoch
+    } else {
+        return Err(Error::InsufficientBalanceForActivation { validator_index });
+    };
+
+    // The queue is ordered by eligibility epoch, then by index.
+    let queue_position = state
+        .validator_registry
+        .iter()
+        .enumerate()
+        .filter(|(index, other)| {
+            other.activation_eligibility_epoch != far_future_epoch
+                && other.activation_epoch == far_future_epoch
+                && (other.activation_eligibility_epoch, *index)
+                    < (eligibility_epoch, validator_index)
+        })
+        .count() as u64;
+
+    let churn_limit = state.get_churn_limit(spec)?;
+
+    Ok(current_epoch + 1 + spec.activation_exit_delay + queue_position / churn_limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::test_utils::TestingBeaconStateBuilder;
+    use types::{MinimalEthSpec, RelativeEpoch};
+
+    #[test]
+    fn queue_beyond_churn_limit() {
+        let spec = MinimalEthSpec::default_spec();
+        let builder: TestingBeaconStateBuilder<MinimalEthSpec> =
+            TestingBeaconStateBuilder::from_deterministic_keypairs(8, &spec);
+        let (mut state, _keypairs) = builder.build();
+        state
+            .build_committee_cache(RelativeEpoch::Current, &spec)
+            .unwrap();
+
+        let churn_limit = state.get_churn_limit(&spec).unwrap();
+        assert_eq!(churn_limit, spec.min_per_epoch_churn_limit);
+
+        // Queue more than two epochs' worth of pending validators, all eligible at genesis.
+        let active_count = state.validator_registry.len();
+        let pending_count = churn_limit as usize * 2 + 1;
+        for _ in 0..pending_count {
+            let mut validator = state.validator_registry[0].clone();
+            validator.activation_eligibility_epoch = state.current_epoch();
+            validator.activation_epoch = spec.far_future_epoch;
+            state.validator_registry.push(validator);
+        }
+
+        let first_activation = state.current_epoch() + 1 + spec.activation_exit_delay;
+        for position in 0..pending_count {
+            assert_eq!(
+                expected_activation_epoch(&state, active_count + position, &spec),
+                Ok(first_activation + position as u64 / churn_limit),
+                "queue position {}",
+                position
+            );
+        }
+
+        // The last pending validator waits for two full epochs of churn.
+        assert_eq!(
+            expected_activation_epoch(&state, active_count + pending_count - 1, &spec),
+            Ok(first_activation + 2)
+        );
+
+        // Active validators keep their activation epoch.
+        assert_eq!(
+            expected_activation_epoch(&state, 0, &spec),
+            Ok(MinimalEthSpec::genesis_epoch())
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use types::Epoch;
+
+/// Sentinel epoch indicating that no count has been cached yet.
+const UNINITIALIZED_EPOCH: u64 = std::u64::MAX;
+
+/// A lock-free cache of the number of active validators in some epoch.
+#[derive(Debug)]
+pub struct ActiveValidatorCount {
+    epoch: AtomicU64,
+    count: AtomicUsize,
+}
+
+impl Default for ActiveValidatorCount {
+    fn default() -> Self {
+        Self {
+            epoch: AtomicU64::new(UNINITIALIZED_EPOCH),
+            count: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl ActiveValidatorCount {
+    /// Returns the cached count if it was computed for `epoch`, otherwise calls `count_fn`, caches
+    /// the result and returns it.
+    pub fn get_or_update<F>(&self, epoch: Epoch, count_fn: F) -> usize
+    where
+        F: FnOnce() -> usize,
+    {
+        if self.epoch.load(Ordering::Acquire) == epoch.as_u64() {
+            return self.count.load(Ordering::Acquire);
+        }
+
+        let count = count_fn();
+
+        // The count is stored before the epoch so that a reader that observes the new epoch also
+        // observes the new count.
+        self.count.store(count, Ordering::Release);
+        self.epoch.store(epoch.as_u64(), Ordering::Release);
+
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalidates_across_epoch_boundary() {
+        let cache = ActiveValidatorCount::default();
+
+        assert_eq!(cache.get_or_update(Epoch::new(1), || 10), 10);
+        assert_eq!(
+            cache.get_or_update(Epoch::new(1), || panic!("should be cached")),
+            10
+        );
+
+        assert_eq!(cache.get_or_update(Epoch::new(2), || 12), 12);
+        assert_eq!(
+            cache.get_or_update(Epoch::new(2), || panic!("should be cached")),
+            12
+        );
+    }
+}
@@ -1,26 +1,52 @@
+use crate::activation_queue::expected_activation_epoch;
+use crate::active_validator_count::ActiveValidatorCount;
 use crate::checkpoint::CheckPoint;
-use crate::errors::{BeaconChainError as Error, BlockProductionError};
+use crate::errors::{
+    AttestationError, BeaconChainError as Error, BlockProductionError, ProposerSlashingError,
+    SyncAggregateError,
+};
+use crate::execution_engine::{ExecutionEngine, PayloadStatus};
 use crate::fork_choice::{Error as ForkChoiceError, ForkChoice};
 use crate::metrics::Metrics;
-use crate::persisted_beacon_chain::{PersistedBeaconChain, BEACON_CHAIN_DB_KEY};
+use crate::persisted_beacon_chain::{
+    PersistedBeaconChain, PersistedHead, BEACON_CHAIN_DB_KEY, BEACON_CHAIN_HEAD_DB_KEY,
+};
+use crate::prune_interval::PruneInterval;
+use crate::validator_inclusion_cache::ValidatorInclusionCache;
+use futures::future::{self, Either};
+use futures::Future;
+use hashing::hash;
 use lmd_ghost::LmdGhost;
+use merkle_proof::{merkle_branch, merkle_root_from_branch};
 use operation_pool::DepositInsertStatus;
 use operation_pool::{OperationPool, PersistedOperationPool};
-use parking_lot::{RwLock, RwLockReadGuard};
-use slog::{info, Logger};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard};
+use serde_derive::Serialize;
+use slog::{debug, info, warn, Logger};
 use slot_clock::SlotClock;
+use state_processing::common::convert_to_indexed;
+use state_processing::common::get_attesting_indices_unsorted;
+use state_processing::common::initiate_validator_exit;
 use state_processing::per_block_processing::errors::{
     AttestationValidationError, AttesterSlashingValidationError, DepositValidationError,
     ExitValidationError, ProposerSlashingValidationError, TransferValidationError,
 };
+use state_processing::per_block_processing::{
+    verify_deposit_merkle_proof, verify_exit, verify_indexed_attestation,
+};
+use state_processing::per_epoch_processing::apply_rewards::{
+    get_attestation_delta_components, get_base_reward, get_proposer_rewards,
+};
+use state_processing::per_epoch_processing::validator_statuses::ValidatorStatuses;
 use state_processing::{
     per_block_processing, per_block_processing_without_verifying_block_signature,
     per_slot_processing, BlockProcessingError,
 };
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use store::iter::{BestBlockRootsIterator, BlockIterator, BlockRootsIterator, StateRootsIterator};
-use store::{Error as DBError, Store};
-use tree_hash::TreeHash;
+use store::{DBColumn, Error as DBError, Store, StoreItem};
+use tree_hash::{SignedRoot, TreeHash};
 use types::*;
 
 // Text included in blocks.
@@ -29,6 +55,18 @@ use types::*;
 //                          |-------must be this long------|
 pub const GRAFFITI: &str = "sigp/lighthouse-0.0.0-prerelease";
 
+/// The maximum number of slots `BeaconChain::find_common_ancestor` will search backwards.
+pub const FINALIZED_CHECKPOINT_DEPTH: u64 = 1_024;
+
+/// A fork choice tree with more nodes than this is logged as a warning.
+pub const FORK_CHOICE_TREE_SIZE_WARN_THRESHOLD: usize = 10_000;
+
+/// By default, the fork choice tree is pruned on every finalization update.
+pub const DEFAULT_FORK_CHOICE_STORE_PRUNE_INTERVAL: u64 = 1;
+
+/// The maximum number of `SyncCommitteeMessage`s stored for the current committee period.
+pub const MAX_SYNC_COMMITTEE_MESSAGES: usize = 16_384;
+
 #[derive(Debug, PartialEq)]
 pub enum BlockProcessingOutcome {
     /// Block was valid and imported into the block graph.
@@ -52,6 +90,159 @@ pub enum BlockProcessingOutcome {
     PerBlockProcessingError(BlockProcessingError),
 }
 
+/// Attestation participation of the validators that were active in some epoch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidatorInclusionData {
+    /// The number of active validators with an attestation included on chain.
+    pub participating_validators: usize,
+    /// The number of active validators.
+    pub total_validators: usize,
+    /// The effective balance of `participating_validators`.
+    pub attesting_balance_gwei: u64,
+    /// The effective balance of `total_validators`.
+    pub total_balance_gwei: u64,
+}
+
+/// The rewards (positive) and penalties (negative) of a validator for some epoch, in gwei.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct EpochRewards {
+    pub validator_index: usize,
+    pub attestation_source_reward: i64,
+    /// The bonus for the attestation being included quickly.
+    pub attestation_inclusion_delay_reward: i64,
+    pub attestation_target_reward: i64,
+    pub attestation_head_reward: i64,
+    /// Always zero, there are no sync committees in this version of the spec.
+    pub sync_committee_reward: i64,
+    /// Earned for proposing blocks that were first to include attestations of the epoch.
+    pub proposer_reward: i64,
+    /// Applied while the chain has not finalized for `min_epochs_to_inactivity_penalty` epochs.
+    pub inactivity_penalty: i64,
+}
+
+/// The rewards earned by the proposer of a block, by source.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct BlockRewards {
+    /// The sum of all other fields.
+    pub total_gwei: u64,
+    /// Earned for including attestations.
+    pub attestation_reward_gwei: u64,
+    /// Always zero, there are no sync committees in this version of the spec.
+    pub sync_aggregate_reward_gwei: u64,
+    /// Earned as the whistleblower of proposer slashings.
+    pub proposer_slashing_reward_gwei: u64,
+    /// Earned as the whistleblower of attester slashings.
+    pub attester_slashing_reward_gwei: u64,
+}
+
+/// The status of a validator in the head state, as reported to a co-located validator client.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidatorStatus {
+    pub pubkey: PublicKey,
+    /// `None` if the validator is not in the registry.
+    pub index: Option<usize>,
+    pub balance_gwei: u64,
+    pub is_active: bool,
+    pub is_slashed: bool,
+    /// The number of `PendingAttestation`s of the previous and current epochs that include the
+    /// validator.
+    pub pending_attestations: usize,
+}
+
+/// How an attestation would be rewarded if included in the block following the head.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttestationRewardData {
+    /// The attestation votes for the justified checkpoint of its target epoch.
+    pub source_correct: bool,
+    /// The attestation votes for the block at the start of its target epoch.
+    pub target_correct: bool,
+    /// The attestation votes for the block at its slot.
+    pub head_correct: bool,
+    /// The number of slots between the attestation's slot and the slot after the head.
+    pub inclusion_delay: u64,
+    /// The fraction of the attesting committee that signed the attestation.
+    pub participation_fraction: f64,
+}
+
+/// The data a light client requires to follow the chain through one committee period.
+///
+/// This is a stub: the branches are always empty until the state supports Merkle proofs.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LightClientUpdate {
+    /// The header of the latest block in the period.
+    pub attested_header: BeaconBlockHeader,
+    pub sync_committee_branch: Vec<Hash256>,
+    pub finality_branch: Vec<Hash256>,
+}
+
+/// A signature over the head block root by a member of the sync committee.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncCommitteeMessage {
+    pub slot: Slot,
+    pub beacon_block_root: Hash256,
+    pub validator_index: u64,
+    pub signature: Signature,
+}
+
+/// The aggregated `SyncCommitteeMessage`s of a sync committee.
+///
+/// `sync_committee_bits` is indexed by position in the sync committee.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncAggregate {
+    pub sync_committee_bits: Bitfield,
+    pub sync_committee_signature: AggregateSignature,
+}
+
+/// The payload attributes sent to an execution engine when it is asked to build a payload.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExecutionPayloadAttributes {
+    pub timestamp: u64,
+    pub prev_randao: Hash256,
+    pub suggested_fee_recipient: Address,
+}
+
+/// An aggregate `Attestation` whose signers and signature have been checked by
+/// `BeaconChain::verify_aggregate_attestation`, so its signature need not be verified again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedAggregateAttestation {
+    attestation: Attestation,
+}
+
+impl VerifiedAggregateAttestation {
+    pub fn attestation(&self) -> &Attestation {
+        &self.attestation
+    }
+
+    pub fn into_attestation(self) -> Attestation {
+        self.attestation
+    }
+}
+
+/// The result of `BeaconChain::optimistic_update`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptimisticUpdateResult {
+    /// `true` if the block can never be imported.
+    pub definitely_invalid: bool,
+    /// Why the block is invalid, or why the checks were skipped.
+    pub reason: Option<String>,
+}
+
+impl OptimisticUpdateResult {
+    fn invalid(reason: &str) -> Self {
+        Self {
+            definitely_invalid: true,
+            reason: Some(reason.to_string()),
+        }
+    }
+
+    fn unknown(reason: Option<&str>) -> Self {
+        Self {
+            definitely_invalid: false,
+            reason: reason.map(String::from),
+        }
+    }
+}
+
 pub trait BeaconChainTypes {
     type Store: store::Store;
     type SlotClock: slot_clock::SlotClock;
@@ -78,11 +269,47 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     state: RwLock<BeaconState<T::EthSpec>>,
     /// The root of the genesis block.
     genesis_block_root: Hash256,
+    /// The slot of the genesis block, which is later than the genesis slot if the chain was
+    /// anchored at a trusted block. No earlier blocks are available.
+    tail_slot: Slot,
+    /// The tree hash root of the validator registry of the genesis state.
+    genesis_validators_root: Hash256,
     /// A state-machine that is updated with information from the network and chooses a canonical
     /// head block.
     pub fork_choice: ForkChoice<T>,
     /// Stores metrics about this `BeaconChain`.
     pub metrics: Metrics,
+    /// The result of the last call to `compute_expected_withdrawals`, keyed by head block root
+    /// and epoch.
+    expected_withdrawals_cache: RwLock<Option<(Hash256, Epoch, Vec<(Address, u64)>)>>,
+    /// The number of active validators in the current epoch, see `count_active_validators`.
+    active_validator_count: ActiveValidatorCount,
+    /// The results of `get_validator_inclusion_data` for the most recent finalized epochs.
+    validator_inclusion_cache: ValidatorInclusionCache,
+    /// The result of the last call to `compute_next_shuffling`, keyed by head block root and
+    /// epoch.
+    next_shuffling_cache: RwLock<Option<(Hash256, Epoch, Vec<Vec<usize>>)>>,
+    /// The `(shard, committee)` pairs of each slot of the most recently requested epoch, keyed by
+    /// head block root and epoch. See `get_beacon_committees_at_slot`.
+    beacon_committees_cache: RwLock<Option<(Hash256, Epoch, Vec<Vec<(u64, Vec<usize>)>>)>>,
+    /// The shuffling seed of the most recently requested epoch, keyed by head block root and
+    /// epoch. See `compute_shuffling_proof`.
+    shuffling_seed_cache: RwLock<Option<(Hash256, Epoch, Hash256)>>,
+    /// Counts finalization updates, so the fork choice tree is only pruned once every interval.
+    fork_choice_prune_interval: PruneInterval,
+    /// The root and slot of the finalized block at the last call to
+    /// `prune_block_roots_before_finalization`.
+    last_pruned_finalized_block: RwLock<(Hash256, Slot)>,
+    /// If `Some`, the eth1 deposit count voted for advances by one every this many slots. See
+    /// `with_junk_eth1_data_interval`.
+    junk_eth1_data_interval: Option<u64>,
+    /// The lowest `BuilderBid::value` accepted by `compute_builder_bid_validity`. See
+    /// `with_min_builder_bid_value`.
+    min_builder_bid_value: EthBalance,
+    /// The execution engine to which payloads are sent. See `with_execution_engine`.
+    execution_engine: Option<ExecutionEngine>,
+    /// Sync committee messages received for the current committee period.
+    sync_committee_messages: Arc<Mutex<Vec<SyncCommitteeMessage>>>,
 
     pub log: Logger,
 }
@@ -109,6 +336,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let genesis_block_root = genesis_block.block_header().canonical_root();
         store.put(&spec.zero_hash, &genesis_block)?;
 
+        let genesis_validators_root =
+            Hash256::from_slice(&genesis_state.validator_registry.tree_hash_root());
+
         info!(log, "Beacon chain initialized from genesis";
               "gensis_slot" => genesis_state.slot,
               "state_root" => format!("{}", state_root),
@@ -129,8 +359,24 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             state: RwLock::new(genesis_state),
             canonical_head,
             genesis_block_root,
+            tail_slot: genesis_block.slot,
+            genesis_validators_root,
             fork_choice: ForkChoice::new(store.clone(), &genesis_block, genesis_block_root),
             metrics: Metrics::new()?,
+            expected_withdrawals_cache: RwLock::new(None),
+            active_validator_count: ActiveValidatorCount::default(),
+            validator_inclusion_cache: ValidatorInclusionCache::default(),
+            next_shuffling_cache: RwLock::new(None),
+            beacon_committees_cache: RwLock::new(None),
+            shuffling_seed_cache: RwLock::new(None),
+            fork_choice_prune_interval: PruneInterval::new(
+                DEFAULT_FORK_CHOICE_STORE_PRUNE_INTERVAL,
+            ),
+            last_pruned_finalized_block: RwLock::new((genesis_block_root, genesis_block.slot)),
+            junk_eth1_data_interval: None,
+            min_builder_bid_value: EthBalance::zero(),
+            execution_engine: None,
+            sync_committee_messages: Arc::new(Mutex::new(vec![])),
             store,
             log,
         })
@@ -155,25 +401,134 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             spec.seconds_per_slot,
         );
 
-        let last_finalized_root = p.canonical_head.beacon_state.finalized_root;
-        let last_finalized_block = &p.canonical_head.beacon_block;
+        // The head is saved more often than the rest of the chain, so it may be more recent.
+        let head_key = Hash256::from_slice(&BEACON_CHAIN_HEAD_DB_KEY.as_bytes());
+        let (canonical_head, state) = match store.get::<PersistedHead>(&head_key)? {
+            Some(head) if head.head_block_root != p.canonical_head.beacon_block_root => {
+                let beacon_block = store
+                    .get(&head.head_block_root)?
+                    .ok_or_else(|| Error::MissingBeaconBlock(head.head_block_root))?;
+                let beacon_state: BeaconState<T::EthSpec> = store
+                    .get(&head.head_state_root)?
+                    .ok_or_else(|| Error::MissingBeaconState(head.head_state_root))?;
+
+                let checkpoint = CheckPoint::new(
+                    beacon_block,
+                    head.head_block_root,
+                    beacon_state.clone(),
+                    head.head_state_root,
+                );
+
+                (checkpoint, beacon_state)
+            }
+            _ => (p.canonical_head, p.state),
+        };
 
-        let op_pool = p.op_pool.into_operation_pool(&p.state, &spec);
+        let last_finalized_root = canonical_head.beacon_state.finalized_root;
+        let last_finalized_block = &canonical_head.beacon_block;
+
+        let op_pool = p.op_pool.into_operation_pool(&state, &spec);
+
+        let genesis_block: BeaconBlock = store
+            .get(&p.genesis_block_root)?
+            .ok_or_else(|| Error::MissingBeaconBlock(p.genesis_block_root))?;
+
+        // Derived from the genesis state, rather than persisted, so the encoding of
+        // `PersistedBeaconChain` is unchanged.
+        let genesis_state: BeaconState<T::EthSpec> = store
+            .get(&genesis_block.state_root)?
+            .ok_or_else(|| Error::MissingBeaconState(genesis_block.state_root))?;
+        let genesis_validators_root =
+            Hash256::from_slice(&genesis_state.validator_registry.tree_hash_root());
 
         Ok(Some(BeaconChain {
             spec,
             slot_clock,
             fork_choice: ForkChoice::new(store.clone(), last_finalized_block, last_finalized_root),
             op_pool,
-            canonical_head: RwLock::new(p.canonical_head),
-            state: RwLock::new(p.state),
+            canonical_head: RwLock::new(canonical_head),
+            state: RwLock::new(state),
             genesis_block_root: p.genesis_block_root,
+            tail_slot: genesis_block.slot,
+            genesis_validators_root,
             metrics: Metrics::new()?,
+            expected_withdrawals_cache: RwLock::new(None),
+            active_validator_count: ActiveValidatorCount::default(),
+            validator_inclusion_cache: ValidatorInclusionCache::default(),
+            next_shuffling_cache: RwLock::new(None),
+            beacon_committees_cache: RwLock::new(None),
+            shuffling_seed_cache: RwLock::new(None),
+            fork_choice_prune_interval: PruneInterval::new(
+                DEFAULT_FORK_CHOICE_STORE_PRUNE_INTERVAL,
+            ),
+            // The previous prune is not persisted, so the first prune after a restart considers
+            // every block finalized since the genesis block.
+            last_pruned_finalized_block: RwLock::new((p.genesis_block_root, genesis_block.slot)),
+            junk_eth1_data_interval: None,
+            min_builder_bid_value: EthBalance::zero(),
+            execution_engine: None,
+            sync_committee_messages: Arc::new(Mutex::new(vec![])),
             store,
             log,
         }))
     }
 
+    /// Use `weight_fn` as the fork choice weight of each validator index, instead of the
+    /// validator's effective balance. Intended for testing.
+    pub fn with_fork_choice_score_override<F>(mut self, weight_fn: F) -> Self
+    where
+        F: Fn(usize) -> Option<u64> + Send + Sync + 'static,
+    {
+        self.fork_choice.set_weight_override(Arc::new(weight_fn));
+        self
+    }
+
+    /// Bounds the block cache of the reduced tree fork choice to `max_cache_size` blocks.
+    ///
+    /// Has no effect for fork choice backends without a cache.
+    pub fn with_reduced_tree_fork_choice_max_cache_size(self, max_cache_size: usize) -> Self {
+        self.fork_choice.set_max_cache_size(max_cache_size);
+        self
+    }
+
+    /// Only prune the fork choice tree once every `interval` finalization updates, rather than on
+    /// each one. An `interval` of `0` is treated as `1`.
+    ///
+    /// Non-canonical blocks are pruned from the store at the same time, see
+    /// `prune_block_roots_before_finalization`.
+    pub fn with_fork_choice_store_prune_interval(mut self, interval: u64) -> Self {
+        self.fork_choice_prune_interval = PruneInterval::new(interval);
+        self
+    }
+
+    /// Returns the number of finalization updates required between prunes of the fork choice
+    /// tree.
+    pub fn fork_choice_store_prune_interval(&self) -> u64 {
+        self.fork_choice_prune_interval.interval()
+    }
+
+    /// Makes the eth1 data voted for in produced blocks report one more deposit every `interval`
+    /// slots, so deposit processing can be tested without an eth1 node. Intended for testing.
+    ///
+    /// By default the deposit count never advances. An `interval` of `0` is treated as `1`.
+    pub fn with_junk_eth1_data_interval(mut self, interval: u64) -> Self {
+        self.junk_eth1_data_interval = Some(std::cmp::max(interval, 1));
+        self
+    }
+
+    /// Rejects builder bids with a `value` (in wei) lower than `value`. By default any value is
+    /// accepted.
+    pub fn with_min_builder_bid_value(mut self, value: EthBalance) -> Self {
+        self.min_builder_bid_value = value;
+        self
+    }
+
+    /// Sends execution payloads to `execution_engine`, see `notify_new_payload`.
+    pub fn with_execution_engine(mut self, execution_engine: ExecutionEngine) -> Self {
+        self.execution_engine = Some(execution_engine);
+        self
+    }
+
     /// Attempt to save this instance to `self.store`.
     pub fn persist(&self) -> Result<(), Error> {
         let p: PersistedBeaconChain<T> = PersistedBeaconChain {
@@ -189,6 +544,71 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Checks that the deposits known to this node agree with the head state. Intended to be run
+    /// at startup, as a crash may leave the persisted operation pool out of step with the chain.
+    ///
+    /// There is no eth1 backend, so the operation pool is treated as the local deposit tree. Its
+    /// next deposits must not exceed `state.latest_eth1_data.deposit_count` and must have valid
+    /// Merkle proofs against `state.latest_eth1_data.deposit_root`. Stale deposits are logged and
+    /// removed from the pool.
+    ///
+    /// Returns the number of deposits removed, or an error if the head state itself has
+    /// processed more deposits than its eth1 data reports.
+    pub fn verify_deposit_tree_consistency(&self) -> Result<usize, String> {
+        let head = self.head();
+        let state = &head.beacon_state;
+        let eth1_data = &state.latest_eth1_data;
+
+        if state.deposit_index > eth1_data.deposit_count {
+            return Err(format!(
+                "Head state has processed {} deposits but its eth1 data only has {}",
+                state.deposit_index, eth1_data.deposit_count
+            ));
+        }
+
+        let mut pruned = 0;
+        for deposit in self.op_pool.get_deposits(state, &self.spec) {
+            let stale = if deposit.index >= eth1_data.deposit_count {
+                Some(format!(
+                    "beyond the eth1 deposit count of {}",
+                    eth1_data.deposit_count
+                ))
+            } else {
+                verify_deposit_merkle_proof(state, &deposit, &self.spec)
+                    .err()
+                    .map(|e| format!("no proof against {}: {:?}", eth1_data.deposit_root, e))
+            };
+
+            if let Some(reason) = stale {
+                warn!(self.log, "Pruning stale deposit"; "index" => deposit.index, "reason" => reason);
+                self.op_pool.remove_deposit(deposit.index);
+                pruned += 1;
+            }
+        }
+
+        Ok(pruned)
+    }
+
+    /// Writes the roots of the canonical head block and state to `self.store`, so the head can be
+    /// recovered by `from_store` after a crash.
+    pub fn save_head_to_db(&self) -> Result<(), DBError> {
+        let persisted_head = {
+            let head = self.canonical_head.read();
+            PersistedHead {
+                head_block_root: head.beacon_block_root,
+                head_state_root: head.beacon_state_root,
+            }
+        };
+
+        self.put_persisted_head(&persisted_head)
+    }
+
+    /// Writes `persisted_head` to the well-known head key of `self.store`.
+    fn put_persisted_head(&self, persisted_head: &PersistedHead) -> Result<(), DBError> {
+        let key = Hash256::from_slice(&BEACON_CHAIN_HEAD_DB_KEY.as_bytes());
+        self.store.put(&key, persisted_head)
+    }
+
     /// Returns the beacon block body for each beacon block root in `roots`.
     ///
     /// Fails if any root in `roots` does not have a corresponding block.
@@ -238,55 +658,1156 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         BlockRootsIterator::owned(self.store.clone(), self.state.read().clone(), slot)
     }
 
-    /// Iterates in reverse (highest to lowest slot) through all block roots from largest
-    /// `slot <= beacon_state.slot` through to genesis.
+    /// Iterates in reverse (highest to lowest slot) through all block roots from largest
+    /// `slot <= beacon_state.slot` through to genesis.
+    ///
+    /// Returns `None` for roots prior to genesis or when there is an error reading from `Store`.
+    ///
+    /// Contains duplicate roots when skip slots are encountered.
+    pub fn rev_iter_best_block_roots(
+        &self,
+        slot: Slot,
+    ) -> BestBlockRootsIterator<T::EthSpec, T::Store> {
+        BestBlockRootsIterator::owned(self.store.clone(), self.state.read().clone(), slot)
+    }
+
+    /// Iterates in reverse (highest to lowest slot) through all state roots from `slot` through to
+    /// genesis.
+    ///
+    /// Returns `None` for roots prior to genesis or when there is an error reading from `Store`.
+    pub fn rev_iter_state_roots(&self, slot: Slot) -> StateRootsIterator<T::EthSpec, T::Store> {
+        StateRootsIterator::owned(self.store.clone(), self.state.read().clone(), slot)
+    }
+
+    /// Returns the root of the canonical state at `slot`, which must not be later than the head.
+    ///
+    /// Roots are read by a `StateRootsIterator` starting at the head state, which loads older
+    /// states from the store as required. Returns `Err(NotArchived)` if a state needed to reach
+    /// `slot` is not stored.
+    pub fn get_historical_state_root(&self, slot: Slot) -> Result<Hash256, Error> {
+        // Clone the head state so the head lock is not held while reading from the store.
+        let head_state = {
+            let head = self.head();
+
+            if slot == head.beacon_state.slot {
+                return Ok(head.beacon_state_root);
+            } else if slot > head.beacon_state.slot {
+                return Err(Error::NoStateForSlot(slot));
+            }
+
+            head.beacon_state.clone()
+        };
+        let start_slot = head_state.slot - 1;
+
+        StateRootsIterator::owned(self.store.clone(), head_state, start_slot)
+            .find(|(_root, root_slot)| *root_slot == slot)
+            .map(|(root, _slot)| root)
+            .ok_or_else(|| Error::NotArchived(slot))
+    }
+
+    /// Returns the `(withdrawal_address, balance)` of each validator in the head state that is
+    /// withdrawable at `epoch` and has a non-zero balance.
+    ///
+    /// The withdrawal address is taken to be the last 20 bytes of the validator's
+    /// `withdrawal_credentials`. Results are cached for the most recent head and epoch.
+    pub fn compute_expected_withdrawals(&self, epoch: Epoch) -> Result<Vec<(Address, u64)>, Error> {
+        let head = self.head();
+
+        if let Some((root, cached_epoch, withdrawals)) = &*self.expected_withdrawals_cache.read() {
+            if *root == head.beacon_block_root && *cached_epoch == epoch {
+                return Ok(withdrawals.clone());
+            }
+        }
+
+        let state = &head.beacon_state;
+        let withdrawals = state
+            .validator_registry
+            .iter()
+            .zip(state.balances.iter())
+            .filter(|(validator, balance)| validator.is_withdrawable_at(epoch) && **balance > 0)
+            .map(|(validator, balance)| {
+                (
+                    Address::from_slice(&validator.withdrawal_credentials[12..]),
+                    *balance,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        *self.expected_withdrawals_cache.write() =
+            Some((head.beacon_block_root, epoch, withdrawals.clone()));
+
+        Ok(withdrawals)
+    }
+
+    /// Returns the validator indices of the committees at each slot of `epoch + 1`, computed from
+    /// the head state. Each inner `Vec` is the concatenation of the committees at that slot.
+    ///
+    /// The seed for `epoch + 1` depends upon the RANDAO mix of `epoch`, so the result may change
+    /// until `epoch` ends. Results are cached for the most recent head and epoch.
+    pub fn compute_next_shuffling(&self, epoch: Epoch) -> Result<Vec<Vec<usize>>, Error> {
+        let head = self.head();
+
+        if let Some((root, cached_epoch, shuffling)) = &*self.next_shuffling_cache.read() {
+            if *root == head.beacon_block_root && *cached_epoch == epoch {
+                return Ok(shuffling.clone());
+            }
+        }
+
+        let next_epoch = epoch + 1;
+        let committee_cache =
+            CommitteeCache::initialized(&head.beacon_state, next_epoch, &self.spec)?;
+
+        let shuffling = next_epoch
+            .slot_iter(T::EthSpec::slots_per_epoch())
+            .map(|slot| {
+                committee_cache
+                    .get_crosslink_committees_for_slot(slot)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .flat_map(|crosslink_committee| crosslink_committee.committee)
+                    .collect()
+            })
+            .collect::<Vec<Vec<usize>>>();
+
+        *self.next_shuffling_cache.write() =
+            Some((head.beacon_block_root, epoch, shuffling.clone()));
+
+        Ok(shuffling)
+    }
+
+    /// Returns a Merkle proof of the position of `validator_index` in the shuffling of `epoch`,
+    /// which must be the previous, current or next epoch of the head state.
+    ///
+    /// The leaves of the tree are the shuffled validator indices (as little-endian 32-byte
+    /// words), padded with zero leaves to a power of two. The root of the proof is
+    /// `hash(shuffling_root ++ seed)`, so the final element of the branch is the shuffling seed
+    /// and the proof verifies with a depth one greater than that of the shuffling tree.
+    pub fn compute_shuffling_proof(
+        &self,
+        validator_index: usize,
+        epoch: Epoch,
+    ) -> Result<Vec<Hash256>, Error> {
+        let head = self.head();
+
+        let committee_cache = CommitteeCache::initialized(&head.beacon_state, epoch, &self.spec)?;
+        let shuffling = committee_cache.shuffling();
+
+        let position = shuffling
+            .iter()
+            .position(|&index| index == validator_index)
+            .ok_or_else(|| Error::ValidatorNotActive {
+                validator_index,
+                epoch,
+            })?;
+
+        let leaves = shuffling
+            .iter()
+            .map(|&index| {
+                let mut leaf = Hash256::zero();
+                leaf.as_bytes_mut()[0..8].copy_from_slice(&(index as u64).to_le_bytes());
+                leaf
+            })
+            .collect::<Vec<_>>();
+        let depth = leaves.len().next_power_of_two().trailing_zeros() as usize;
+
+        let (mut branch, _shuffling_root) = merkle_branch(&leaves, position, depth);
+
+        let cached_seed = match &*self.shuffling_seed_cache.read() {
+            Some((root, cached_epoch, seed))
+                if *root == head.beacon_block_root && *cached_epoch == epoch =>
+            {
+                Some(*seed)
+            }
+            _ => None,
+        };
+        let seed = match cached_seed {
+            Some(seed) => seed,
+            None => {
+                let seed = head.beacon_state.generate_seed(epoch, &self.spec)?;
+                *self.shuffling_seed_cache.write() = Some((head.beacon_block_root, epoch, seed));
+                seed
+            }
+        };
+        branch.push(seed);
+
+        Ok(branch)
+    }
+
+    /// Returns the attestation subnets that `validator_index` will be assigned to in the epoch
+    /// after that of the present head, so it may subscribe to them ahead of time.
+    pub fn compute_next_epoch_participation_flags(
+        &self,
+        validator_index: usize,
+    ) -> Result<AttestationSubnetFlags, Error> {
+        let next_epoch = self.head().beacon_state.next_epoch();
+
+        self.compute_participation_flags(validator_index, next_epoch)
+    }
+
+    /// Returns the attestation subnets that `validator_index` is assigned to in `epoch`, which
+    /// must be in the previous, current or next epoch of the present head.
+    pub fn compute_participation_flags(
+        &self,
+        validator_index: usize,
+        epoch: Epoch,
+    ) -> Result<AttestationSubnetFlags, Error> {
+        let committee_cache =
+            CommitteeCache::initialized(&self.head().beacon_state, epoch, &self.spec)?;
+
+        let duty = committee_cache
+            .get_attestation_duties(validator_index)
+            .ok_or_else(|| Error::ValidatorNotActive {
+                validator_index,
+                epoch,
+            })?;
+
+        let mut flags = AttestationSubnetFlags::default();
+        flags.insert_shard(duty.shard);
+
+        Ok(flags)
+    }
+
+    /// Returns the `(shard, validator_indices)` of each crosslink committee at `slot`, which must
+    /// be in the previous, current or next epoch of the present state.
+    ///
+    /// The committees of the whole epoch are computed at once and cached for the most recent head
+    /// and epoch.
+    pub fn get_beacon_committees_at_slot(
+        &self,
+        slot: Slot,
+    ) -> Result<Vec<(u64, Vec<usize>)>, Error> {
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        let epoch = slot.epoch(slots_per_epoch);
+        let slot_index = (slot - epoch.start_slot(slots_per_epoch)).as_usize();
+        let head_block_root = self.head().beacon_block_root;
+
+        if let Some((root, cached_epoch, committees)) = &*self.beacon_committees_cache.read() {
+            if *root == head_block_root && *cached_epoch == epoch {
+                return Ok(committees[slot_index].clone());
+            }
+        }
+
+        let committee_cache = CommitteeCache::initialized(&*self.state.read(), epoch, &self.spec)?;
+
+        let committees = epoch
+            .slot_iter(slots_per_epoch)
+            .map(|slot| {
+                committee_cache
+                    .get_crosslink_committees_for_slot(slot)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|crosslink_committee| {
+                        (crosslink_committee.shard, crosslink_committee.committee)
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<_>>>();
+        let slot_committees = committees[slot_index].clone();
+
+        *self.beacon_committees_cache.write() = Some((head_block_root, epoch, committees));
+
+        Ok(slot_committees)
+    }
+
+    /// Returns the `Fork` to sign messages with at `epoch`, which may be in the future.
+    ///
+    /// No fork upgrades are scheduled in the `ChainSpec`, so this is the fork of the head state.
+    /// Its `get_fork_version` gives the correct version for any epoch up to `far_future_epoch`.
+    pub fn get_fork_at_epoch(&self, epoch: Epoch) -> Result<Fork, Error> {
+        if epoch >= self.spec.far_future_epoch {
+            return Err(Error::EpochTooFarInFuture(epoch));
+        }
+
+        Ok(self.head().beacon_state.fork.clone())
+    }
+
+    /// Returns the `LightClientUpdate` for `period`, where periods are `epochs_per_shard_period`
+    /// epochs long (the period of the persistent committees, which stand in for sync
+    /// committees).
+    ///
+    /// The attested header is that of the latest canonical block in the period, or of the head
+    /// block if the period is the current one.
+    pub fn get_light_client_update(&self, period: u64) -> Result<LightClientUpdate, Error> {
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        let head_block_root = self.head().beacon_block_root;
+        let head_slot = self.head().beacon_block.slot;
+
+        let current_period =
+            head_slot.epoch(slots_per_epoch).as_u64() / self.spec.epochs_per_shard_period;
+        if period > current_period {
+            return Err(Error::PeriodNotStarted {
+                period,
+                current_period,
+            });
+        }
+
+        let last_slot_in_period = Epoch::new((period + 1) * self.spec.epochs_per_shard_period)
+            .start_slot(slots_per_epoch)
+            - 1;
+
+        let (mut iter, _) = self.rev_iter_block_roots_from(head_block_root)?;
+        let block_root = iter
+            .find(|(_, slot)| *slot <= last_slot_in_period)
+            .map(|(root, _)| root)
+            .ok_or_else(|| Error::NoStateForSlot(last_slot_in_period))?;
+        let block = self
+            .get_block(&block_root)?
+            .ok_or_else(|| Error::MissingBeaconBlock(block_root))?;
+
+        Ok(LightClientUpdate {
+            attested_header: block.block_header(),
+            sync_committee_branch: vec![],
+            finality_branch: vec![],
+        })
+    }
+
+    /// Verifies `message` and stores it for inclusion in the next `SyncAggregate`.
+    ///
+    /// Messages from earlier committee periods are rejected, as are new messages once
+    /// `MAX_SYNC_COMMITTEE_MESSAGES` are stored. A message identical to a stored one is ignored.
+    pub fn process_sync_committee_message(
+        &self,
+        message: SyncCommitteeMessage,
+    ) -> Result<(), Error> {
+        let current_period = {
+            let head = self.head();
+            let state = &head.beacon_state;
+
+            let pubkey = &state
+                .validator_registry
+                .get(message.validator_index as usize)
+                .ok_or_else(|| SyncAggregateError::UnknownValidator(message.validator_index))?
+                .pubkey;
+            let domain = self.spec.get_domain(
+                message.slot.epoch(T::EthSpec::slots_per_epoch()),
+                Domain::SyncCommittee,
+                &state.fork,
+            );
+            if !message
+                .signature
+                .verify(message.beacon_block_root.as_bytes(), domain, pubkey)
+            {
+                return Err(SyncAggregateError::InvalidSignature.into());
+            }
+
+            self.sync_committee_period(state.slot)
+        };
+
+        let period = self.sync_committee_period(message.slot);
+        if period < current_period {
+            return Err(SyncAggregateError::PastPeriod {
+                period,
+                current_period,
+            }
+            .into());
+        }
+
+        let mut messages = self.sync_committee_messages.lock();
+        if messages.contains(&message) {
+            return Ok(());
+        }
+
+        messages.retain(|stored| self.sync_committee_period(stored.slot) >= current_period);
+        if messages.len() >= MAX_SYNC_COMMITTEE_MESSAGES {
+            return Err(SyncAggregateError::MessagePoolFull.into());
+        }
+        messages.push(message);
+
+        Ok(())
+    }
+
+    /// Returns the sync committee period of `slot`.
+    fn sync_committee_period(&self, slot: Slot) -> u64 {
+        slot.epoch(T::EthSpec::slots_per_epoch()).as_u64() / self.spec.epochs_per_shard_period
+    }
+
+    /// Aggregates the stored `SyncCommitteeMessage`s of the current committee period that sign the
+    /// head block root.
+    ///
+    /// There are no sync committees in this version of the spec, so the committee is taken to be
+    /// all validators active in the current epoch. At least two thirds of it must participate.
+    /// Signatures were verified by `process_sync_committee_message`; messages from earlier periods
+    /// are discarded.
+    pub fn produce_sync_aggregate(&self) -> Result<SyncAggregate, Error> {
+        let head = self.head();
+        let state = &head.beacon_state;
+
+        let current_period = self.sync_committee_period(state.slot);
+
+        let committee = state.get_active_validator_indices(state.current_epoch());
+        let mut sync_committee_bits = Bitfield::with_capacity(committee.len());
+        let mut sync_committee_signature = AggregateSignature::new();
+        let mut participants = 0;
+
+        let mut messages = self.sync_committee_messages.lock();
+        messages.retain(|message| self.sync_committee_period(message.slot) >= current_period);
+
+        for message in messages.iter() {
+            if self.sync_committee_period(message.slot) != current_period
+                || message.beacon_block_root != head.beacon_block_root
+            {
+                continue;
+            }
+
+            if let Ok(position) = committee.binary_search(&(message.validator_index as usize)) {
+                if sync_committee_bits.set(position, true) != Some(true) {
+                    sync_committee_signature.add(&message.signature);
+                    participants += 1;
+                }
+            }
+        }
+
+        let required = (committee.len() * 2 + 2) / 3;
+        if participants < required {
+            return Err(SyncAggregateError::InsufficientParticipation {
+                got: participants,
+                required,
+            }
+            .into());
+        }
+
+        Ok(SyncAggregate {
+            sync_committee_bits,
+            sync_committee_signature,
+        })
+    }
+
+    /// Returns the attributes an execution engine requires to build the payload for `slot`.
+    ///
+    /// This is a seam for an Engine API client. The spec implemented here has no Merge fork, so
+    /// every slot is pre-Merge and an error is always returned.
+    pub fn prepare_execution_payload(
+        &self,
+        _slot: Slot,
+    ) -> Result<ExecutionPayloadAttributes, Error> {
+        Err(Error::NotSupportedBeforeMerge)
+    }
+
+    /// Returns the header of the execution payload for a blinded block at `slot`.
+    ///
+    /// There is no relay client, so the header is always built locally. No Merge fork is scheduled
+    /// in this version of the spec, so every block carries the empty (default) payload and that
+    /// payload's header is returned.
+    pub fn get_execution_payload_header(
+        &self,
+        _slot: Slot,
+    ) -> Result<ExecutionPayloadHeader, Error> {
+        Ok(ExecutionPayloadHeader::from(&ExecutionPayload::default()))
+    }
+
+    /// Returns the EIP-4844 blob sidecars stored for the block at `block_root`, ordered by index.
+    ///
+    /// Blocks without blobs (including all pre-Deneb blocks) return an empty `Vec`.
+    pub fn get_blob_sidecars(&self, block_root: Hash256) -> Result<Vec<BlobSidecar>, Error> {
+        if !self.store.exists::<BeaconBlock>(&block_root)? {
+            return Err(Error::MissingBeaconBlock(block_root));
+        }
+
+        Ok(self.store.get_blob_sidecars(&block_root)?)
+    }
+
+    /// Returns the block at the given root, if any.
+    ///
+    /// ## Errors
+    ///
+    /// May return a database error.
+    pub fn get_block(&self, block_root: &Hash256) -> Result<Option<BeaconBlock>, Error> {
+        Ok(self.store.get(block_root)?)
+    }
+
+    /// Returns the root of the latest block that is an ancestor of (or equal to) both `root_a` and
+    /// `root_b`.
+    ///
+    /// Both chains are walked backwards in lockstep, slot-by-slot, for at most
+    /// `FINALIZED_CHECKPOINT_DEPTH` slots.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if either block (or its state) is not in the `Store`, or if no common
+    /// ancestor is found within the search depth.
+    pub fn find_common_ancestor(&self, root_a: Hash256, root_b: Hash256) -> Result<Hash256, Error> {
+        let (iter_a, slot_a) = self.rev_iter_block_roots_from(root_a)?;
+        let (iter_b, slot_b) = self.rev_iter_block_roots_from(root_b)?;
+
+        // Start both iterators from the same slot so that each step compares like with like.
+        let start_slot = std::cmp::min(slot_a, slot_b);
+
+        iter_a
+            .skip_while(|(_, slot)| *slot > start_slot)
+            .zip(iter_b.skip_while(|(_, slot)| *slot > start_slot))
+            .take(FINALIZED_CHECKPOINT_DEPTH as usize)
+            .find(|((a, _), (b, _))| a == b)
+            .map(|((root, _), _)| root)
+            .ok_or_else(|| Error::NoCommonAncestor { root_a, root_b })
+    }
+
+    /// Returns an iterator over `(block_root, slot)` from the block at `block_root` back to
+    /// genesis, along with the slot of that block.
+    ///
+    /// Contains duplicate roots when skip slots are encountered.
+    fn rev_iter_block_roots_from(
+        &self,
+        block_root: Hash256,
+    ) -> Result<(impl Iterator<Item = (Hash256, Slot)>, Slot), Error> {
+        let block = self
+            .get_block(&block_root)?
+            .ok_or_else(|| Error::MissingBeaconBlock(block_root))?;
+        let state: BeaconState<T::EthSpec> = self
+            .store
+            .get(&block.state_root)?
+            .ok_or_else(|| Error::MissingBeaconState(block.state_root))?;
+
+        // The block's own state does not yet contain its root, so it is yielded first.
+        let prior_roots = if block.slot > 0 {
+            Some(BlockRootsIterator::owned(
+                self.store.clone(),
+                state,
+                block.slot - 1,
+            ))
+        } else {
+            None
+        };
+
+        let iter =
+            std::iter::once((block_root, block.slot)).chain(prior_roots.into_iter().flatten());
+
+        Ok((iter, block.slot))
+    }
+
+    /// Returns a read-lock guarded `BeaconState` which is the `canonical_head` that has been
+    /// updated to match the current slot clock.
+    pub fn current_state(&self) -> RwLockReadGuard<BeaconState<T::EthSpec>> {
+        self.state.read()
+    }
+
+    /// Returns a read-lock guarded `CheckPoint` struct for reading the head (as chosen by the
+    /// fork-choice rule).
+    ///
+    /// It is important to note that the `beacon_state` returned may not match the present slot. It
+    /// is the state as it was when the head block was received, which could be some slots prior to
+    /// now.
+    pub fn head(&self) -> RwLockReadGuard<CheckPoint<T::EthSpec>> {
+        self.canonical_head.read()
+    }
+
+    /// Returns the slot of the highest block in the canonical chain.
+    pub fn best_slot(&self) -> Slot {
+        self.canonical_head.read().beacon_block.slot
+    }
+
+    /// Returns the slot of the oldest block available in the store.
+    ///
+    /// This is the genesis slot, unless the chain was anchored at a trusted block (see
+    /// `ClientGenesis::TrustedBlockRoot`), in which case it is the slot of that block.
+    pub fn tail_slot(&self) -> Slot {
+        self.tail_slot
+    }
+
+    /// Returns the root of the genesis block.
+    pub fn genesis_block_root(&self) -> Hash256 {
+        self.genesis_block_root
+    }
+
+    /// Returns the tree hash root of the validator registry of the genesis state.
+    pub fn genesis_validators_root(&self) -> Hash256 {
+        self.genesis_validators_root
+    }
+
+    /// Returns the signature domain for `domain_type` at `epoch`, using the fork of the head state.
+    pub fn compute_domain(&self, domain_type: Domain, epoch: Epoch) -> u64 {
+        self.spec
+            .get_domain(epoch, domain_type, &self.head().beacon_state.fork)
+    }
+
+    /// Returns the eth1 data this node votes for when building on the head.
+    ///
+    /// Without an eth1 backend the vote is derived from the head state alone, so all nodes with
+    /// the same genesis vote identically. See `compute_eth1_data`.
+    pub fn compute_head_eth1_data(&self) -> Eth1Data {
+        self.compute_eth1_data(&self.head().beacon_state)
+    }
+
+    /// Returns a deterministic eth1 data vote for a block built upon `state`.
+    ///
+    /// The deposit count and root are those of `state`, so no new deposits are required, unless a
+    /// `junk_eth1_data_interval` is set. Even then, the count only advances over deposits in the
+    /// operation pool, voting for the root their proofs are against, so a winning vote never
+    /// requires deposits this node cannot include. The eth1 block hash is the hash of the genesis
+    /// fork version and the deposit count.
+    fn compute_eth1_data(&self, state: &BeaconState<T::EthSpec>) -> Eth1Data {
+        let mut deposit_root = state.latest_eth1_data.deposit_root;
+        let mut deposit_count = state.latest_eth1_data.deposit_count;
+
+        if let Some(interval) = self.junk_eth1_data_interval {
+            // Count from the start of the voting period so every vote in the period agrees.
+            let voting_period_start =
+                state.slot - state.slot % self.spec.slots_per_eth1_voting_period;
+            let junk_deposit_count =
+                (voting_period_start - self.spec.genesis_slot).as_u64() / interval;
+
+            if let Some((known_root, known_count)) = self.known_deposit_tree(state) {
+                let count = std::cmp::min(junk_deposit_count, known_count);
+                if count > deposit_count {
+                    deposit_root = known_root;
+                    deposit_count = count;
+                }
+            }
+        }
+
+        let genesis_fork_version =
+            Fork::genesis(self.spec.genesis_slot.epoch(T::EthSpec::slots_per_epoch()))
+                .current_version;
+
+        let preimage = [&genesis_fork_version[..], &deposit_count.to_le_bytes()[..]].concat();
+
+        Eth1Data {
+            deposit_root,
+            deposit_count,
+            block_hash: Hash256::from_slice(&hash(&preimage)),
+        }
+    }
+
+    /// Returns the deposit root and count described by the deposits in the operation pool that
+    /// follow `state.deposit_index`, or `None` if there are none.
+    ///
+    /// Deposits are taken in order while each has a proof against the same root as the first.
+    fn known_deposit_tree(&self, state: &BeaconState<T::EthSpec>) -> Option<(Hash256, u64)> {
+        let depth = self.spec.deposit_contract_tree_depth as usize;
+        let mut known: Option<(Hash256, u64)> = None;
+
+        for deposit in self.op_pool.get_deposits(state, &self.spec) {
+            if deposit.proof.len() != depth {
+                break;
+            }
+
+            let leaf = Hash256::from_slice(&deposit.data.tree_hash_root());
+            let root =
+                merkle_root_from_branch(leaf, &deposit.proof[..], depth, deposit.index as usize);
+
+            match known {
+                Some((known_root, _)) if known_root != root => break,
+                _ => known = Some((root, deposit.index + 1)),
+            }
+        }
+
+        known
+    }
+
+    /// Returns the deposits from the operation pool that a block voting for `eth1_data` must
+    /// include when built upon `state`.
+    ///
+    /// If the vote wins, the block must include deposits up to its count, otherwise up to the
+    /// count of `state.latest_eth1_data`.
+    fn get_block_deposits(
+        &self,
+        state: &BeaconState<T::EthSpec>,
+        eth1_data: &Eth1Data,
+    ) -> Vec<Deposit> {
+        let votes = state
+            .eth1_data_votes
+            .iter()
+            .filter(|vote| *vote == eth1_data)
+            .count() as u64
+            + 1;
+
+        let deposit_count = if votes * 2 > self.spec.slots_per_eth1_voting_period {
+            eth1_data.deposit_count
+        } else {
+            state.latest_eth1_data.deposit_count
+        };
+
+        let mut deposits = self.op_pool.get_deposits(state, &self.spec);
+        deposits.truncate(deposit_count.saturating_sub(state.deposit_index) as usize);
+        deposits
+    }
+
+    /// Returns the deposit tree snapshot of the head state, being the `latest_eth1_data` (deposit
+    /// root, deposit count and eth1 block hash) and the index of the next deposit to be processed.
+    pub fn get_deposit_snapshot(&self) -> (Eth1Data, u64) {
+        let state = &self.head().beacon_state;
+
+        (state.latest_eth1_data.clone(), state.deposit_index)
+    }
+
+    /// Returns the number of votes for each distinct `Eth1Data` in the current eth1 voting period
+    /// of the head state.
+    pub fn get_eth1_data_vote_tally(&self) -> HashMap<Eth1Data, usize> {
+        let mut tally = HashMap::new();
+
+        for vote in &self.head().beacon_state.eth1_data_votes {
+            *tally.entry(vote.clone()).or_insert(0) += 1;
+        }
+
+        tally
+    }
+
+    /// Sends `payload` to the configured execution engine (via `engine_newPayload`) and returns
+    /// a future of its verdict.
+    ///
+    /// The returned future must be run on a tokio executor.
+    pub fn notify_new_payload(
+        &self,
+        payload: &ExecutionPayload,
+    ) -> impl Future<Item = PayloadStatus, Error = Error> {
+        match &self.execution_engine {
+            Some(execution_engine) => Either::A(execution_engine.new_payload(payload).from_err()),
+            None => Either::B(future::err(Error::NoExecutionEngine)),
+        }
+    }
+
+    /// Returns `true` if `bid` is signed by `bid.pubkey` and its value is at least the configured
+    /// minimum (see `with_min_builder_bid_value`).
+    ///
+    /// Invalid bids return `Ok(false)` rather than an error.
+    pub fn compute_builder_bid_validity(&self, bid: &BuilderBid) -> Result<bool, Error> {
+        if bid.value < self.min_builder_bid_value {
+            return Ok(false);
+        }
+
+        let state = self.state.read();
+        let domain = self
+            .spec
+            .get_domain(state.current_epoch(), Domain::BuilderBid, &state.fork);
+
+        Ok(bid
+            .signature
+            .verify(&bid.signed_root()[..], domain, &bid.pubkey))
+    }
+
+    /// Returns the withdrawal credentials of the validator at `index` in the head state, if any.
+    pub fn get_validator_withdrawal_credentials(&self, index: usize) -> Option<Hash256> {
+        self.head()
+            .beacon_state
+            .validator_registry
+            .get(index)
+            .map(|validator| validator.withdrawal_credentials)
+    }
+
+    /// Returns the withdrawal credentials of the validator at each of `indices`, as
+    /// `get_validator_withdrawal_credentials`, reading all of them from the same head state.
+    pub fn get_validators_withdrawal_credentials(&self, indices: &[usize]) -> Vec<Option<Hash256>> {
+        let head = self.head();
+
+        indices
+            .iter()
+            .map(|&index| {
+                head.beacon_state
+                    .validator_registry
+                    .get(index)
+                    .map(|validator| validator.withdrawal_credentials)
+            })
+            .collect()
+    }
+
+    /// Returns the epoch at which the validator at `validator_index` would exit if it initiated a
+    /// voluntary exit in the head state, or its present exit epoch if it has already initiated one.
+    ///
+    /// The exit queue is computed on a copy of the head state, which is not modified.
+    pub fn compute_voluntary_exit_epoch(&self, validator_index: usize) -> Result<Epoch, Error> {
+        let mut state = self.head().beacon_state.clone();
+
+        state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+        // The exit cache is not stored with the state, so rebuild it from the registry.
+        state.exit_cache = Default::default();
+        state
+            .exit_cache
+            .build_from_registry(&state.validator_registry, &self.spec);
+
+        initiate_validator_exit(&mut state, validator_index, &self.spec)?;
+
+        Ok(state.validator_registry[validator_index].exit_epoch)
+    }
+
+    /// Returns the maximum number of validators that may be activated (or exited) in `epoch`,
+    /// computed from the validators active at `epoch` in the head state.
+    pub fn compute_activation_churn_limit(&self, epoch: Epoch) -> u64 {
+        let active_validator_count = self
+            .head()
+            .beacon_state
+            .validator_registry
+            .iter()
+            .filter(|validator| validator.is_active_at(epoch))
+            .count() as u64;
+
+        std::cmp::max(
+            self.spec.min_per_epoch_churn_limit,
+            active_validator_count / self.spec.churn_limit_quotient,
+        )
+    }
+
+    /// Estimates the epoch at which the validator at `validator_index` will activate, or returns
+    /// its activation epoch if one has already been assigned.
+    ///
+    /// See `activation_queue::expected_activation_epoch` for the assumptions of the estimate.
+    pub fn compute_expected_activation_epoch(
+        &self,
+        validator_index: usize,
+    ) -> Result<Epoch, Error> {
+        expected_activation_epoch(&self.head().beacon_state, validator_index, &self.spec)
+    }
+
+    /// Returns the status of each of `pubkeys` in the head state, in the same order.
+    ///
+    /// Unknown validators are reported with no `index` and default values.
+    pub fn list_attached_validators(&self, pubkeys: &[PublicKey]) -> Vec<ValidatorStatus> {
+        let state = &self.head().beacon_state;
+
+        let mut pending_attestations: HashMap<usize, usize> = HashMap::new();
+        for attestation in state
+            .previous_epoch_attestations
+            .iter()
+            .chain(state.current_epoch_attestations.iter())
+        {
+            // Attestations from epochs without a committee cache cannot be attributed.
+            if let Ok(indices) = get_attesting_indices_unsorted(
+                state,
+                &attestation.data,
+                &attestation.aggregation_bitfield,
+            ) {
+                for index in indices {
+                    *pending_attestations.entry(index).or_insert(0) += 1;
+                }
+            }
+        }
+
+        pubkeys
+            .iter()
+            .map(|pubkey| {
+                let index = state.get_validator_index(pubkey).unwrap_or_else(|_| {
+                    state
+                        .validator_registry
+                        .iter()
+                        .position(|validator| validator.pubkey == *pubkey)
+                });
+
+                match index.and_then(|i| state.validator_registry.get(i).map(|v| (i, v))) {
+                    Some((index, validator)) => ValidatorStatus {
+                        pubkey: pubkey.clone(),
+                        index: Some(index),
+                        balance_gwei: state.balances.get(index).cloned().unwrap_or(0),
+                        is_active: validator.is_active_at(state.current_epoch()),
+                        is_slashed: validator.slashed,
+                        pending_attestations: pending_attestations
+                            .get(&index)
+                            .cloned()
+                            .unwrap_or(0),
+                    },
+                    None => ValidatorStatus {
+                        pubkey: pubkey.clone(),
+                        index: None,
+                        balance_gwei: 0,
+                        is_active: false,
+                        is_slashed: false,
+                        pending_attestations: 0,
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the attestation participation for `epoch`, which must be prior to the current
+    /// epoch.
+    ///
+    /// Participation is read from the state at the last slot of `epoch + 1`, or from the current
+    /// state if that slot has not yet been reached. Results are cached once `epoch` is finalized, for
+    /// a bounded number of epochs.
+    pub fn get_validator_inclusion_data(
+        &self,
+        epoch: Epoch,
+    ) -> Result<ValidatorInclusionData, Error> {
+        if let Some(data) = self.validator_inclusion_cache.get(epoch) {
+            return Ok(data);
+        }
+
+        let state = self.epoch_boundary_state(epoch)?;
+
+        let mut statuses =
+            ValidatorStatuses::new(&state, &self.spec).map_err(Error::BeaconStateError)?;
+        statuses
+            .process_attestations(&state, &self.spec)
+            .map_err(Error::BeaconStateError)?;
+
+        let data = ValidatorInclusionData {
+            participating_validators: statuses
+                .statuses
+                .iter()
+                .filter(|status| status.is_previous_epoch_attester)
+                .count(),
+            total_validators: statuses
+                .statuses
+                .iter()
+                .filter(|status| status.is_active_in_previous_epoch)
+                .count(),
+            attesting_balance_gwei: statuses.total_balances.previous_epoch_attesters,
+            total_balance_gwei: statuses.total_balances.previous_epoch,
+        };
+
+        if epoch < self.head().beacon_state.finalized_epoch {
+            self.validator_inclusion_cache.insert(epoch, data.clone());
+        }
+
+        Ok(data)
+    }
+
+    /// Returns the rewards and penalties of each validator for attesting in `epoch`, which must be
+    /// prior to the current epoch, as applied by epoch processing at the end of `epoch + 1`.
+    ///
+    /// Crosslink rewards are not included.
+    pub fn compute_rewards_and_penalties(&self, epoch: Epoch) -> Result<Vec<EpochRewards>, Error> {
+        let spec = &self.spec;
+        let state = self.epoch_boundary_state(epoch)?;
+
+        let mut statuses = ValidatorStatuses::new(&state, spec)?;
+        statuses.process_attestations(&state, spec)?;
+
+        let finality_delay = (state.previous_epoch() - state.finalized_epoch).as_u64();
+        let proposer_rewards = get_proposer_rewards(&state, &statuses, spec)?;
+
+        statuses
+            .statuses
+            .iter()
+            .zip(proposer_rewards)
+            .enumerate()
+            .map(
+                |(validator_index, (status, proposer_reward))| -> Result<_, Error> {
+                    let base_reward = get_base_reward(
+                        &state,
+                        validator_index,
+                        statuses.total_balances.current_epoch,
+                        spec,
+                    )?;
+                    let delta = get_attestation_delta_components(
+                        status,
+                        &statuses.total_balances,
+                        base_reward,
+                        finality_delay,
+                        spec,
+                    );
+
+                    Ok(EpochRewards {
+                        validator_index,
+                        attestation_source_reward: delta.source,
+                        attestation_inclusion_delay_reward: delta.inclusion_delay,
+                        attestation_target_reward: delta.target,
+                        attestation_head_reward: delta.head,
+                        sync_committee_reward: 0,
+                        proposer_reward: proposer_reward as i64,
+                        inactivity_penalty: delta.inactivity,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    /// Returns the state at the last slot of `epoch + 1`, in which `epoch` is the previous epoch,
+    /// or the current state if that slot has not yet been reached. All caches are built.
+    ///
+    /// Returns an error if `epoch` is not prior to the current epoch.
+    fn epoch_boundary_state(&self, epoch: Epoch) -> Result<BeaconState<T::EthSpec>, Error> {
+        let current_state = self.state.read().clone();
+
+        if epoch >= current_state.current_epoch() {
+            return Err(Error::EpochNotComplete {
+                epoch,
+                current_epoch: current_state.current_epoch(),
+            });
+        }
+
+        let boundary_slot = (epoch + 2).start_slot(T::EthSpec::slots_per_epoch()) - 1;
+
+        let mut state = if boundary_slot >= current_state.slot {
+            current_state
+        } else {
+            let state_root = self
+                .rev_iter_state_roots(current_state.slot - 1)
+                .find(|(_root, slot)| *slot == boundary_slot)
+                .map(|(root, _slot)| root)
+                .ok_or_else(|| Error::NoStateForSlot(boundary_slot))?;
+
+            self.store
+                .get::<BeaconState<T::EthSpec>>(&state_root)?
+                .ok_or_else(|| Error::MissingBeaconState(state_root))?
+        };
+
+        state
+            .build_all_caches(&self.spec)
+            .map_err(Error::BeaconStateError)?;
+
+        Ok(state)
+    }
+
+    /// Returns the data that determines the reward for `attestation`, assuming it is included in
+    /// a block at the slot after the canonical head.
     ///
-    /// Returns `None` for roots prior to genesis or when there is an error reading from `Store`.
+    /// ## Errors
     ///
-    /// Contains duplicate roots when skip slots are encountered.
-    pub fn rev_iter_best_block_roots(
+    /// Returns an error if the attestation's target epoch is neither the previous nor current
+    /// epoch of that slot, or its shard is not attested to at that epoch.
+    pub fn get_attestation_reward_data(
         &self,
-        slot: Slot,
-    ) -> BestBlockRootsIterator<T::EthSpec, T::Store> {
-        BestBlockRootsIterator::owned(self.store.clone(), self.state.read().clone(), slot)
-    }
+        attestation: &Attestation,
+    ) -> Result<AttestationRewardData, Error> {
+        let spec = &self.spec;
+        let data = &attestation.data;
 
-    /// Iterates in reverse (highest to lowest slot) through all state roots from `slot` through to
-    /// genesis.
-    ///
-    /// Returns `None` for roots prior to genesis or when there is an error reading from `Store`.
-    pub fn rev_iter_state_roots(&self, slot: Slot) -> StateRootsIterator<T::EthSpec, T::Store> {
-        StateRootsIterator::owned(self.store.clone(), self.state.read().clone(), slot)
+        let mut state = self.head().beacon_state.clone();
+        state.build_committee_cache(RelativeEpoch::Next, spec)?;
+        per_slot_processing(&mut state, spec)?;
+        state.build_all_caches(spec)?;
+
+        let (justified_epoch, justified_root) = if data.target_epoch == state.current_epoch() {
+            (state.current_justified_epoch, state.current_justified_root)
+        } else {
+            (
+                state.previous_justified_epoch,
+                state.previous_justified_root,
+            )
+        };
+        let source_correct =
+            data.source_epoch == justified_epoch && data.source_root == justified_root;
+
+        let target_slot = data.target_epoch.start_slot(T::EthSpec::slots_per_epoch());
+        let target_correct = state
+            .get_block_root(target_slot)
+            .map_or(false, |root| *root == data.target_root);
+
+        let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), data.target_epoch)
+            .map_err(BeaconStateError::from)?;
+        let committee = state.get_crosslink_committee_for_shard(data.shard, relative_epoch)?;
+        let attestation_slot = committee.slot;
+        let head_correct = state
+            .get_block_root(attestation_slot)
+            .map_or(false, |root| *root == data.beacon_block_root);
+
+        let attesters =
+            get_attesting_indices_unsorted(&state, data, &attestation.aggregation_bitfield)?;
+
+        Ok(AttestationRewardData {
+            source_correct,
+            target_correct,
+            head_correct,
+            inclusion_delay: (state.slot - attestation_slot).as_u64(),
+            participation_fraction: attesters.len() as f64 / committee.committee.len() as f64,
+        })
     }
 
-    /// Returns the block at the given root, if any.
+    /// Returns the rewards earned by the proposer of the block at `block_root`.
+    ///
+    /// Rewards are computed against the parent state advanced to the slot of the block. The
+    /// attestation reward is the proposer's share of the base reward of each validator attesting
+    /// in the block, as later applied during epoch processing. It may overstate the reward if an
+    /// attestation was already included in an earlier block.
     ///
     /// ## Errors
     ///
-    /// May return a database error.
-    pub fn get_block(&self, block_root: &Hash256) -> Result<Option<BeaconBlock>, Error> {
-        Ok(self.store.get(block_root)?)
-    }
+    /// Returns an error if the block, its parent or the parent state are not in the `Store`.
+    pub fn get_block_rewards(&self, block_root: Hash256) -> Result<BlockRewards, Error> {
+        let spec = &self.spec;
 
-    /// Returns a read-lock guarded `BeaconState` which is the `canonical_head` that has been
-    /// updated to match the current slot clock.
-    pub fn current_state(&self) -> RwLockReadGuard<BeaconState<T::EthSpec>> {
-        self.state.read()
+        let block = self
+            .get_block(&block_root)?
+            .ok_or_else(|| Error::MissingBeaconBlock(block_root))?;
+        let parent_block = self
+            .get_block(&block.previous_block_root)?
+            .ok_or_else(|| Error::MissingBeaconBlock(block.previous_block_root))?;
+        let mut state = self
+            .store
+            .get::<BeaconState<T::EthSpec>>(&parent_block.state_root)?
+            .ok_or_else(|| Error::MissingBeaconState(parent_block.state_root))?;
+
+        while state.slot < block.slot {
+            state.build_committee_cache(RelativeEpoch::Next, spec)?;
+            per_slot_processing(&mut state, spec)?;
+        }
+        state.build_all_caches(spec)?;
+
+        let total_active_balance = state.get_total_balance(
+            &state.get_active_validator_indices(state.current_epoch()),
+            spec,
+        )?;
+
+        let mut attesters = HashSet::new();
+        for attestation in &block.body.attestations {
+            attesters.extend(get_attesting_indices_unsorted(
+                &state,
+                &attestation.data,
+                &attestation.aggregation_bitfield,
+            )?);
+        }
+        let attestation_reward_gwei = attesters.iter().try_fold(0, |acc, &index| {
+            get_base_reward(&state, index, total_active_balance, spec)
+                .map(|base_reward| acc + base_reward / spec.proposer_reward_quotient)
+        })?;
+
+        // The proposer is the whistleblower, so receives the entire whistleblowing reward.
+        let whistleblowing_reward = |index: usize| -> Result<u64, Error> {
+            Ok(state.get_effective_balance(index, spec)? / spec.whistleblowing_reward_quotient)
+        };
+
+        let proposer_slashing_reward_gwei =
+            block
+                .body
+                .proposer_slashings
+                .iter()
+                .try_fold(0, |acc, slashing| {
+                    whistleblowing_reward(slashing.proposer_index as usize).map(|r| acc + r)
+                })?;
+
+        let mut slashed = HashSet::new();
+        for slashing in &block.body.attester_slashings {
+            let indices = |a: &IndexedAttestation| -> HashSet<u64> {
+                a.custody_bit_0_indices
+                    .iter()
+                    .chain(a.custody_bit_1_indices.iter())
+                    .cloned()
+                    .collect()
+            };
+            let indices_1 = indices(&slashing.attestation_1);
+            let indices_2 = indices(&slashing.attestation_2);
+
+            slashed.extend(
+                indices_1
+                    .intersection(&indices_2)
+                    .map(|&index| index as usize)
+                    .filter(|&index| {
+                        state
+                            .validator_registry
+                            .get(index)
+                            .map_or(false, |validator| {
+                                validator.is_slashable_at(state.current_epoch())
+                            })
+                    }),
+            );
+        }
+        let attester_slashing_reward_gwei = slashed.iter().try_fold(0, |acc, &index| {
+            whistleblowing_reward(index).map(|r| acc + r)
+        })?;
+
+        Ok(BlockRewards {
+            total_gwei: attestation_reward_gwei
+                + proposer_slashing_reward_gwei
+                + attester_slashing_reward_gwei,
+            attestation_reward_gwei,
+            sync_aggregate_reward_gwei: 0,
+            proposer_slashing_reward_gwei,
+            attester_slashing_reward_gwei,
+        })
     }
 
-    /// Returns a read-lock guarded `CheckPoint` struct for reading the head (as chosen by the
-    /// fork-choice rule).
+    /// Returns the number of validators that are active in the current epoch.
     ///
-    /// It is important to note that the `beacon_state` returned may not match the present slot. It
-    /// is the state as it was when the head block was received, which could be some slots prior to
-    /// now.
-    pub fn head(&self) -> RwLockReadGuard<CheckPoint<T::EthSpec>> {
-        self.canonical_head.read()
-    }
+    /// The count is cached and only recomputed when the epoch of `self.state` changes.
+    pub fn count_active_validators(&self) -> usize {
+        let state = self.state.read();
+        let epoch = state.current_epoch();
 
-    /// Returns the slot of the highest block in the canonical chain.
-    pub fn best_slot(&self) -> Slot {
-        self.canonical_head.read().beacon_block.slot
+        self.active_validator_count.get_or_update(epoch, || {
+            state
+                .validator_registry
+                .iter()
+                .filter(|v| v.is_active_at(epoch))
+                .count()
+        })
     }
 
     /// Ensures the current canonical `BeaconState` has been transitioned to match the `slot_clock`.
@@ -399,6 +1920,50 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(index)
     }
 
+    /// Returns the index of the proposer of `block`, verifying that it is the expected proposer
+    /// for the block's slot.
+    ///
+    /// Blocks do not yet carry a `proposer_index`, so the block signature is verified against the
+    /// expected proposer instead. The block must be in the epoch of the current state.
+    pub fn verify_block_proposer(&self, block: &BeaconBlock) -> Result<usize, Error> {
+        self.verify_block_proposer_in_state(block, &self.state.read())
+    }
+
+    /// As `verify_block_proposer`, for callers already holding a lock on the current `state`.
+    fn verify_block_proposer_in_state(
+        &self,
+        block: &BeaconBlock,
+        state: &BeaconState<T::EthSpec>,
+    ) -> Result<usize, Error> {
+        if block.slot > state.slot {
+            return Err(Error::BlockSlotInFuture {
+                block_slot: block.slot,
+                present_slot: state.slot,
+            });
+        }
+
+        let proposer_index =
+            state.get_beacon_proposer_index(block.slot, RelativeEpoch::Current, &self.spec)?;
+        let domain = self.spec.get_domain(
+            block.slot.epoch(T::EthSpec::slots_per_epoch()),
+            Domain::BeaconProposer,
+            &state.fork,
+        );
+
+        if block.signature.verify(
+            &block.signed_root()[..],
+            domain,
+            &state.validator_registry[proposer_index].pubkey,
+        ) {
+            Ok(proposer_index)
+        } else {
+            Err(Error::IncorrectBlockProposer {
+                block_slot: block.slot,
+                expected_proposer: proposer_index,
+            })
+        }
+    }
+
     /// Returns the attestation slot and shard for a given validator index.
     ///
     /// Information is read from the current state, so only information from the present and prior
@@ -532,6 +2097,209 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         result
     }
 
+    /// As per `process_attestation`, but for an attestation already verified by
+    /// `verify_aggregate_attestation`, so its signature is not verified again.
+    pub fn process_verified_attestation(
+        &self,
+        verified: VerifiedAggregateAttestation,
+    ) -> Result<(), AttestationValidationError> {
+        self.metrics.attestation_processing_requests.inc();
+        let timer = self.metrics.attestation_processing_times.start_timer();
+
+        let result = self.op_pool.insert_verified_attestation(
+            verified.into_attestation(),
+            &*self.state.read(),
+            &self.spec,
+        );
+
+        timer.observe_duration();
+
+        if result.is_ok() {
+            self.metrics.attestation_processing_successes.inc();
+        }
+
+        result
+    }
+
+    /// Checks that a gossiped aggregate `Attestation` is not from a future slot, and has at least
+    /// one signer and a valid aggregate signature, using the current state.
+    ///
+    /// The returned `VerifiedAggregateAttestation` can be passed to
+    /// `process_verified_attestation` without verifying the signature again.
+    ///
+    /// Note: attestations in this spec version carry no aggregator selection proof, so there is
+    /// no proof to verify.
+    pub fn verify_aggregate_attestation(
+        &self,
+        aggregate: &Attestation,
+    ) -> Result<VerifiedAggregateAttestation, AttestationError> {
+        if aggregate.aggregation_bitfield.is_zero() {
+            return Err(AttestationError::NoSigners);
+        }
+
+        let state = self.state.read();
+
+        let attestation_slot = state.get_attestation_slot(&aggregate.data)?;
+        if attestation_slot > state.slot {
+            return Err(AttestationError::FutureSlot {
+                attestation_slot,
+                present_slot: state.slot,
+            });
+        }
+
+        let indexed_attestation = convert_to_indexed(&*state, aggregate)?;
+        verify_indexed_attestation(&*state, &indexed_attestation, &self.spec)?;
+
+        Ok(VerifiedAggregateAttestation {
+            attestation: aggregate.clone(),
+        })
+    }
+
+    /// Returns the `IndexedAttestation` of `attestation`, using the committee cache of the current
+    /// state to find its attesters.
+    ///
+    /// Returns an error if the attestation's target epoch is not cached or a bitfield does not
+    /// match its committee.
+    pub fn get_indexed_attestation(
+        &self,
+        attestation: &Attestation,
+    ) -> Result<IndexedAttestation, Error> {
+        Ok(convert_to_indexed(&*self.state.read(), attestation)?)
+    }
+
+    /// Returns an `AttesterSlashing` if `a` and `b` are a double or surround vote by at least one
+    /// common validator, using the current state to find their attesters.
+    ///
+    /// Returns `None` if the pair is not slashable or either attestation cannot be indexed.
+    pub fn compute_attester_slashing_for_double_vote(
+        &self,
+        a: &Attestation,
+        b: &Attestation,
+    ) -> Option<AttesterSlashing> {
+        let state = self.state.read();
+        let indexed_a = convert_to_indexed(&*state, a).ok()?;
+        let indexed_b = convert_to_indexed(&*state, b).ok()?;
+
+        AttesterSlashing::from_conflicting_attestations(indexed_a, indexed_b)
+    }
+
+    /// Verifies `slashing` against the head state.
+    ///
+    /// Block headers do not name their proposer, so both must be signed by the validator at
+    /// `proposer_index`. Returns `Ok(false)` if the slashing is valid but the proposer is already
+    /// slashed.
+    pub fn verify_proposer_slashing(&self, slashing: &ProposerSlashing) -> Result<bool, Error> {
+        let state = &self.head().beacon_state;
+        let (header_1, header_2) = (&slashing.header_1, &slashing.header_2);
+
+        let proposer = state
+            .validator_registry
+            .get(slashing.proposer_index as usize)
+            .ok_or_else(|| ProposerSlashingError::UnknownProposer(slashing.proposer_index))?;
+
+        if header_1.slot != header_2.slot {
+            return Err(ProposerSlashingError::SlotMismatch {
+                slot_1: header_1.slot,
+                slot_2: header_2.slot,
+            }
+            .into());
+        }
+
+        if header_1.signed_root() == header_2.signed_root() {
+            return Err(ProposerSlashingError::IdenticalRoots.into());
+        }
+
+        let domain = self.spec.get_domain(
+            header_1.slot.epoch(T::EthSpec::slots_per_epoch()),
+            Domain::BeaconProposer,
+            &state.fork,
+        );
+        for header in &[header_1, header_2] {
+            if !header
+                .signature
+                .verify(&header.signed_root(), domain, &proposer.pubkey)
+            {
+                return Err(ProposerSlashingError::InvalidSignature.into());
+            }
+        }
+
+        Ok(!proposer.slashed)
+    }
+
+    /// Performs cheap checks on `block` which may show it to be invalid without running a state
+    /// transition.
+    ///
+    /// Blocks from a slot later than the present slot are rejected, as gossip must not forward
+    /// them. The proposer and RANDAO signatures are only checked when the block builds upon the
+    /// head in the current epoch, since only then is the proposer known from the current state.
+    /// `definitely_invalid == false` does not imply that the block is valid.
+    pub fn optimistic_update(&self, block: &BeaconBlock) -> Result<OptimisticUpdateResult, Error> {
+        let head_block_root = self.head().beacon_block_root;
+        let state = self.state.read();
+        let finalized_slot = state
+            .finalized_epoch
+            .start_slot(T::EthSpec::slots_per_epoch());
+
+        if block.slot == 0 || block.slot <= finalized_slot {
+            return Ok(OptimisticUpdateResult::invalid(
+                "block slot is prior to the finalized slot",
+            ));
+        }
+
+        if let Some(present_slot) = self.read_slot_clock() {
+            if block.slot > present_slot {
+                return Ok(OptimisticUpdateResult::invalid(
+                    "block slot is later than the present slot",
+                ));
+            }
+        }
+
+        let block_root = block.block_header().canonical_root();
+
+        if self.store.exists::<BeaconBlock>(&block_root)? {
+            return Ok(OptimisticUpdateResult::unknown(Some(
+                "block is already known",
+            )));
+        }
+
+        let block_epoch = block.slot.epoch(T::EthSpec::slots_per_epoch());
+
+        if block.previous_block_root != head_block_root || block_epoch != state.current_epoch() {
+            return Ok(OptimisticUpdateResult::unknown(Some(
+                "proposer cannot be determined without a state transition",
+            )));
+        }
+
+        let proposer_index = match self.verify_block_proposer_in_state(block, &state) {
+            Ok(proposer_index) => proposer_index,
+            Err(Error::IncorrectBlockProposer { .. }) => {
+                return Ok(OptimisticUpdateResult::invalid(
+                    "proposer signature is invalid",
+                ))
+            }
+            Err(Error::BlockSlotInFuture { .. }) => {
+                return Ok(OptimisticUpdateResult::unknown(Some(
+                    "block slot is later than the current state",
+                )))
+            }
+            Err(e) => return Err(e),
+        };
+        let pubkey = &state.validator_registry[proposer_index].pubkey;
+
+        let domain = self
+            .spec
+            .get_domain(block_epoch, Domain::Randao, &state.fork);
+        if !block
+            .body
+            .randao_reveal
+            .verify(&block_epoch.tree_hash_root()[..], domain, pubkey)
+        {
+            return Ok(OptimisticUpdateResult::invalid("randao reveal is invalid"));
+        }
+
+        Ok(OptimisticUpdateResult::unknown(None))
+    }
+
     /// Accept some deposit and queue it for inclusion in an appropriate block.
     pub fn process_deposit(
         &self,
@@ -546,6 +2314,40 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .insert_voluntary_exit(exit, &*self.state.read(), &self.spec)
     }
 
+    /// Verifies `exit` against the head state and, if it could be included in the next block, adds
+    /// it to the operation pool.
+    ///
+    /// Unlike `process_voluntary_exit`, exits for future epochs are rejected, as are exits for
+    /// validators that already have one in the pool. Publishing the exit is left to the caller,
+    /// see `network::Service::broadcast_voluntary_exit`.
+    pub fn import_voluntary_exit(&self, exit: VoluntaryExit) -> Result<(), Error> {
+        // An exit already in the pool has been imported (and published) before.
+        if self.op_pool.has_voluntary_exit(exit.validator_index) {
+            return Err(Error::AlreadyExiting {
+                validator_index: exit.validator_index,
+            });
+        }
+
+        let state = &self.head().beacon_state;
+
+        let validator = state
+            .validator_registry
+            .get(exit.validator_index as usize)
+            .ok_or(BeaconStateError::UnknownValidator)?;
+
+        if validator.exit_epoch != self.spec.far_future_epoch {
+            return Err(Error::AlreadyExiting {
+                validator_index: exit.validator_index,
+            });
+        }
+
+        verify_exit(state, &exit, &self.spec)?;
+
+        self.op_pool.insert_verified_voluntary_exit(exit);
+
+        Ok(())
+    }
+
     /// Accept some transfer and queue it for inclusion in an appropriate block.
     pub fn process_transfer(&self, transfer: Transfer) -> Result<(), TransferValidationError> {
         self.op_pool
@@ -570,6 +2372,43 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .insert_attester_slashing(attester_slashing, &*self.state.read(), &self.spec)
     }
 
+    /// Returns all slashings pending in the operation pool, including those that would not fit
+    /// in the next block.
+    pub fn get_slashings_from_pool(&self) -> (Vec<ProposerSlashing>, Vec<AttesterSlashing>) {
+        self.op_pool.get_all_slashings()
+    }
+
+    /// Imports `blocks`, a chain segment in ascending slot order where each block is the parent of
+    /// the next, as received during sync.
+    ///
+    /// Each block verifies its own proposer signature. An unweighted aggregate of the segment's
+    /// signatures is not used, as invalid signatures could cancel each other out.
+    ///
+    /// Returns the outcome of each block that was submitted for processing. Processing stops at
+    /// the first block that is not `Processed`.
+    pub fn process_chain_segment(
+        &self,
+        blocks: Vec<BeaconBlock>,
+    ) -> Result<Vec<BlockProcessingOutcome>, Error> {
+        let mut outcomes = Vec::with_capacity(blocks.len());
+
+        for block in blocks {
+            let outcome = self.process_block(block)?;
+            let processed = match outcome {
+                BlockProcessingOutcome::Processed { .. } => true,
+                _ => false,
+            };
+
+            outcomes.push(outcome);
+
+            if !processed {
+                break;
+            }
+        }
+
+        Ok(outcomes)
+    }
+
     /// Accept some block and attempt to add it to block DAG.
     ///
     /// Will accept blocks from prior slots, however it will reject any block from a future slot.
@@ -642,12 +2481,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         // Apply the received block to its parent state (which has been transitioned into this
         // slot).
-        match per_block_processing(&mut state, &block, &self.spec) {
-            Err(BlockProcessingError::BeaconStateError(e)) => {
-                return Err(Error::BeaconStateError(e))
+        if let Err(e) = per_block_processing(&mut state, &block, &self.spec) {
+            match e.into_beacon_state_error() {
+                Ok(e) => return Err(Error::BeaconStateError(e)),
+                Err(e) => return Ok(BlockProcessingOutcome::PerBlockProcessingError(e)),
             }
-            Err(e) => return Ok(BlockProcessingOutcome::PerBlockProcessingError(e)),
-            _ => {}
         }
 
         let state_root = state.canonical_root();
@@ -656,9 +2494,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             return Ok(BlockProcessingOutcome::StateRootMismatch);
         }
 
-        // Store the block and state.
-        self.store.put(&block_root, &block)?;
-        self.store.put(&state_root, &state)?;
+        // Store the block and state in a single batch, so a crash cannot persist one without the
+        // other.
+        self.store.batch_put(vec![
+            block.as_batch_item(&block_root),
+            state.as_batch_item(&state_root),
+        ])?;
 
         // Register the new block with the fork choice service.
         self.fork_choice.process_block(&state, &block, block_root)?;
@@ -694,6 +2535,46 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.produce_block_on_state(state, slot, randao_reveal)
     }
 
+    /// Produce a new block at the present slot, setting `block.body.graffiti` to `graffiti`.
+    ///
+    /// As with `self.produce_block()`, the block must be signed by a block producer.
+    pub fn produce_block_v2(
+        &self,
+        randao_reveal: Signature,
+        graffiti: [u8; 32],
+    ) -> Result<(BeaconBlock, BeaconState<T::EthSpec>), BlockProductionError> {
+        let state = self.state.read().clone();
+        let slot = self
+            .read_slot_clock()
+            .ok_or_else(|| BlockProductionError::UnableToReadSlot)?;
+
+        self.produce_block_on_state_with_graffiti(state, slot, randao_reveal, graffiti)
+    }
+
+    /// Produce a new blinded block at the present slot, returning the header of its execution
+    /// payload from `self.get_execution_payload_header()` in place of the payload.
+    ///
+    /// As with `self.produce_block()`, the block must be signed by a block producer.
+    pub fn produce_blinded_block(
+        &self,
+        randao_reveal: Signature,
+        graffiti: [u8; 32],
+    ) -> Result<(BeaconBlock, ExecutionPayloadHeader, BeaconState<T::EthSpec>), BlockProductionError>
+    {
+        let slot = self
+            .read_slot_clock()
+            .ok_or_else(|| BlockProductionError::UnableToReadSlot)?;
+        let header = self
+            .get_execution_payload_header(slot)
+            .map_err(BlockProductionError::ExecutionPayloadHeaderError)?;
+        let state = self.state.read().clone();
+
+        let (block, state) =
+            self.produce_block_on_state_with_graffiti(state, slot, randao_reveal, graffiti)?;
+
+        Ok((block, header, state))
+    }
+
     /// Produce a block for some `slot` upon the given `state`.
     ///
     /// Typically the `self.produce_block()` function should be used, instead of calling this
@@ -703,10 +2584,25 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// The given state will be advanced to the given `produce_at_slot`, then a block will be
     /// produced at that slot height.
     pub fn produce_block_on_state(
+        &self,
+        state: BeaconState<T::EthSpec>,
+        produce_at_slot: Slot,
+        randao_reveal: Signature,
+    ) -> Result<(BeaconBlock, BeaconState<T::EthSpec>), BlockProductionError> {
+        let mut graffiti: [u8; 32] = [0; 32];
+        graffiti.copy_from_slice(GRAFFITI.as_bytes());
+
+        self.produce_block_on_state_with_graffiti(state, produce_at_slot, randao_reveal, graffiti)
+    }
+
+    /// As per `self.produce_block_on_state()`, but with a caller-supplied `graffiti` instead of
+    /// the default `GRAFFITI`.
+    pub fn produce_block_on_state_with_graffiti(
         &self,
         mut state: BeaconState<T::EthSpec>,
         produce_at_slot: Slot,
         randao_reveal: Signature,
+        graffiti: [u8; 32],
     ) -> Result<(BeaconBlock, BeaconState<T::EthSpec>), BlockProductionError> {
         self.metrics.block_production_requests.inc();
         let timer = self.metrics.block_production_times.start_timer();
@@ -726,12 +2622,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             state.latest_block_header.canonical_root()
         };
 
-        let mut graffiti: [u8; 32] = [0; 32];
-        graffiti.copy_from_slice(GRAFFITI.as_bytes());
-
         let (proposer_slashings, attester_slashings) =
             self.op_pool.get_slashings(&state, &self.spec);
 
+        let eth1_data = self.compute_eth1_data(&state);
+        let deposits = self.get_block_deposits(&state, &eth1_data);
+
         let mut block = BeaconBlock {
             slot: state.slot,
             previous_block_root,
@@ -739,17 +2635,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             signature: Signature::empty_signature(), // To be completed by a validator.
             body: BeaconBlockBody {
                 randao_reveal,
-                // TODO: replace with real data.
-                eth1_data: Eth1Data {
-                    deposit_count: 0,
-                    deposit_root: Hash256::zero(),
-                    block_hash: Hash256::zero(),
-                },
+                eth1_data,
                 graffiti,
                 proposer_slashings,
                 attester_slashings,
                 attestations: self.op_pool.get_attestations(&state, &self.spec),
-                deposits: self.op_pool.get_deposits(&state, &self.spec),
+                deposits,
                 voluntary_exits: self.op_pool.get_voluntary_exits(&state, &self.spec),
                 transfers: self.op_pool.get_transfers(&state, &self.spec),
             },
@@ -780,6 +2671,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // End fork choice metrics timer.
         timer.observe_duration();
 
+        let tree_size = self.fork_choice.tree_size();
+        self.metrics.fork_choice_nodes.set(tree_size as i64);
+        if tree_size > FORK_CHOICE_TREE_SIZE_WARN_THRESHOLD {
+            warn!(self.log, "Large fork choice tree";
+                "nodes" => tree_size,
+                "votes" => self.fork_choice.vote_count(),
+            );
+        }
+
         // If a new head was chosen.
         if beacon_block_root != self.head().beacon_block_root {
             self.metrics.fork_choice_changed_head.inc();
@@ -837,6 +2737,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
     /// Update the canonical head to `new_head`.
     fn update_canonical_head(&self, new_head: CheckPoint<T::EthSpec>) -> Result<(), Error> {
+        // Persist the new head before adopting it, so a failed write leaves the in-memory head
+        // and the store in agreement.
+        self.put_persisted_head(&PersistedHead {
+            head_block_root: new_head.beacon_block_root,
+            head_state_root: new_head.beacon_state_root,
+        })?;
+
         // Update the checkpoint that stores the head of the chain at the time it received the
         // block.
         *self.canonical_head.write() = new_head;
@@ -892,13 +2799,104 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 "pruning fork choice from slot" => format!("{}", finalized_block.slot),
             );
 
-            self.fork_choice
-                .process_finalization(&finalized_block, finalized_block_root)?;
+            // Pruning the store walks the finalized chain and scans every stored block, so it
+            // shares the fork choice prune interval rather than running on every update.
+            if self.fork_choice_prune_interval.on_update() {
+                let pruned_nodes = self
+                    .fork_choice
+                    .process_finalization(&finalized_block, finalized_block_root)?;
+
+                debug!(self.log, "Pruned fork choice tree";
+                    "pruned_nodes" => pruned_nodes,
+                    "finalized_root" => format!("{}", finalized_block_root),
+                );
+
+                let pruned_blocks = self.prune_block_roots_before_finalization()?;
+
+                debug!(self.log, "Pruned non-canonical blocks";
+                    "pruned_blocks" => pruned_blocks,
+                    "finalized_root" => format!("{}", finalized_block_root),
+                );
+            }
 
             Ok(())
         }
     }
 
+    /// Deletes from the store every block (and its state) that is after the previously pruned
+    /// finalized block and at or before the finalized slot of the head state, but is not an
+    /// ancestor of the finalized block.
+    ///
+    /// Candidates are found by scanning the blocks in the store, so blocks imported before a
+    /// restart are also pruned. The finalized chain is walked back to the previously pruned
+    /// finalized block; if a block is missing or the walk does not reach that block, an error is
+    /// returned and nothing is deleted.
+    ///
+    /// Called on finalization once every `fork_choice_store_prune_interval` updates, alongside the
+    /// fork choice tree. Each call covers all blocks finalized since the previous one.
+    ///
+    /// Returns the number of blocks deleted.
+    pub fn prune_block_roots_before_finalization(&self) -> Result<usize, Error> {
+        let finalized_root = self.head().beacon_state.finalized_root;
+        let (old_finalized_root, old_finalized_slot) = *self.last_pruned_finalized_block.read();
+
+        // The finalized root is zero until the first finalization.
+        if finalized_root == Hash256::zero() || finalized_root == old_finalized_root {
+            return Ok(0);
+        }
+
+        let mut canonical_roots = HashSet::new();
+        let mut finalized_slot = None;
+        let mut block_root = finalized_root;
+        while block_root != old_finalized_root {
+            let block = self
+                .get_block(&block_root)?
+                .ok_or_else(|| Error::MissingBeaconBlock(block_root))?;
+
+            if block.slot <= old_finalized_slot {
+                return Err(Error::DBInconsistent(format!(
+                    "Finalized block {} does not descend from {}",
+                    finalized_root, old_finalized_root
+                )));
+            }
+
+            finalized_slot.get_or_insert(block.slot);
+            canonical_roots.insert(block_root);
+            block_root = block.previous_block_root;
+        }
+        let finalized_slot = finalized_slot.unwrap_or(old_finalized_slot);
+
+        let column: &str = DBColumn::BeaconBlock.into();
+        let stored_roots = self
+            .store
+            .iter_keys_by_prefix(column.as_bytes())
+            .map(|key| Ok(Hash256::from_slice(&key?[column.len()..])))
+            .collect::<Result<Vec<_>, DBError>>()?;
+
+        let mut pruned = 0;
+        for block_root in stored_roots {
+            if canonical_roots.contains(&block_root) {
+                continue;
+            }
+
+            let block = match self.get_block(&block_root)? {
+                Some(block) => block,
+                None => continue,
+            };
+
+            if block.slot > old_finalized_slot && block.slot <= finalized_slot {
+                self.store.delete::<BeaconBlock>(&block_root)?;
+                self.store
+                    .delete::<BeaconState<T::EthSpec>>(&block.state_root)?;
+                pruned += 1;
+            }
+        }
+
+        *self.last_pruned_finalized_block.write() = (finalized_root, finalized_slot);
+
+        Ok(pruned)
+    }
+
     /// Returns `true` if the given block root has not been processed.
     pub fn is_new_block_root(&self, beacon_block_root: &Hash256) -> Result<bool, Error> {
         Ok(!self.store.exists::<BeaconBlock>(beacon_block_root)?)
@@ -1,6 +1,11 @@
+use crate::execution_engine::Error as ExecutionEngineError;
 use crate::fork_choice::Error as ForkChoiceError;
 use crate::metrics::Error as MetricsError;
+use state_processing::per_block_processing::errors::{
+    ExitValidationError, IndexedAttestationInvalid, IndexedAttestationValidationError,
+};
 use state_processing::BlockProcessingError;
+use state_processing::EpochProcessingError;
 use state_processing::SlotProcessingError;
 use types::*;
 
@@ -29,11 +34,71 @@ pub enum BeaconChainError {
     ForkChoiceError(ForkChoiceError),
     MissingBeaconBlock(Hash256),
     MissingBeaconState(Hash256),
+    NoCommonAncestor {
+        root_a: Hash256,
+        root_b: Hash256,
+    },
+    NoStateForSlot(Slot),
+    /// The state needed to find the root at this slot has been pruned from the store.
+    NotArchived(Slot),
+    EpochNotComplete {
+        epoch: Epoch,
+        current_epoch: Epoch,
+    },
     SlotProcessingError(SlotProcessingError),
+    EpochProcessingError(EpochProcessingError),
     MetricsError(String),
+    NotSupportedBeforeMerge,
+    EpochTooFarInFuture(Epoch),
+    BlockSlotInFuture {
+        block_slot: Slot,
+        present_slot: Slot,
+    },
+    IncorrectBlockProposer {
+        block_slot: Slot,
+        expected_proposer: usize,
+    },
+    PeriodNotStarted {
+        period: u64,
+        current_period: u64,
+    },
+    /// No execution engine is configured, see `BeaconChain::with_execution_engine`.
+    NoExecutionEngine,
+    ExecutionEngineUnavailable,
+    ExecutionEngineInvalidResponse(String),
+    ValidatorNotActive {
+        validator_index: usize,
+        epoch: Epoch,
+    },
+    /// The validator is not in the activation queue and its balance is too low to join it.
+    InsufficientBalanceForActivation {
+        validator_index: usize,
+    },
+    SyncAggregateError(SyncAggregateError),
+    ProposerSlashingError(ProposerSlashingError),
+    /// The validator has already initiated an exit, or has one pending in the operation pool.
+    AlreadyExiting {
+        validator_index: u64,
+    },
+    ExitValidationError(ExitValidationError),
 }
 
 easy_from_to!(SlotProcessingError, BeaconChainError);
+easy_from_to!(SyncAggregateError, BeaconChainError);
+easy_from_to!(ProposerSlashingError, BeaconChainError);
+easy_from_to!(ExitValidationError, BeaconChainError);
+easy_from_to!(EpochProcessingError, BeaconChainError);
+
+impl From<ExecutionEngineError> for BeaconChainError {
+    fn from(e: ExecutionEngineError) -> BeaconChainError {
+        match e {
+            ExecutionEngineError::Unavailable => BeaconChainError::ExecutionEngineUnavailable,
+            ExecutionEngineError::InvalidResponse(e) => {
+                BeaconChainError::ExecutionEngineInvalidResponse(e)
+            }
+        }
+    }
+}
 
 impl From<MetricsError> for BeaconChainError {
     fn from(e: MetricsError) -> BeaconChainError {
@@ -41,6 +106,32 @@ impl From<MetricsError> for BeaconChainError {
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub enum SyncAggregateError {
+    /// Fewer members of the sync committee sent messages than are required for an aggregate.
+    InsufficientParticipation { got: usize, required: usize },
+    /// The `validator_index` is not in the validator registry.
+    UnknownValidator(u64),
+    /// The message is not signed by the validator.
+    InvalidSignature,
+    /// The message is for a committee period prior to the current one.
+    PastPeriod { period: u64, current_period: u64 },
+    /// `MAX_SYNC_COMMITTEE_MESSAGES` messages of the current period are already stored.
+    MessagePoolFull,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ProposerSlashingError {
+    /// The `proposer_index` is not in the validator registry.
+    UnknownProposer(u64),
+    /// The headers are for different slots.
+    SlotMismatch { slot_1: Slot, slot_2: Slot },
+    /// The headers have the same signed root, so are not a double proposal.
+    IdenticalRoots,
+    /// A header is not signed by the proposer.
+    InvalidSignature,
+}
+
 #[derive(Debug, PartialEq)]
 pub enum BlockProductionError {
     UnableToGetBlockRootFromState,
@@ -48,8 +139,36 @@ pub enum BlockProductionError {
     SlotProcessingError(SlotProcessingError),
     BlockProcessingError(BlockProcessingError),
     BeaconStateError(BeaconStateError),
+    ExecutionPayloadHeaderError(BeaconChainError),
 }
 
 easy_from_to!(BlockProcessingError, BlockProductionError);
 easy_from_to!(BeaconStateError, BlockProductionError);
 easy_from_to!(SlotProcessingError, BlockProductionError);
+
+#[derive(Debug, PartialEq)]
+pub enum AttestationError {
+    /// The aggregation bitfield has no bits set.
+    NoSigners,
+    /// The attestation is for a slot later than the present slot.
+    FutureSlot {
+        attestation_slot: Slot,
+        present_slot: Slot,
+    },
+    /// The aggregate signature is not valid for the attesting validators.
+    InvalidSignature,
+    /// The attesting validators do not form a valid `IndexedAttestation`.
+    InvalidIndexedAttestation(IndexedAttestationInvalid),
+    BeaconStateError(BeaconStateError),
+}
+
+easy_from_to!(BeaconStateError, AttestationError);
+
+impl From<IndexedAttestationValidationError> for AttestationError {
+    fn from(e: IndexedAttestationValidationError) -> AttestationError {
+        match e.into() {
+            IndexedAttestationInvalid::BadSignature => AttestationError::InvalidSignature,
+            e => AttestationError::InvalidIndexedAttestation(e),
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use futures::{future, Future, Stream};
+use hyper::client::HttpConnector;
+use hyper::{Body, Client, Request, Uri};
+use ring::{digest, hmac};
+use serde_derive::Serialize;
+use serde_json::{json, Value};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::prelude::FutureExt;
+use types::{ExecutionPayload, Hash256};
+
+/// The default time allowed for a response from the execution engine.
+pub const DEFAULT_EXECUTION_ENGINE_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// The length of the secret shared with the execution engine, in bytes.
+pub const JWT_SECRET_LENGTH: usize = 32;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The execution engine could not be reached, or did not respond in time.
+    Unavailable,
+    /// The execution engine responded, but not with a valid `engine_newPayload` result.
+    InvalidResponse(String),
+}
+
+/// The validity of a payload, as reported by the execution engine.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+pub enum PayloadStatusKind {
+    Valid,
+    Invalid,
+    Syncing,
+}
+
+/// The result of `engine_newPayload`.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct PayloadStatus {
+    pub status: PayloadStatusKind,
+    pub latest_valid_hash: Option<Hash256>,
+    pub validation_error: Option<String>,
+}
+
+/// A JSON-RPC client for the Engine API of an execution engine.
+///
+/// Cloning is cheap; all clones share the same connection pool.
+#[derive(Debug, Clone)]
+pub struct ExecutionEngine {
+    client: Client<HttpConnector>,
+    endpoint: Uri,
+    jwt_secret: Option<Vec<u8>>,
+    timeout: Duration,
+}
+
+impl ExecutionEngine {
+    /// Returns a client for the execution engine at `endpoint`, e.g. `http://localhost:8551`.
+    pub fn new(endpoint: &str, timeout: Duration) -> Result<Self, String> {
+        let endpoint = endpoint
+            .parse::<Uri>()
+            .map_err(|e| format!("Invalid execution engine endpoint: {}", e))?;
+
+        Ok(Self {
+            client: Client::new(),
+            endpoint,
+            jwt_secret: None,
+            timeout,
+        })
+    }
+
+    /// Authenticates each request with a JWT signed by `jwt_secret`, see `parse_jwt_secret`.
+    pub fn with_jwt_secret(mut self, jwt_secret: Vec<u8>) -> Self {
+        self.jwt_secret = Some(jwt_secret);
+        self
+    }
+
+    /// Sends `payload` to the execution engine via `engine_newPayloadV1`.
+    ///
+    /// The returned future must be run on a tokio executor.
+    pub fn new_payload(
+        &self,
+        payload: &ExecutionPayload,
+    ) -> impl Future<Item = PayloadStatus, Error = Error> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "method": "engine_newPayloadV1",
+            "params": [payload_to_json(payload)],
+            "id": 1,
+        });
+
+        let mut request = Request::post(self.endpoint.clone());
+        request.header("Content-Type", "application/json");
+        if let Some(secret) = &self.jwt_secret {
+            request.header(
+                "Authorization",
+                format!("Bearer {}", jwt_token(secret, unix_time())).as_str(),
+            );
+        }
+
+        let client = self.client.clone();
+        let timeout = self.timeout;
+
+        future::result(
+            request
+                .body(Body::from(body.to_string()))
+                .map_err(|e| Error::InvalidResponse(format!("Unable to build request: {}", e))),
+        )
+        .and_then(move |request| {
+            client
+                .request(request)
+                .and_then(|response| response.into_body().concat2())
+                .timeout(timeout)
+                .map_err(|_| Error::Unavailable)
+        })
+        .and_then(|bytes| {
+            let response = serde_json::from_slice::<Value>(&bytes)
+                .map_err(|e| Error::InvalidResponse(format!("{}", e)))?;
+
+            parse_payload_status(&response)
+        })
+    }
+}
+
+/// Parses the hex-encoded secret shared with the execution engine (e.g. the contents of its
+/// `jwt.hex` file), with or without a `0x` prefix.
+pub fn parse_jwt_secret(hex_secret: &str) -> Result<Vec<u8>, String> {
+    let secret = hex::decode(hex_secret.trim().trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid JWT secret: {}", e))?;
+
+    if secret.len() == JWT_SECRET_LENGTH {
+        Ok(secret)
+    } else {
+        Err(format!(
+            "Invalid JWT secret: expected {} bytes, got {}",
+            JWT_SECRET_LENGTH,
+            secret.len()
+        ))
+    }
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// Returns an HS256 JWT, issued at `iat`, as required by the Engine API.
+fn jwt_token(secret: &[u8], iat: u64) -> String {
+    let header = base64::encode_config(r#"{"alg":"HS256","typ":"JWT"}"#, base64::URL_SAFE_NO_PAD);
+    let claims = base64::encode_config(&json!({ "iat": iat }).to_string(), base64::URL_SAFE_NO_PAD);
+    let message = format!("{}.{}", header, claims);
+
+    let key = hmac::SigningKey::new(&digest::SHA256, secret);
+    let signature = hmac::sign(&key, message.as_bytes());
+
+    format!(
+        "{}.{}",
+        message,
+        base64::encode_config(signature.as_ref(), base64::URL_SAFE_NO_PAD)
+    )
+}
+
+/// Encodes `payload` as an Engine API `ExecutionPayloadV1`, with quantities and byte strings as
+/// hex strings.
+fn payload_to_json(payload: &ExecutionPayload) -> Value {
+    json!({
+        "parentHash": format!("{:?}", payload.parent_hash),
+        "feeRecipient": format!("{:?}", payload.fee_recipient),
+        "stateRoot": format!("{:?}", payload.state_root),
+        "receiptsRoot": format!("{:?}", payload.receipts_root),
+        "logsBloom": format!("0x{}", hex::encode(&payload.logs_bloom)),
+        "prevRandao": format!("{:?}", payload.random),
+        "blockNumber": format!("0x{:x}", payload.block_number),
+        "gasLimit": format!("0x{:x}", payload.gas_limit),
+        "gasUsed": format!("0x{:x}", payload.gas_used),
+        "timestamp": format!("0x{:x}", payload.timestamp),
+        "extraData": format!("0x{}", hex::encode(&payload.extra_data)),
+        "baseFeePerGas": format!("0x{:x}", payload.base_fee_per_gas),
+        "blockHash": format!("{:?}", payload.block_hash),
+        "transactions": payload
+            .transactions
+            .iter()
+            .map(|transaction| format!("0x{}", hex::encode(transaction)))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Parses the JSON-RPC response to `engine_newPayload`.
+fn parse_payload_status(response: &Value) -> Result<PayloadStatus, Error> {
+    if let Some(error) = response.get("error") {
+        return Err(Error::InvalidResponse(format!("{}", error)));
+    }
+
+    let result = response
+        .get("result")
+        .ok_or_else(|| Error::InvalidResponse("Missing result".to_string()))?;
+
+    let status = match result.get("status").and_then(Value::as_str) {
+        Some("VALID") => PayloadStatusKind::Valid,
+        Some("INVALID") | Some("INVALID_BLOCK_HASH") => PayloadStatusKind::Invalid,
+        Some("SYNCING") | Some("ACCEPTED") => PayloadStatusKind::Syncing,
+        other => {
+            return Err(Error::InvalidResponse(format!(
+                "Unknown status: {:?}",
+                other
+            )))
+        }
+    };
+
+    let latest_valid_hash = match result.get("latestValidHash").and_then(Value::as_str) {
+        Some(hash) => Some(
+            hash.trim_start_matches("0x")
+                .parse::<Hash256>()
+                .map_err(|e| Error::InvalidResponse(format!("Invalid latestValidHash: {}", e)))?,
+        ),
+        None => None,
+    };
+
+    let validation_error = result
+        .get("validationError")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    Ok(PayloadStatus {
+        status,
+        latest_valid_hash,
+        validation_error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_payload_status() {
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "status": "INVALID",
+                "latestValidHash": format!("{:?}", Hash256::from(42)),
+                "validationError": "bad state root",
+            },
+        });
+
+        assert_eq!(
+            parse_payload_status(&response),
+            Ok(PayloadStatus {
+                status: PayloadStatusKind::Invalid,
+                latest_valid_hash: Some(Hash256::from(42)),
+                validation_error: Some("bad state root".to_string()),
+            })
+        );
+
+        let response = json!({ "result": { "status": "SYNCING", "latestValidHash": null } });
+
+        assert_eq!(
+            parse_payload_status(&response),
+            Ok(PayloadStatus {
+                status: PayloadStatusKind::Syncing,
+                latest_valid_hash: None,
+                validation_error: None,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_error_responses() {
+        let response = json!({ "error": { "code": -32000, "message": "oops" } });
+
+        assert!(parse_payload_status(&response).is_err());
+        assert!(parse_payload_status(&json!({ "result": { "status": "MAYBE" } })).is_err());
+    }
+
+    #[test]
+    fn unreachable_engine_is_unavailable() {
+        // Nothing listens on the discard port.
+        let engine =
+            ExecutionEngine::new("http://127.0.0.1:9", Duration::from_millis(500)).unwrap();
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+        assert_eq!(
+            runtime.block_on(engine.new_payload(&ExecutionPayload::default())),
+            Err(Error::Unavailable)
+        );
+    }
+
+    #[test]
+    fn encodes_all_payload_fields() {
+        let payload = ExecutionPayload {
+            logs_bloom: vec![0xff; 2],
+            extra_data: vec![0x01],
+            base_fee_per_gas: 7,
+            transactions: vec![vec![0xab, 0xcd], vec![]],
+            ..ExecutionPayload::default()
+        };
+
+        let json = payload_to_json(&payload);
+
+        for field in &[
+            "parentHash",
+            "feeRecipient",
+            "stateRoot",
+            "receiptsRoot",
+            "logsBloom",
+            "prevRandao",
+            "blockNumber",
+            "gasLimit",
+            "gasUsed",
+            "timestamp",
+            "extraData",
+            "baseFeePerGas",
+            "blockHash",
+            "transactions",
+        ] {
+            assert!(json.get(field).is_some(), "missing {}", field);
+        }
+
+        assert_eq!(json["logsBloom"], "0xffff");
+        assert_eq!(json["extraData"], "0x01");
+        assert_eq!(json["baseFeePerGas"], "0x7");
+        assert_eq!(json["transactions"], json!(["0xabcd", "0x"]));
+    }
+
+    #[test]
+    fn jwt_token_is_signed_with_secret() {
+        let secret = vec![42; JWT_SECRET_LENGTH];
+        let token = jwt_token(&secret, 1_000);
+
+        let parts = token.split('.').collect::<Vec<_>>();
+        assert_eq!(parts.len(), 3);
+
+        let claims = base64::decode_config(parts[1], base64::URL_SAFE_NO_PAD).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&claims).unwrap(),
+            json!({ "iat": 1_000 })
+        );
+
+        let signature = base64::decode_config(parts[2], base64::URL_SAFE_NO_PAD).unwrap();
+        let key = hmac::SigningKey::new(&digest::SHA256, &secret);
+        let message = format!("{}.{}", parts[0], parts[1]);
+        assert!(hmac::verify_with_own_key(&key, message.as_bytes(), &signature).is_ok());
+        assert!(hmac::verify_with_own_key(&key, b"other", &signature).is_err());
+    }
+
+    #[test]
+    fn parses_jwt_secret() {
+        let hex_secret = "0x".to_string() + &"ab".repeat(JWT_SECRET_LENGTH);
+
+        assert_eq!(
+            parse_jwt_secret(&format!("{}\n", hex_secret)),
+            Ok(vec![0xab; JWT_SECRET_LENGTH])
+        );
+        assert!(parse_jwt_secret("abcd").is_err());
+        assert!(parse_jwt_secret("not hex").is_err());
+    }
+}
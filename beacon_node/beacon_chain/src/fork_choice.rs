@@ -7,6 +7,9 @@ use types::{Attestation, BeaconBlock, BeaconState, BeaconStateError, Epoch, EthS
 
 type Result<T> = std::result::Result<T, Error>;
 
+/// A function that returns the fork choice weight for some validator index.
+pub type WeightFn = Arc<dyn Fn(usize) -> Option<u64> + Send + Sync>;
+
 #[derive(Debug, PartialEq)]
 pub enum Error {
     MissingBlock(Hash256),
@@ -23,6 +26,8 @@ pub struct ForkChoice<T: BeaconChainTypes> {
     /// Does not necessarily need to be the _actual_ genesis, it suffices to be the finalized root
     /// whenever the struct was instantiated.
     genesis_block_root: Hash256,
+    /// If `Some`, used instead of the effective balances of the justified state.
+    weight_override: Option<WeightFn>,
 }
 
 impl<T: BeaconChainTypes> ForkChoice<T> {
@@ -38,9 +43,30 @@ impl<T: BeaconChainTypes> ForkChoice<T> {
         Self {
             backend: T::LmdGhost::new(store, genesis_block, genesis_block_root),
             genesis_block_root,
+            weight_override: None,
         }
     }
 
+    /// Use `weight_fn` to weigh validators in `find_head`, instead of their effective balance.
+    pub fn set_weight_override(&mut self, weight_fn: WeightFn) {
+        self.weight_override = Some(weight_fn);
+    }
+
+    /// Bounds any internal cache of the fork choice backend to `max_cache_size` entries.
+    pub fn set_max_cache_size(&self, max_cache_size: usize) {
+        self.backend.set_max_cache_size(max_cache_size);
+    }
+
+    /// Returns the number of nodes in the fork choice tree.
+    pub fn tree_size(&self) -> usize {
+        self.backend.tree_size()
+    }
+
+    /// Returns the number of validators with a latest vote in the fork choice tree.
+    pub fn vote_count(&self) -> usize {
+        self.backend.vote_count()
+    }
+
     pub fn find_head(&self, chain: &BeaconChain<T>) -> Result<Hash256> {
         let start_slot = |epoch: Epoch| epoch.start_slot(T::EthSpec::slots_per_epoch());
 
@@ -84,10 +110,13 @@ impl<T: BeaconChainTypes> ForkChoice<T> {
 
         // A function that returns the weight for some validator index.
         let weight = |validator_index: usize| -> Option<u64> {
-            start_state
-                .validator_registry
-                .get(validator_index)
-                .map(|v| v.effective_balance)
+            match &self.weight_override {
+                Some(weight_override) => weight_override(validator_index),
+                None => start_state
+                    .validator_registry
+                    .get(validator_index)
+                    .map(|v| v.effective_balance),
+            }
         };
 
         self.backend
@@ -153,10 +182,12 @@ impl<T: BeaconChainTypes> ForkChoice<T> {
                 .target_epoch
                 .start_slot(T::EthSpec::slots_per_epoch());
 
-            for validator_index in validator_indices {
-                self.backend
-                    .process_attestation(validator_index, block_hash, block_slot)?;
-            }
+            let votes = validator_indices
+                .into_iter()
+                .map(|validator_index| (validator_index, block_hash, block_slot))
+                .collect();
+
+            self.backend.process_attestation_batch(votes)?;
         }
 
         Ok(())
@@ -166,11 +197,13 @@ impl<T: BeaconChainTypes> ForkChoice<T> {
     /// it may prune it's storage.
     ///
     /// `finalized_block_root` must be the root of `finalized_block`.
+    ///
+    /// Returns the number of nodes pruned from the backend.
     pub fn process_finalization(
         &self,
         finalized_block: &BeaconBlock,
         finalized_block_root: Hash256,
-    ) -> Result<()> {
+    ) -> Result<usize> {
         self.backend
             .update_finalized_root(finalized_block, finalized_block_root)
             .map_err(Into::into)
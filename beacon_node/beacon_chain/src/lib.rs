@@ -1,14 +1,27 @@
+mod activation_queue;
+mod active_validator_count;
 mod beacon_chain;
 mod checkpoint;
 mod errors;
+pub mod execution_engine;
 mod fork_choice;
 mod metrics;
 mod persisted_beacon_chain;
+mod prune_interval;
 pub mod test_utils;
+mod validator_inclusion_cache;
 
-pub use self::beacon_chain::{BeaconChain, BeaconChainTypes, BlockProcessingOutcome};
+pub use self::beacon_chain::{
+    AttestationRewardData, BeaconChain, BeaconChainTypes, BlockProcessingOutcome, BlockRewards,
+    EpochRewards, ExecutionPayloadAttributes, LightClientUpdate, OptimisticUpdateResult,
+    SyncAggregate, SyncCommitteeMessage, ValidatorInclusionData, ValidatorStatus,
+    VerifiedAggregateAttestation, GRAFFITI,
+};
 pub use self::checkpoint::CheckPoint;
-pub use self::errors::{BeaconChainError, BlockProductionError};
+pub use self::errors::{
+    AttestationError, BeaconChainError, BlockProductionError, ProposerSlashingError,
+    SyncAggregateError,
+};
 pub use lmd_ghost;
 pub use parking_lot;
 pub use slot_clock;
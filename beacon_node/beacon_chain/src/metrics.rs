@@ -1,5 +1,5 @@
 pub use prometheus::Error;
-use prometheus::{Histogram, HistogramOpts, IntCounter, Opts, Registry};
+use prometheus::{Histogram, HistogramOpts, IntCounter, IntGauge, Opts, Registry};
 
 pub struct Metrics {
     pub block_processing_requests: IntCounter,
@@ -18,6 +18,7 @@ pub struct Metrics {
     pub fork_choice_changed_head: IntCounter,
     pub fork_choice_reorg_count: IntCounter,
     pub fork_choice_times: Histogram,
+    pub fork_choice_nodes: IntGauge,
     pub operations_per_block_attestation: Histogram,
 }
 
@@ -109,6 +110,13 @@ impl Metrics {
                 let opts = HistogramOpts::new("fork_choice_time", "total_time_to_run_fork_choice");
                 Histogram::with_opts(opts)?
             },
+            fork_choice_nodes: {
+                let opts = Opts::new(
+                    "lighthouse_fork_choice_nodes",
+                    "number_of_nodes_in_fork_choice_tree",
+                );
+                IntGauge::with_opts(opts)?
+            },
             operations_per_block_attestation: {
                 let opts = HistogramOpts::new(
                     "operations_per_block_attestation",
@@ -136,6 +144,7 @@ impl Metrics {
         registry.register(Box::new(self.fork_choice_changed_head.clone()))?;
         registry.register(Box::new(self.fork_choice_reorg_count.clone()))?;
         registry.register(Box::new(self.fork_choice_times.clone()))?;
+        registry.register(Box::new(self.fork_choice_nodes.clone()))?;
         registry.register(Box::new(self.operations_per_block_attestation.clone()))?;
 
         Ok(())
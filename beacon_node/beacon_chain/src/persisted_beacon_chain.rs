@@ -8,6 +8,9 @@ use types::{BeaconState, Hash256};
 /// 32-byte key for accessing the `PersistedBeaconChain`.
 pub const BEACON_CHAIN_DB_KEY: &str = "PERSISTEDBEACONCHAINPERSISTEDBEA";
 
+/// 32-byte key for accessing the `PersistedHead`.
+pub const BEACON_CHAIN_HEAD_DB_KEY: &str = "PERSISTEDBEACONCHAINHEADPERSISTE";
+
 #[derive(Encode, Decode)]
 pub struct PersistedBeaconChain<T: BeaconChainTypes> {
     pub canonical_head: CheckPoint<T::EthSpec>,
@@ -29,3 +32,25 @@ impl<T: BeaconChainTypes> StoreItem for PersistedBeaconChain<T> {
         Self::from_ssz_bytes(bytes).map_err(Into::into)
     }
 }
+
+/// The roots of the canonical head block and state, written on every head update so the head
+/// survives a crash between full persists of the `BeaconChain`.
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct PersistedHead {
+    pub head_block_root: Hash256,
+    pub head_state_root: Hash256,
+}
+
+impl StoreItem for PersistedHead {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconChain
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &mut [u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
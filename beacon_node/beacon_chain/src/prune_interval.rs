@@ -0,0 +1,52 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counts finalization updates so that the fork choice tree is only pruned once every `interval`
+/// of them.
+#[derive(Debug)]
+pub struct PruneInterval {
+    interval: u64,
+    updates: AtomicU64,
+}
+
+impl PruneInterval {
+    /// An `interval` of `0` is treated as `1`.
+    pub fn new(interval: u64) -> Self {
+        Self {
+            interval: std::cmp::max(interval, 1),
+            updates: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the number of updates required between prunes.
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Records a finalization update, returning `true` if the tree should now be pruned.
+    pub fn on_update(&self) -> bool {
+        let updates = self.updates.fetch_add(1, Ordering::SeqCst) + 1;
+        updates % self.interval == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prunes_once_per_interval() {
+        let prune_interval = PruneInterval::new(3);
+
+        let prunes: Vec<bool> = (0..7).map(|_| prune_interval.on_update()).collect();
+        assert_eq!(prunes, vec![false, false, true, false, false, true, false]);
+    }
+
+    #[test]
+    fn zero_interval_prunes_on_every_update() {
+        let prune_interval = PruneInterval::new(0);
+
+        assert_eq!(prune_interval.interval(), 1);
+        assert!(prune_interval.on_update());
+        assert!(prune_interval.on_update());
+    }
+}
@@ -0,0 +1,70 @@
+use crate::beacon_chain::ValidatorInclusionData;
+use parking_lot::RwLock;
+use std::collections::BTreeMap;
+use types::Epoch;
+
+/// The number of epochs kept by a `ValidatorInclusionCache`.
+pub const VALIDATOR_INCLUSION_CACHE_EPOCHS: usize = 64;
+
+/// A cache of the validator inclusion data of the most recent finalized epochs.
+///
+/// Once full, inserting an epoch evicts the earliest.
+#[derive(Debug, Default)]
+pub struct ValidatorInclusionCache {
+    epochs: RwLock<BTreeMap<Epoch, ValidatorInclusionData>>,
+}
+
+impl ValidatorInclusionCache {
+    /// Returns the cached data for `epoch`, if any.
+    pub fn get(&self, epoch: Epoch) -> Option<ValidatorInclusionData> {
+        self.epochs.read().get(&epoch).cloned()
+    }
+
+    /// Caches `data` for `epoch`, evicting the earliest epochs beyond
+    /// `VALIDATOR_INCLUSION_CACHE_EPOCHS`.
+    pub fn insert(&self, epoch: Epoch, data: ValidatorInclusionData) {
+        let mut epochs = self.epochs.write();
+
+        epochs.insert(epoch, data);
+
+        while epochs.len() > VALIDATOR_INCLUSION_CACHE_EPOCHS {
+            let earliest = *epochs.keys().next().expect("cache is not empty");
+            epochs.remove(&earliest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(epoch: u64) -> ValidatorInclusionData {
+        ValidatorInclusionData {
+            participating_validators: epoch as usize,
+            total_validators: epoch as usize,
+            attesting_balance_gwei: epoch,
+            total_balance_gwei: epoch,
+        }
+    }
+
+    #[test]
+    fn evicts_earliest_epochs() {
+        let cache = ValidatorInclusionCache::default();
+        let epochs = VALIDATOR_INCLUSION_CACHE_EPOCHS as u64;
+
+        for epoch in 0..epochs {
+            cache.insert(Epoch::new(epoch), data(epoch));
+        }
+        assert_eq!(cache.get(Epoch::new(0)), Some(data(0)));
+
+        cache.insert(Epoch::new(epochs), data(epochs));
+        assert_eq!(cache.get(Epoch::new(0)), None);
+        assert_eq!(cache.get(Epoch::new(1)), Some(data(1)));
+        assert_eq!(cache.get(Epoch::new(epochs)), Some(data(epochs)));
+
+        // Inserting an epoch that is already cached evicts nothing.
+        cache.insert(Epoch::new(1), data(1));
+        assert_eq!(cache.get(Epoch::new(1)), Some(data(1)));
+        assert_eq!(cache.epochs.read().len(), VALIDATOR_INCLUSION_CACHE_EPOCHS);
+    }
+}
@@ -1,14 +1,33 @@
 #![cfg(not(debug_assertions))]
 
+use beacon_chain::slot_clock::{SlotClock, TestingSlotClock};
 use beacon_chain::test_utils::{
     AttestationStrategy, BeaconChainHarness, BlockStrategy, CommonTypes, PersistedBeaconChain,
     BEACON_CHAIN_DB_KEY,
 };
+use beacon_chain::{
+    AttestationError, BeaconChain, BeaconChainError, BlockProcessingOutcome, ProposerSlashingError,
+    SyncAggregateError, SyncCommitteeMessage,
+};
+use futures::Future;
+use hashing::hash;
 use lmd_ghost::ThreadSafeReducedTree;
+use merkle_proof::{merkle_branch, verify_merkle_proof};
 use rand::Rng;
+use state_processing::per_block_processing::errors::{ExitInvalid, ExitValidationError};
+use std::sync::Arc;
 use store::{MemoryStore, Store};
-use types::test_utils::{SeedableRng, TestRandom, XorShiftRng};
-use types::{Deposit, EthSpec, Hash256, MinimalEthSpec, Slot};
+use tree_hash::{SignedRoot, TreeHash};
+use types::test_utils::{
+    SeedableRng, TestRandom, TestingDepositBuilder, TestingProposerSlashingBuilder,
+    TestingVoluntaryExitBuilder, XorShiftRng,
+};
+use types::{
+    Address, AggregateSignature, AttestationSubnetFlags, BeaconBlock, BeaconState,
+    BeaconStateError, Bitfield, BlobSidecar, BuilderBid, CommitteeCache, Deposit, Domain, Epoch,
+    Eth1Data, EthBalance, EthSpec, ExecutionPayload, ExecutionPayloadHeader, Hash256, Keypair,
+    MinimalEthSpec, RelativeEpoch, Signature, Slot,
+};
 
 // Should ideally be divisible by 3.
 pub const VALIDATOR_COUNT: usize = 24;
@@ -24,6 +43,31 @@ fn get_harness(validator_count: usize) -> BeaconChainHarness<TestForkChoice, Min
     harness
 }
 
+/// Returns a chain in a new store that starts from `state`, in place of the genesis state.
+fn chain_from_state(
+    harness: &BeaconChainHarness<TestForkChoice, MinimalEthSpec>,
+    state: BeaconState<MinimalEthSpec>,
+) -> BeaconChain<CommonTypes<TestForkChoice, MinimalEthSpec>> {
+    let spec = &harness.spec;
+
+    let mut block = BeaconBlock::empty(spec);
+    block.slot = state.slot;
+    block.state_root = state.canonical_root();
+
+    let slot_clock =
+        TestingSlotClock::new(spec.genesis_slot, state.genesis_time, spec.seconds_per_slot);
+
+    BeaconChain::from_genesis(
+        Arc::new(MemoryStore::open()),
+        slot_clock,
+        state,
+        block,
+        spec.clone(),
+        harness.chain.log.clone(),
+    )
+    .expect("should build chain from state")
+}
+
 #[test]
 fn fork() {
     let harness = get_harness(VALIDATOR_COUNT);
@@ -83,185 +127,2008 @@ fn fork() {
     );
 }
 
-#[test]
-fn finalizes_with_full_participation() {
-    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
-
-    let harness = get_harness(VALIDATOR_COUNT);
+/// Builds a canonical chain and an unattested fork from it, returning the block and state roots
+/// of the fork.
+fn build_unattested_fork(
+    harness: &BeaconChainHarness<TestForkChoice, MinimalEthSpec>,
+) -> Vec<(Hash256, Hash256)> {
+    let initial_blocks = 2;
+    let fork_blocks = 5;
 
     harness.extend_chain(
-        num_blocks_produced as usize,
+        initial_blocks,
         BlockStrategy::OnCanonicalHead,
         AttestationStrategy::AllValidators,
     );
+    harness.advance_slot();
 
-    let state = &harness.chain.head().beacon_state;
+    harness.extend_chain(
+        fork_blocks,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
 
-    assert_eq!(
-        state.slot, num_blocks_produced,
-        "head should be at the current slot"
+    // Build a fork without attestations, which fork choice will not select.
+    let mut fork_root = harness.extend_chain(
+        fork_blocks,
+        BlockStrategy::ForkCanonicalChainAt {
+            previous_slot: Slot::from(initial_blocks),
+            first_slot: Slot::from(initial_blocks + 2),
+        },
+        AttestationStrategy::SomeValidators(vec![]),
     );
-    assert_eq!(
-        state.current_epoch(),
-        num_blocks_produced / MinimalEthSpec::slots_per_epoch(),
-        "head should be at the expected epoch"
+
+    let mut fork = vec![];
+    while fork.len() < fork_blocks {
+        let block = harness
+            .chain
+            .get_block(&fork_root)
+            .unwrap()
+            .expect("fork block should be stored");
+        fork.push((fork_root, block.state_root));
+        fork_root = block.previous_block_root;
+    }
+
+    fork
+}
+
+/// Finalizes the canonical chain of `harness` beyond `fork`.
+fn finalize_beyond_fork(
+    harness: &BeaconChainHarness<TestForkChoice, MinimalEthSpec>,
+    fork: &[(Hash256, Hash256)],
+) {
+    let fork_slot = harness.chain.get_block(&fork[0].0).unwrap().unwrap().slot;
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize * 5,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
     );
-    assert_eq!(
-        state.current_justified_epoch,
-        state.current_epoch() - 1,
-        "the head should be justified one behind the current epoch"
+
+    assert!(
+        harness
+            .chain
+            .head()
+            .beacon_state
+            .finalized_epoch
+            .start_slot(MinimalEthSpec::slots_per_epoch())
+            > fork_slot,
+        "the fork should be prior to finalization"
     );
+}
+
+/// Checks that the blocks and states of `fork` were pruned and the canonical chain was retained.
+fn assert_fork_pruned(
+    chain: &BeaconChain<CommonTypes<TestForkChoice, MinimalEthSpec>>,
+    fork: &[(Hash256, Hash256)],
+) {
+    for (block_root, state_root) in fork {
+        assert!(
+            !chain.store.exists::<BeaconBlock>(block_root).unwrap(),
+            "fork blocks should be pruned"
+        );
+        assert!(
+            !chain
+                .store
+                .exists::<BeaconState<MinimalEthSpec>>(state_root)
+                .unwrap(),
+            "fork states should be pruned"
+        );
+    }
+
+    for (root, _slot) in chain.rev_iter_block_roots(chain.head().beacon_state.slot - 1) {
+        assert!(
+            chain.store.exists::<BeaconBlock>(&root).unwrap(),
+            "canonical blocks should be retained"
+        );
+    }
+}
+
+#[test]
+fn prune_block_roots_before_finalization() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let fork = build_unattested_fork(&harness);
+    finalize_beyond_fork(&harness, &fork);
+
+    assert_fork_pruned(&harness.chain, &fork);
+}
+
+#[test]
+fn prune_block_roots_before_finalization_after_restart() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+
+    // Defer pruning until after the restart.
+    harness.chain = harness
+        .chain
+        .with_fork_choice_store_prune_interval(u64::max_value());
+
+    let fork = build_unattested_fork(&harness);
+    finalize_beyond_fork(&harness, &fork);
+
+    for (block_root, _) in &fork {
+        assert!(harness
+            .chain
+            .store
+            .exists::<BeaconBlock>(block_root)
+            .unwrap());
+    }
+
+    // Blocks imported before a restart are found in the store.
+    harness.chain.persist().unwrap();
+    let restored = BeaconChain::<CommonTypes<TestForkChoice, MinimalEthSpec>>::from_store(
+        harness.chain.store.clone(),
+        harness.spec.clone(),
+        harness.chain.log.clone(),
+    )
+    .unwrap()
+    .expect("should restore chain from store");
+
     assert_eq!(
-        state.finalized_epoch,
-        state.current_epoch() - 2,
-        "the head should be finalized two behind the current epoch"
+        restored.prune_block_roots_before_finalization(),
+        Ok(fork.len())
     );
+    assert_fork_pruned(&restored, &fork);
 }
 
 #[test]
-fn finalizes_with_two_thirds_participation() {
-    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
-
-    let harness = get_harness(VALIDATOR_COUNT);
+fn fork_choice_score_override() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
 
     let two_thirds = (VALIDATOR_COUNT / 3) * 2;
-    let attesters = (0..two_thirds).collect();
+    let delay = MinimalEthSpec::default_spec().min_attestation_inclusion_delay as usize;
+
+    let honest_validators: Vec<usize> = (0..two_thirds).collect();
+    let faulty_validators: Vec<usize> = (two_thirds..VALIDATOR_COUNT).collect();
+
+    // Only the faulty validators carry any weight.
+    harness.chain = harness
+        .chain
+        .with_fork_choice_score_override(move |i| Some(if i >= two_thirds { 1 } else { 0 }));
+
+    let initial_blocks = delay + 1;
 
     harness.extend_chain(
-        num_blocks_produced as usize,
+        initial_blocks,
         BlockStrategy::OnCanonicalHead,
-        AttestationStrategy::SomeValidators(attesters),
+        AttestationStrategy::AllValidators,
     );
 
-    let state = &harness.chain.head().beacon_state;
+    harness.advance_slot();
 
-    assert_eq!(
-        state.slot, num_blocks_produced,
-        "head should be at the current slot"
-    );
-    assert_eq!(
-        state.current_epoch(),
-        num_blocks_produced / MinimalEthSpec::slots_per_epoch(),
-        "head should be at the expected epoch"
+    let honest_head = harness.extend_chain(
+        delay + 1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::SomeValidators(honest_validators),
     );
 
-    // Note: the 2/3rds tests are not justifying the immediately prior epochs because the
-    // `MIN_ATTESTATION_INCLUSION_DELAY` is preventing an adequate number of attestations being
-    // included in blocks during that epoch.
-
-    assert_eq!(
-        state.current_justified_epoch,
-        state.current_epoch() - 2,
-        "the head should be justified two behind the current epoch"
+    let faulty_head = harness.extend_chain(
+        delay + 2,
+        BlockStrategy::ForkCanonicalChainAt {
+            previous_slot: Slot::from(initial_blocks),
+            first_slot: Slot::from(initial_blocks + 2),
+        },
+        AttestationStrategy::SomeValidators(faulty_validators),
     );
+
+    assert!(honest_head != faulty_head, "forks should be distinct");
     assert_eq!(
-        state.finalized_epoch,
-        state.current_epoch() - 4,
-        "the head should be finalized three behind the current epoch"
+        harness.chain.head().beacon_block_root,
+        faulty_head,
+        "the only weighted validators should decide the canonical chain"
     );
 }
 
 #[test]
-fn does_not_finalize_with_less_than_two_thirds_participation() {
-    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
-
+fn find_common_ancestor_of_fork() {
     let harness = get_harness(VALIDATOR_COUNT);
 
-    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
-    let less_than_two_thirds = two_thirds - 1;
-    let attesters = (0..less_than_two_thirds).collect();
+    let initial_blocks = 2;
+    let fork_blocks = 10;
 
-    harness.extend_chain(
-        num_blocks_produced as usize,
+    let common_root = harness.extend_chain(
+        initial_blocks,
         BlockStrategy::OnCanonicalHead,
-        AttestationStrategy::SomeValidators(attesters),
+        AttestationStrategy::AllValidators,
     );
 
-    let state = &harness.chain.head().beacon_state;
+    harness.advance_slot();
+
+    let head_a = harness.extend_chain(
+        fork_blocks,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let head_b = harness.extend_chain(
+        fork_blocks,
+        BlockStrategy::ForkCanonicalChainAt {
+            previous_slot: Slot::from(initial_blocks),
+            first_slot: Slot::from(initial_blocks + 2),
+        },
+        AttestationStrategy::SomeValidators(vec![]),
+    );
+
+    assert!(head_a != head_b, "forks should be distinct");
 
     assert_eq!(
-        state.slot, num_blocks_produced,
-        "head should be at the current slot"
+        harness.chain.find_common_ancestor(head_a, head_b),
+        Ok(common_root)
     );
     assert_eq!(
-        state.current_epoch(),
-        num_blocks_produced / MinimalEthSpec::slots_per_epoch(),
-        "head should be at the expected epoch"
+        harness.chain.find_common_ancestor(head_b, head_a),
+        Ok(common_root)
+    );
+}
+
+#[test]
+fn find_common_ancestor_of_ancestor() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let ancestor = harness.extend_chain(
+        3,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
     );
+
+    let head = harness.extend_chain(
+        5,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
     assert_eq!(
-        state.current_justified_epoch, 0,
-        "no epoch should have been justified"
+        harness.chain.find_common_ancestor(ancestor, head),
+        Ok(ancestor)
     );
     assert_eq!(
-        state.finalized_epoch, 0,
-        "no epoch should have been finalized"
+        harness.chain.find_common_ancestor(head, ancestor),
+        Ok(ancestor)
     );
+    assert_eq!(harness.chain.find_common_ancestor(head, head), Ok(head));
 }
 
 #[test]
-fn does_not_finalize_without_attestation() {
-    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
+fn compute_domain() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let fork = harness.chain.head().beacon_state.fork.clone();
+    let spec = &harness.chain.spec;
+
+    for epoch in (0..3).map(Epoch::new) {
+        assert_eq!(
+            harness.chain.compute_domain(Domain::BeaconProposer, epoch),
+            spec.get_domain(epoch, Domain::BeaconProposer, &fork)
+        );
+        assert_eq!(
+            harness.chain.compute_domain(Domain::Randao, epoch),
+            spec.get_domain(epoch, Domain::Randao, &fork)
+        );
+    }
+
+    assert_ne!(
+        harness
+            .chain
+            .compute_domain(Domain::BeaconProposer, Epoch::new(0)),
+        harness.chain.compute_domain(Domain::Randao, Epoch::new(0))
+    );
+}
 
+#[test]
+fn verify_aggregate_attestation() {
     let harness = get_harness(VALIDATOR_COUNT);
 
     harness.extend_chain(
-        num_blocks_produced as usize,
+        MinimalEthSpec::slots_per_epoch() as usize,
         BlockStrategy::OnCanonicalHead,
-        AttestationStrategy::SomeValidators(vec![]),
+        AttestationStrategy::AllValidators,
     );
 
-    let state = &harness.chain.head().beacon_state;
+    let attestation = harness.chain.head().beacon_block.body.attestations[0].clone();
+
+    let verified = harness
+        .chain
+        .verify_aggregate_attestation(&attestation)
+        .expect("should verify attestation from the head block");
+    assert_eq!(verified.attestation(), &attestation);
+
+    // The verified attestation is pooled without its signature being checked again.
+    assert_eq!(harness.chain.process_verified_attestation(verified), Ok(()));
 
+    // An attestation for a slot later in the current epoch.
+    let mut future = attestation.clone();
+    {
+        let state = harness.chain.current_state();
+        let future_slot = state.slot + 1;
+        future.data.target_epoch = state.current_epoch();
+        future.data.shard = state
+            .get_crosslink_committees_at_slot(future_slot)
+            .expect("should get committees for the next slot")[0]
+            .shard;
+    }
+    assert!(match harness.chain.verify_aggregate_attestation(&future) {
+        Err(AttestationError::FutureSlot {
+            attestation_slot,
+            present_slot,
+        }) => attestation_slot == present_slot + 1,
+        _ => false,
+    });
+
+    let mut no_signers = attestation.clone();
+    no_signers.aggregation_bitfield = Bitfield::new();
     assert_eq!(
-        state.slot, num_blocks_produced,
-        "head should be at the current slot"
+        harness.chain.verify_aggregate_attestation(&no_signers),
+        Err(AttestationError::NoSigners)
     );
+
+    let mut bad_signature = attestation.clone();
+    bad_signature.signature = AggregateSignature::new();
     assert_eq!(
-        state.current_epoch(),
-        num_blocks_produced / MinimalEthSpec::slots_per_epoch(),
-        "head should be at the expected epoch"
+        harness.chain.verify_aggregate_attestation(&bad_signature),
+        Err(AttestationError::InvalidSignature)
     );
-    assert_eq!(
-        state.current_justified_epoch, 0,
-        "no epoch should have been justified"
+}
+
+#[test]
+fn get_attestation_reward_data() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // Included attestations are for the slot before the head.
+    let attestation = harness.chain.head().beacon_block.body.attestations[0].clone();
+
+    let data = harness
+        .chain
+        .get_attestation_reward_data(&attestation)
+        .expect("should get reward data");
+    assert!(data.source_correct);
+    assert!(data.target_correct);
+    assert!(data.head_correct);
+    assert_eq!(data.inclusion_delay, 2);
+    assert_eq!(data.participation_fraction, 1.0);
+
+    let mut wrong_head = attestation.clone();
+    wrong_head.data.beacon_block_root = Hash256::from(42);
+    let data = harness
+        .chain
+        .get_attestation_reward_data(&wrong_head)
+        .expect("should get reward data");
+    assert!(data.source_correct);
+    assert!(!data.head_correct);
+}
+
+#[test]
+fn get_indexed_attestation() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let mut attestation = harness.chain.head().beacon_block.body.attestations[0].clone();
+    let committee_len = attestation.aggregation_bitfield.num_set_bits();
+    assert!(
+        committee_len > 1,
+        "test requires a committee of two or more"
     );
+
+    let indexed = harness
+        .chain
+        .get_indexed_attestation(&attestation)
+        .expect("should index attestation");
+    assert_eq!(indexed.custody_bit_0_indices.len(), committee_len);
+    assert!(indexed.custody_bit_1_indices.is_empty());
+    assert!(indexed
+        .custody_bit_0_indices
+        .windows(2)
+        .all(|pair| pair[0] < pair[1]));
+    assert_eq!(indexed.data, attestation.data);
+
+    // Set the custody bit of the first committee member.
+    attestation.custody_bitfield.set(0, true);
+    let with_custody_bit = harness
+        .chain
+        .get_indexed_attestation(&attestation)
+        .expect("should index attestation");
+    assert_eq!(with_custody_bit.custody_bit_1_indices.len(), 1);
     assert_eq!(
-        state.finalized_epoch, 0,
-        "no epoch should have been finalized"
+        with_custody_bit.custody_bit_0_indices.len(),
+        committee_len - 1
     );
+    assert!(!with_custody_bit
+        .custody_bit_0_indices
+        .contains(&with_custody_bit.custody_bit_1_indices[0]));
 }
 
 #[test]
-fn roundtrip_operation_pool() {
-    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
-
+fn compute_attester_slashing_for_double_vote() {
     let harness = get_harness(VALIDATOR_COUNT);
 
-    // Add some attestations
     harness.extend_chain(
-        num_blocks_produced as usize,
+        MinimalEthSpec::slots_per_epoch() as usize,
         BlockStrategy::OnCanonicalHead,
         AttestationStrategy::AllValidators,
     );
-    assert!(harness.chain.op_pool.num_attestations() > 0);
 
-    // Add some deposits
-    let rng = &mut XorShiftRng::from_seed([66; 16]);
-    for _ in 0..rng.gen_range(1, VALIDATOR_COUNT) {
-        harness
-            .chain
-            .process_deposit(Deposit::random_for_test(rng))
-            .unwrap();
-    }
+    let attestation = harness.chain.head().beacon_block.body.attestations[0].clone();
 
-    // TODO: could add some other operations
-    harness.chain.persist().unwrap();
+    let mut double_vote = attestation.clone();
+    double_vote.data.beacon_block_root = Hash256::from_slice(&[1; 32]);
 
-    let key = Hash256::from_slice(&BEACON_CHAIN_DB_KEY.as_bytes());
-    let p: PersistedBeaconChain<CommonTypes<TestForkChoice, MinimalEthSpec>> =
-        harness.chain.store.get(&key).unwrap().unwrap();
+    let slashing = harness
+        .chain
+        .compute_attester_slashing_for_double_vote(&attestation, &double_vote)
+        .expect("double vote should be slashable");
+    assert_eq!(
+        slashing.attestation_1.data, attestation.data,
+        "first attestation should be the first argument"
+    );
+    assert_eq!(slashing.attestation_2.data, double_vote.data);
 
-    let restored_op_pool = p.op_pool.into_operation_pool(&p.state, &harness.spec);
+    assert!(
+        harness
+            .chain
+            .compute_attester_slashing_for_double_vote(&attestation, &attestation)
+            .is_none(),
+        "identical attestations are not slashable"
+    );
+}
 
-    assert_eq!(harness.chain.op_pool, restored_op_pool);
+#[test]
+fn validator_inclusion_data() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let attesters: Vec<usize> = (0..two_thirds).collect();
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize * 3,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::SomeValidators(attesters),
+    );
+
+    let data = harness
+        .chain
+        .get_validator_inclusion_data(Epoch::new(1))
+        .expect("should get inclusion data");
+
+    assert_eq!(data.total_validators, VALIDATOR_COUNT);
+    assert_eq!(data.participating_validators, two_thirds);
+    assert_eq!(
+        data.total_balance_gwei,
+        VALIDATOR_COUNT as u64 * harness.chain.spec.max_effective_balance
+    );
+    assert_eq!(
+        data.attesting_balance_gwei,
+        two_thirds as u64 * harness.chain.spec.max_effective_balance
+    );
+
+    let current_epoch = harness.chain.current_state().current_epoch();
+    assert!(harness
+        .chain
+        .get_validator_inclusion_data(current_epoch)
+        .is_err());
+}
+
+#[test]
+fn get_block_rewards() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let head_block_root = harness.chain.head().beacon_block_root;
+    let rewards = harness
+        .chain
+        .get_block_rewards(head_block_root)
+        .expect("should get block rewards");
+
+    assert!(rewards.attestation_reward_gwei > 0);
+    assert_eq!(rewards.proposer_slashing_reward_gwei, 0);
+    assert_eq!(rewards.attester_slashing_reward_gwei, 0);
+    assert_eq!(rewards.total_gwei, rewards.attestation_reward_gwei);
+
+    assert!(harness
+        .chain
+        .get_block_rewards(Hash256::from_slice(&[42; 32]))
+        .is_err());
+}
+
+#[test]
+fn list_attached_validators() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize * 2,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let unknown = Keypair::random().pk;
+    let pubkeys = vec![harness.keypairs[3].pk.clone(), unknown.clone()];
+
+    let statuses = harness.chain.list_attached_validators(&pubkeys);
+
+    assert_eq!(statuses.len(), 2);
+    assert_eq!(statuses[0].index, Some(3));
+    assert!(statuses[0].is_active);
+    assert!(!statuses[0].is_slashed);
+    assert!(statuses[0].balance_gwei > 0);
+    assert!(statuses[0].pending_attestations > 0);
+
+    assert_eq!(statuses[1].pubkey, unknown);
+    assert_eq!(statuses[1].index, None);
+    assert!(!statuses[1].is_active);
+}
+
+#[test]
+fn get_fork_at_epoch() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let head_fork = harness.chain.head().beacon_state.fork.clone();
+
+    assert_eq!(
+        harness.chain.get_fork_at_epoch(Epoch::new(100)),
+        Ok(head_fork)
+    );
+    assert!(harness
+        .chain
+        .get_fork_at_epoch(harness.chain.spec.far_future_epoch)
+        .is_err());
+}
+
+#[test]
+fn genesis_validators_root() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let genesis_block_root = harness.chain.genesis_block_root();
+
+    let genesis_block = harness
+        .chain
+        .get_block(&genesis_block_root)
+        .unwrap()
+        .expect("should have genesis block");
+    let genesis_state: BeaconState<MinimalEthSpec> = harness
+        .chain
+        .store
+        .get(&genesis_block.state_root)
+        .unwrap()
+        .expect("should have genesis state");
+
+    assert_eq!(
+        harness.chain.genesis_validators_root(),
+        Hash256::from_slice(&genesis_state.validator_registry.tree_hash_root())
+    );
+
+    // The root is derived from the stored genesis state when the chain is restored.
+    harness.chain.persist().unwrap();
+    let restored = BeaconChain::<CommonTypes<TestForkChoice, MinimalEthSpec>>::from_store(
+        harness.chain.store.clone(),
+        harness.spec.clone(),
+        harness.chain.log.clone(),
+    )
+    .unwrap()
+    .expect("should restore chain from store");
+    assert_eq!(
+        restored.genesis_validators_root(),
+        harness.chain.genesis_validators_root()
+    );
+}
+
+#[test]
+fn get_deposit_snapshot() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let mut state = harness.chain.head().beacon_state.clone();
+
+    let (eth1_data, deposit_index) = harness.chain.get_deposit_snapshot();
+    assert_eq!(eth1_data, state.latest_eth1_data);
+    assert_eq!(deposit_index, state.deposit_index);
+
+    let snapshot = Eth1Data {
+        deposit_root: Hash256::from_slice(&[1; 32]),
+        deposit_count: state.deposit_index + 2,
+        block_hash: Hash256::from_slice(&[2; 32]),
+    };
+    state.latest_eth1_data = snapshot.clone();
+    let chain = chain_from_state(&harness, state.clone());
+
+    assert_eq!(
+        chain.get_deposit_snapshot(),
+        (snapshot, state.deposit_index)
+    );
+}
+
+#[test]
+fn verify_deposit_tree_consistency() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    assert_eq!(harness.chain.verify_deposit_tree_consistency(), Ok(0));
+
+    let rng = &mut XorShiftRng::from_seed([42; 16]);
+    let mut deposit = Deposit::random_for_test(rng);
+    deposit.index = harness.chain.head().beacon_state.deposit_index;
+    harness.chain.op_pool.insert_deposit(deposit).unwrap();
+
+    // The stale deposit is removed rather than reported as an error.
+    assert_eq!(harness.chain.verify_deposit_tree_consistency(), Ok(1));
+    assert_eq!(harness.chain.op_pool.num_deposits(), 0);
+}
+
+#[test]
+fn compute_head_eth1_data() {
+    let harness_a = get_harness(VALIDATOR_COUNT);
+    let harness_b = get_harness(VALIDATOR_COUNT);
+
+    let eth1_data = harness_a.chain.compute_head_eth1_data();
+    let head_state = &harness_a.chain.head().beacon_state;
+
+    assert_eq!(eth1_data, harness_b.chain.compute_head_eth1_data());
+    assert_eq!(
+        eth1_data.deposit_count,
+        head_state.latest_eth1_data.deposit_count
+    );
+    assert_eq!(
+        eth1_data.deposit_root,
+        head_state.latest_eth1_data.deposit_root
+    );
+    assert_ne!(eth1_data.block_hash, Hash256::zero());
+}
+
+#[test]
+fn junk_eth1_data_only_advances_over_known_deposits() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+    harness.chain = harness.chain.with_junk_eth1_data_interval(1);
+    let voting_period = harness.spec.slots_per_eth1_voting_period as usize;
+
+    // With no deposits known, the junk deposit count must not be voted in.
+    harness.extend_chain(
+        voting_period * 2,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // Make a deposit known, with a proof against a tree holding only that deposit.
+    let (deposit, deposit_root) = {
+        let state = &harness.chain.head().beacon_state;
+        assert_eq!(state.slot, Slot::new(voting_period as u64 * 2));
+        assert_eq!(state.latest_eth1_data.deposit_count, 0);
+
+        let keypair = Keypair::random();
+        let mut builder =
+            TestingDepositBuilder::new(keypair.pk.clone(), harness.spec.max_effective_balance);
+        builder.set_index(state.deposit_index);
+        builder.sign(&keypair, state.current_epoch(), &state.fork, &harness.spec);
+        let mut deposit = builder.build();
+
+        let leaf = Hash256::from_slice(&deposit.data.tree_hash_root());
+        let depth = harness.spec.deposit_contract_tree_depth as usize;
+        let (proof, deposit_root) = merkle_branch(&[leaf], 0, depth);
+        deposit.proof = proof.into();
+
+        (deposit, deposit_root)
+    };
+    harness.chain.process_deposit(deposit).unwrap();
+
+    // The deposit is voted in and included during the next voting period.
+    harness.extend_chain(
+        voting_period,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let state = &harness.chain.head().beacon_state;
+    assert_eq!(state.slot, Slot::new(voting_period as u64 * 3));
+    assert_eq!(state.latest_eth1_data.deposit_root, deposit_root);
+    assert_eq!(state.latest_eth1_data.deposit_count, 1);
+    assert_eq!(state.deposit_index, 1);
+    assert_eq!(state.validator_registry.len(), VALIDATOR_COUNT + 1);
+}
+
+#[test]
+fn get_light_client_update() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let update = harness
+        .chain
+        .get_light_client_update(0)
+        .expect("should get update for the current period");
+
+    assert_eq!(
+        update.attested_header,
+        harness.chain.head().beacon_block.block_header()
+    );
+    assert!(harness.chain.get_light_client_update(1).is_err());
+}
+
+#[test]
+fn produce_sync_aggregate() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let slot = harness.chain.head().beacon_state.slot;
+    let head_block_root = harness.chain.head().beacon_block_root;
+    let domain = harness.chain.compute_domain(
+        Domain::SyncCommittee,
+        slot.epoch(MinimalEthSpec::slots_per_epoch()),
+    );
+    let message_for = |validator_index: usize| SyncCommitteeMessage {
+        slot,
+        beacon_block_root: head_block_root,
+        validator_index: validator_index as u64,
+        signature: Signature::new(
+            head_block_root.as_bytes(),
+            domain,
+            &harness.keypairs[validator_index].sk,
+        ),
+    };
+
+    let two_thirds = VALIDATOR_COUNT * 2 / 3;
+    for validator_index in 0..two_thirds - 1 {
+        harness
+            .chain
+            .process_sync_committee_message(message_for(validator_index))
+            .expect("should accept message");
+    }
+
+    // Messages that are not signed by their validator are not stored.
+    let mut forged = message_for(0);
+    forged.validator_index = two_thirds as u64;
+    assert_eq!(
+        harness.chain.process_sync_committee_message(forged),
+        Err(BeaconChainError::SyncAggregateError(
+            SyncAggregateError::InvalidSignature
+        ))
+    );
+    let mut unknown = message_for(0);
+    unknown.validator_index = VALIDATOR_COUNT as u64;
+    assert_eq!(
+        harness.chain.process_sync_committee_message(unknown),
+        Err(BeaconChainError::SyncAggregateError(
+            SyncAggregateError::UnknownValidator(VALIDATOR_COUNT as u64)
+        ))
+    );
+
+    assert_eq!(
+        harness.chain.produce_sync_aggregate(),
+        Err(BeaconChainError::SyncAggregateError(
+            SyncAggregateError::InsufficientParticipation {
+                got: two_thirds - 1,
+                required: two_thirds,
+            }
+        ))
+    );
+
+    // A duplicate message does not count twice.
+    harness
+        .chain
+        .process_sync_committee_message(message_for(0))
+        .expect("should ignore duplicate message");
+    harness
+        .chain
+        .process_sync_committee_message(message_for(two_thirds - 1))
+        .expect("should accept message");
+
+    let aggregate = harness
+        .chain
+        .produce_sync_aggregate()
+        .expect("should produce aggregate with two thirds participation");
+
+    for validator_index in 0..VALIDATOR_COUNT {
+        assert_eq!(
+            aggregate.sync_committee_bits.get(validator_index),
+            Ok(validator_index < two_thirds)
+        );
+    }
+}
+
+#[test]
+fn compute_rewards_and_penalties() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let attesters: Vec<usize> = (0..two_thirds).collect();
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize * 3,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::SomeValidators(attesters),
+    );
+
+    let rewards = harness
+        .chain
+        .compute_rewards_and_penalties(Epoch::new(1))
+        .expect("should compute rewards");
+
+    assert_eq!(rewards.len(), VALIDATOR_COUNT);
+    for reward in &rewards {
+        if reward.validator_index < two_thirds {
+            assert!(reward.attestation_source_reward > 0);
+            assert!(reward.attestation_target_reward > 0);
+        } else {
+            assert!(reward.attestation_source_reward < 0);
+            assert!(reward.attestation_target_reward < 0);
+            assert!(reward.attestation_head_reward < 0);
+        }
+        assert_eq!(reward.sync_committee_reward, 0);
+    }
+    assert!(rewards.iter().any(|reward| reward.proposer_reward > 0));
+
+    let current_epoch = harness.chain.current_state().current_epoch();
+    assert!(harness
+        .chain
+        .compute_rewards_and_penalties(current_epoch)
+        .is_err());
+}
+
+#[test]
+fn verify_block_proposer() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let head_block = harness.chain.head().beacon_block.clone();
+    let expected_proposer = harness
+        .chain
+        .current_state()
+        .get_beacon_proposer_index(head_block.slot, RelativeEpoch::Current, &harness.chain.spec)
+        .unwrap();
+
+    assert_eq!(
+        harness.chain.verify_block_proposer(&head_block),
+        Ok(expected_proposer)
+    );
+
+    let mut wrong_proposer_block = head_block.clone();
+    wrong_proposer_block.body.graffiti = [42; 32];
+    assert_eq!(
+        harness.chain.verify_block_proposer(&wrong_proposer_block),
+        Err(BeaconChainError::IncorrectBlockProposer {
+            block_slot: head_block.slot,
+            expected_proposer,
+        })
+    );
+
+    let mut future_block = head_block.clone();
+    future_block.slot = harness.chain.current_state().slot + 1;
+    assert_eq!(
+        harness.chain.verify_block_proposer(&future_block),
+        Err(BeaconChainError::BlockSlotInFuture {
+            block_slot: future_block.slot,
+            present_slot: harness.chain.current_state().slot,
+        })
+    );
+}
+
+#[test]
+fn verify_proposer_slashing() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let spec = &harness.spec;
+    let fork = harness.chain.head().beacon_state.fork.clone();
+
+    let sign_as = |signer_index: usize| {
+        let sk = harness.keypairs[signer_index].sk.clone();
+        let fork = fork.clone();
+        move |_: u64, message: &[u8], epoch: Epoch, domain: Domain| {
+            Signature::new(message, spec.get_domain(epoch, domain, &fork), &sk)
+        }
+    };
+
+    let slashing = TestingProposerSlashingBuilder::double_vote::<MinimalEthSpec, _>(1, sign_as(1));
+    assert_eq!(harness.chain.verify_proposer_slashing(&slashing), Ok(true));
+
+    let mut unknown_proposer = slashing.clone();
+    unknown_proposer.proposer_index = VALIDATOR_COUNT as u64;
+    assert_eq!(
+        harness.chain.verify_proposer_slashing(&unknown_proposer),
+        Err(BeaconChainError::ProposerSlashingError(
+            ProposerSlashingError::UnknownProposer(VALIDATOR_COUNT as u64)
+        ))
+    );
+
+    let mut slot_mismatch = slashing.clone();
+    slot_mismatch.header_2.slot += 1;
+    assert_eq!(
+        harness.chain.verify_proposer_slashing(&slot_mismatch),
+        Err(BeaconChainError::ProposerSlashingError(
+            ProposerSlashingError::SlotMismatch {
+                slot_1: slashing.header_1.slot,
+                slot_2: slashing.header_1.slot + 1,
+            }
+        ))
+    );
+
+    let mut identical_roots = slashing.clone();
+    identical_roots.header_2 = identical_roots.header_1.clone();
+    assert_eq!(
+        harness.chain.verify_proposer_slashing(&identical_roots),
+        Err(BeaconChainError::ProposerSlashingError(
+            ProposerSlashingError::IdenticalRoots
+        ))
+    );
+
+    // Signed by a validator other than the proposer.
+    let other_proposer =
+        TestingProposerSlashingBuilder::double_vote::<MinimalEthSpec, _>(1, sign_as(2));
+    assert_eq!(
+        harness.chain.verify_proposer_slashing(&other_proposer),
+        Err(BeaconChainError::ProposerSlashingError(
+            ProposerSlashingError::InvalidSignature
+        ))
+    );
+}
+
+#[test]
+fn get_slashings_from_pool() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let spec = &harness.spec;
+    let fork = harness.chain.head().beacon_state.fork.clone();
+
+    assert_eq!(harness.chain.get_slashings_from_pool(), (vec![], vec![]));
+
+    let sk = harness.keypairs[1].sk.clone();
+    let slashing = TestingProposerSlashingBuilder::double_vote::<MinimalEthSpec, _>(
+        1,
+        |_: u64, message: &[u8], epoch: Epoch, domain: Domain| {
+            Signature::new(message, spec.get_domain(epoch, domain, &fork), &sk)
+        },
+    );
+
+    let mut invalid = slashing.clone();
+    invalid.header_2 = invalid.header_1.clone();
+    assert!(harness.chain.process_proposer_slashing(invalid).is_err());
+    assert_eq!(harness.chain.get_slashings_from_pool(), (vec![], vec![]));
+
+    harness
+        .chain
+        .process_proposer_slashing(slashing.clone())
+        .expect("slashing should be valid");
+    assert_eq!(
+        harness.chain.get_slashings_from_pool(),
+        (vec![slashing], vec![])
+    );
+}
+
+#[test]
+fn optimistic_update() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let head_block = harness.chain.head().beacon_block.clone();
+    let result = harness
+        .chain
+        .optimistic_update(&head_block)
+        .expect("should check block");
+    assert!(!result.definitely_invalid);
+
+    harness.advance_slot();
+
+    let mut block = head_block.clone();
+    block.slot = harness.chain.current_state().slot;
+    block.previous_block_root = harness.chain.head().beacon_block_root;
+
+    let result = harness
+        .chain
+        .optimistic_update(&block)
+        .expect("should check block");
+    assert!(result.definitely_invalid);
+    assert_eq!(
+        result.reason,
+        Some("proposer signature is invalid".to_string())
+    );
+
+    let mut future_block = block.clone();
+    future_block.slot += 1;
+
+    let result = harness
+        .chain
+        .optimistic_update(&future_block)
+        .expect("should check block");
+    assert!(result.definitely_invalid);
+    assert_eq!(
+        result.reason,
+        Some("block slot is later than the present slot".to_string())
+    );
+}
+
+#[test]
+fn compute_next_shuffling() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let epoch = harness.chain.head().beacon_state.current_epoch();
+    let shuffling = harness
+        .chain
+        .compute_next_shuffling(epoch)
+        .expect("should compute next shuffling");
+
+    assert_eq!(shuffling.len(), MinimalEthSpec::slots_per_epoch() as usize);
+
+    let mut validators: Vec<usize> = shuffling.iter().flatten().cloned().collect();
+    validators.sort();
+    assert_eq!(validators, (0..VALIDATOR_COUNT).collect::<Vec<_>>());
+
+    assert_eq!(harness.chain.compute_next_shuffling(epoch), Ok(shuffling));
+}
+
+#[test]
+fn get_beacon_committees_at_slot() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let state = harness.chain.current_state().clone();
+    let expected: Vec<(u64, Vec<usize>)> = state
+        .get_crosslink_committees_at_slot(state.slot)
+        .expect("should get committees")
+        .into_iter()
+        .map(|cc| (cc.shard, cc.committee))
+        .collect();
+
+    assert_eq!(
+        harness.chain.get_beacon_committees_at_slot(state.slot),
+        Ok(expected.clone())
+    );
+    // The second call is served from the cache.
+    assert_eq!(
+        harness.chain.get_beacon_committees_at_slot(state.slot),
+        Ok(expected)
+    );
+}
+
+#[test]
+fn finalizes_with_full_participation() {
+    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
+
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let state = &harness.chain.head().beacon_state;
+
+    assert_eq!(
+        state.slot, num_blocks_produced,
+        "head should be at the current slot"
+    );
+    assert_eq!(
+        state.current_epoch(),
+        num_blocks_produced / MinimalEthSpec::slots_per_epoch(),
+        "head should be at the expected epoch"
+    );
+    assert_eq!(
+        state.current_justified_epoch,
+        state.current_epoch() - 1,
+        "the head should be justified one behind the current epoch"
+    );
+    assert_eq!(
+        state.finalized_epoch,
+        state.current_epoch() - 2,
+        "the head should be finalized two behind the current epoch"
+    );
+}
+
+#[test]
+fn finalizes_with_two_thirds_participation() {
+    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
+
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let attesters = (0..two_thirds).collect();
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::SomeValidators(attesters),
+    );
+
+    let state = &harness.chain.head().beacon_state;
+
+    assert_eq!(
+        state.slot, num_blocks_produced,
+        "head should be at the current slot"
+    );
+    assert_eq!(
+        state.current_epoch(),
+        num_blocks_produced / MinimalEthSpec::slots_per_epoch(),
+        "head should be at the expected epoch"
+    );
+
+    // Note: the 2/3rds tests are not justifying the immediately prior epochs because the
+    // `MIN_ATTESTATION_INCLUSION_DELAY` is preventing an adequate number of attestations being
+    // included in blocks during that epoch.
+
+    assert_eq!(
+        state.current_justified_epoch,
+        state.current_epoch() - 2,
+        "the head should be justified two behind the current epoch"
+    );
+    assert_eq!(
+        state.finalized_epoch,
+        state.current_epoch() - 4,
+        "the head should be finalized three behind the current epoch"
+    );
+}
+
+#[test]
+fn does_not_finalize_with_less_than_two_thirds_participation() {
+    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
+
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let less_than_two_thirds = two_thirds - 1;
+    let attesters = (0..less_than_two_thirds).collect();
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::SomeValidators(attesters),
+    );
+
+    let state = &harness.chain.head().beacon_state;
+
+    assert_eq!(
+        state.slot, num_blocks_produced,
+        "head should be at the current slot"
+    );
+    assert_eq!(
+        state.current_epoch(),
+        num_blocks_produced / MinimalEthSpec::slots_per_epoch(),
+        "head should be at the expected epoch"
+    );
+    assert_eq!(
+        state.current_justified_epoch, 0,
+        "no epoch should have been justified"
+    );
+    assert_eq!(
+        state.finalized_epoch, 0,
+        "no epoch should have been finalized"
+    );
+}
+
+#[test]
+fn does_not_finalize_without_attestation() {
+    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
+
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::SomeValidators(vec![]),
+    );
+
+    let state = &harness.chain.head().beacon_state;
+
+    assert_eq!(
+        state.slot, num_blocks_produced,
+        "head should be at the current slot"
+    );
+    assert_eq!(
+        state.current_epoch(),
+        num_blocks_produced / MinimalEthSpec::slots_per_epoch(),
+        "head should be at the expected epoch"
+    );
+    assert_eq!(
+        state.current_justified_epoch, 0,
+        "no epoch should have been justified"
+    );
+    assert_eq!(
+        state.finalized_epoch, 0,
+        "no epoch should have been finalized"
+    );
+}
+
+#[test]
+fn roundtrip_operation_pool() {
+    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
+
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    // Add some attestations
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    assert!(harness.chain.op_pool.num_attestations() > 0);
+
+    // Add some deposits
+    let rng = &mut XorShiftRng::from_seed([66; 16]);
+    for _ in 0..rng.gen_range(1, VALIDATOR_COUNT) {
+        harness
+            .chain
+            .process_deposit(Deposit::random_for_test(rng))
+            .unwrap();
+    }
+
+    // TODO: could add some other operations
+    harness.chain.persist().unwrap();
+
+    let key = Hash256::from_slice(&BEACON_CHAIN_DB_KEY.as_bytes());
+    let p: PersistedBeaconChain<CommonTypes<TestForkChoice, MinimalEthSpec>> =
+        harness.chain.store.get(&key).unwrap().unwrap();
+
+    let restored_op_pool = p.op_pool.into_operation_pool(&p.state, &harness.spec);
+
+    assert_eq!(harness.chain.op_pool, restored_op_pool);
+}
+
+#[test]
+fn save_head_to_db() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.chain.persist().unwrap();
+    let key = Hash256::from_slice(&BEACON_CHAIN_DB_KEY.as_bytes());
+    let stale: PersistedBeaconChain<CommonTypes<TestForkChoice, MinimalEthSpec>> =
+        harness.chain.store.get(&key).unwrap().unwrap();
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // Simulate a crash after the head was saved, but before the chain was persisted.
+    harness.chain.store.put(&key, &stale).unwrap();
+
+    let restored = BeaconChain::<CommonTypes<TestForkChoice, MinimalEthSpec>>::from_store(
+        harness.chain.store.clone(),
+        harness.spec.clone(),
+        harness.chain.log.clone(),
+    )
+    .unwrap()
+    .expect("should restore chain from store");
+
+    assert_eq!(
+        restored.head().beacon_block_root,
+        harness.chain.head().beacon_block_root
+    );
+    assert_eq!(
+        restored.head().beacon_state_root,
+        harness.chain.head().beacon_state_root
+    );
+}
+
+#[test]
+fn tail_slot_of_anchored_chain() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let spec = &harness.spec;
+
+    assert_eq!(harness.chain.tail_slot(), spec.genesis_slot);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // Anchor a new chain at the head, as for a trusted block root.
+    let (anchor_block, anchor_state) = {
+        let head = harness.chain.head();
+        (head.beacon_block.clone(), head.beacon_state.clone())
+    };
+    let anchor_slot = anchor_block.slot;
+    assert!(anchor_slot > spec.genesis_slot);
+
+    let store = Arc::new(MemoryStore::open());
+    let slot_clock = TestingSlotClock::new(
+        spec.genesis_slot,
+        anchor_state.genesis_time,
+        spec.seconds_per_slot,
+    );
+    let anchored = BeaconChain::<CommonTypes<TestForkChoice, MinimalEthSpec>>::from_genesis(
+        store.clone(),
+        slot_clock,
+        anchor_state,
+        anchor_block,
+        spec.clone(),
+        harness.chain.log.clone(),
+    )
+    .unwrap();
+
+    assert_eq!(anchored.tail_slot(), anchor_slot);
+
+    // The tail is restored with the chain.
+    anchored.persist().unwrap();
+    let restored = BeaconChain::<CommonTypes<TestForkChoice, MinimalEthSpec>>::from_store(
+        store,
+        spec.clone(),
+        harness.chain.log.clone(),
+    )
+    .unwrap()
+    .expect("should restore chain from store");
+
+    assert_eq!(restored.tail_slot(), anchor_slot);
+}
+
+/// Signs the randao reveal of the proposer at `slot`.
+fn randao_reveal(
+    harness: &BeaconChainHarness<TestForkChoice, MinimalEthSpec>,
+    slot: Slot,
+) -> Signature {
+    let proposer_index = harness.chain.block_proposer(slot).unwrap();
+    let epoch = slot.epoch(MinimalEthSpec::slots_per_epoch());
+    let fork = harness.chain.head().beacon_state.fork.clone();
+    let domain = harness.spec.get_domain(epoch, Domain::Randao, &fork);
+    Signature::new(
+        &epoch.tree_hash_root(),
+        domain,
+        &harness.keypairs[proposer_index].sk,
+    )
+}
+
+#[test]
+fn produce_block_v2_sets_graffiti() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        2,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    harness.advance_slot();
+
+    let slot = harness.chain.read_slot_clock().unwrap();
+    let randao_reveal = randao_reveal(&harness, slot);
+
+    let mut graffiti = [0; 32];
+    graffiti[..10].copy_from_slice(b"Lighthouse");
+
+    let (block, _state) = harness
+        .chain
+        .produce_block_v2(randao_reveal, graffiti)
+        .expect("should produce block");
+
+    assert_eq!(block.slot, slot);
+    assert_eq!(block.body.graffiti, graffiti);
+}
+
+#[test]
+fn produce_blinded_block_with_local_header() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        2,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    harness.advance_slot();
+
+    let slot = harness.chain.read_slot_clock().unwrap();
+    let randao_reveal = randao_reveal(&harness, slot);
+
+    let expected_header = harness
+        .chain
+        .get_execution_payload_header(slot)
+        .expect("should build local header");
+    assert_eq!(
+        expected_header,
+        ExecutionPayloadHeader::from(&ExecutionPayload::default())
+    );
+
+    let (block, header, _state) = harness
+        .chain
+        .produce_blinded_block(randao_reveal, [0; 32])
+        .expect("should produce blinded block");
+
+    assert_eq!(block.slot, slot);
+    assert_eq!(header, expected_header);
+}
+
+#[test]
+fn compute_shuffling_proof() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let state = harness.chain.head().beacon_state.clone();
+    let epoch = state.current_epoch();
+    let shuffling = CommitteeCache::initialized(&state, epoch, &harness.spec)
+        .unwrap()
+        .shuffling()
+        .to_vec();
+    let seed = state.generate_seed(epoch, &harness.spec).unwrap();
+
+    let leaves = shuffling
+        .iter()
+        .map(|&index| {
+            let mut leaf = Hash256::zero();
+            leaf.as_bytes_mut()[0..8].copy_from_slice(&(index as u64).to_le_bytes());
+            leaf
+        })
+        .collect::<Vec<_>>();
+    let depth = leaves.len().next_power_of_two().trailing_zeros() as usize;
+    let (_, shuffling_root) = merkle_branch(&leaves, 0, depth);
+    let root = Hash256::from_slice(&hash(
+        &[shuffling_root.as_bytes(), seed.as_bytes()].concat(),
+    ));
+
+    for (position, &validator_index) in shuffling.iter().enumerate() {
+        let proof = harness
+            .chain
+            .compute_shuffling_proof(validator_index, epoch)
+            .expect("should compute proof");
+
+        assert_eq!(proof.last(), Some(&seed));
+        assert!(verify_merkle_proof(
+            leaves[position],
+            &proof,
+            depth + 1,
+            position,
+            root
+        ));
+    }
+
+    assert_eq!(
+        harness
+            .chain
+            .compute_shuffling_proof(VALIDATOR_COUNT, epoch),
+        Err(BeaconChainError::ValidatorNotActive {
+            validator_index: VALIDATOR_COUNT,
+            epoch
+        })
+    );
+}
+
+#[test]
+fn next_epoch_participation_flags() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let state = harness.chain.head().beacon_state.clone();
+    let next_epoch = state.next_epoch();
+    let committee_cache = CommitteeCache::initialized(&state, next_epoch, &harness.spec).unwrap();
+
+    for validator_index in 0..VALIDATOR_COUNT {
+        let shard = committee_cache
+            .get_attestation_duties(validator_index)
+            .expect("all validators should be active")
+            .shard;
+
+        let flags = harness
+            .chain
+            .compute_next_epoch_participation_flags(validator_index)
+            .expect("should compute flags");
+
+        assert_eq!(
+            flags.subnets(),
+            vec![AttestationSubnetFlags::subnet_for_shard(shard)]
+        );
+    }
+
+    assert_eq!(
+        harness
+            .chain
+            .compute_next_epoch_participation_flags(VALIDATOR_COUNT),
+        Err(BeaconChainError::ValidatorNotActive {
+            validator_index: VALIDATOR_COUNT,
+            epoch: next_epoch
+        })
+    );
+}
+
+#[test]
+fn get_validator_withdrawal_credentials() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let state = harness.chain.head().beacon_state.clone();
+
+    for (index, validator) in state.validator_registry.iter().enumerate() {
+        assert_eq!(
+            harness.chain.get_validator_withdrawal_credentials(index),
+            Some(validator.withdrawal_credentials)
+        );
+    }
+    assert_eq!(
+        harness
+            .chain
+            .get_validator_withdrawal_credentials(VALIDATOR_COUNT),
+        None
+    );
+
+    assert_eq!(
+        harness
+            .chain
+            .get_validators_withdrawal_credentials(&[1, VALIDATOR_COUNT, 0]),
+        vec![
+            Some(state.validator_registry[1].withdrawal_credentials),
+            None,
+            Some(state.validator_registry[0].withdrawal_credentials),
+        ]
+    );
+}
+
+#[test]
+fn compute_expected_withdrawals() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let mut state = harness.chain.head().beacon_state.clone();
+
+    assert_eq!(
+        harness.chain.compute_expected_withdrawals(Epoch::new(8)),
+        Ok(vec![])
+    );
+
+    state.validator_registry[0].withdrawable_epoch = Epoch::new(2);
+    state.validator_registry[0].withdrawal_credentials = Hash256::from_slice(&[7; 32]);
+    // Validators without a balance have nothing to withdraw.
+    state.validator_registry[1].withdrawable_epoch = Epoch::new(2);
+    state.balances[1] = 0;
+    state.validator_registry[2].withdrawable_epoch = Epoch::new(3);
+    state.validator_registry[2].withdrawal_credentials = Hash256::from_slice(&[9; 32]);
+
+    let balances = state.balances.clone();
+    let chain = chain_from_state(&harness, state);
+
+    assert_eq!(
+        chain.compute_expected_withdrawals(Epoch::new(1)),
+        Ok(vec![])
+    );
+    assert_eq!(
+        chain.compute_expected_withdrawals(Epoch::new(2)),
+        Ok(vec![(Address::from_slice(&[7; 20]), balances[0])])
+    );
+    let expected = vec![
+        (Address::from_slice(&[7; 20]), balances[0]),
+        (Address::from_slice(&[9; 20]), balances[2]),
+    ];
+    assert_eq!(
+        chain.compute_expected_withdrawals(Epoch::new(3)),
+        Ok(expected.clone())
+    );
+    // Served from the cache.
+    assert_eq!(
+        chain.compute_expected_withdrawals(Epoch::new(3)),
+        Ok(expected)
+    );
+}
+
+#[test]
+fn compute_voluntary_exit_epoch() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let state = harness.chain.head().beacon_state.clone();
+    let expected = state.get_delayed_activation_exit_epoch(state.current_epoch(), &harness.spec);
+
+    for index in 0..VALIDATOR_COUNT {
+        assert_eq!(
+            harness.chain.compute_voluntary_exit_epoch(index),
+            Ok(expected)
+        );
+    }
+
+    // The head state must not be modified.
+    assert!(harness
+        .chain
+        .head()
+        .beacon_state
+        .validator_registry
+        .iter()
+        .all(|validator| validator.exit_epoch == harness.spec.far_future_epoch));
+
+    assert_eq!(
+        harness.chain.compute_voluntary_exit_epoch(VALIDATOR_COUNT),
+        Err(BeaconChainError::BeaconStateError(
+            BeaconStateError::UnknownValidator
+        ))
+    );
+}
+
+#[test]
+fn compute_expected_activation_epoch() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let spec = &harness.spec;
+
+    // Far fewer validators than `churn_limit_quotient`, so the minimum applies.
+    assert_eq!(
+        harness.chain.compute_activation_churn_limit(Epoch::new(0)),
+        spec.min_per_epoch_churn_limit
+    );
+
+    // Genesis validators already have an activation epoch.
+    for index in 0..VALIDATOR_COUNT {
+        assert_eq!(
+            harness.chain.compute_expected_activation_epoch(index),
+            Ok(MinimalEthSpec::genesis_epoch())
+        );
+    }
+
+    assert_eq!(
+        harness
+            .chain
+            .compute_expected_activation_epoch(VALIDATOR_COUNT),
+        Err(BeaconChainError::BeaconStateError(
+            BeaconStateError::UnknownValidator
+        ))
+    );
+}
+
+#[test]
+fn import_voluntary_exit() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let spec = &harness.spec;
+    let state = harness.chain.head().beacon_state.clone();
+
+    let mut builder = TestingVoluntaryExitBuilder::new(state.current_epoch(), 0);
+    builder.sign(&harness.keypairs[0].sk, &state.fork, spec);
+    let exit = builder.build();
+
+    // Genesis validators have not been active for `persistent_committee_period`.
+    assert_eq!(
+        harness.chain.import_voluntary_exit(exit.clone()),
+        Err(BeaconChainError::ExitValidationError(
+            ExitValidationError::Invalid(ExitInvalid::TooYoungToLeave {
+                lifespan: state.current_epoch() - MinimalEthSpec::genesis_epoch(),
+                expected: spec.persistent_committee_period,
+            })
+        ))
+    );
+    assert!(harness
+        .chain
+        .op_pool
+        .get_voluntary_exits(&state, spec)
+        .is_empty());
+
+    let mut unknown_validator = exit;
+    unknown_validator.validator_index = VALIDATOR_COUNT as u64;
+    assert_eq!(
+        harness.chain.import_voluntary_exit(unknown_validator),
+        Err(BeaconChainError::BeaconStateError(
+            BeaconStateError::UnknownValidator
+        ))
+    );
+}
+
+#[test]
+fn import_voluntary_exit_once() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+    // Allow genesis validators to exit immediately.
+    harness.chain.spec.persistent_committee_period = 0;
+
+    let validator_index = 3;
+    let exit = {
+        let state = &harness.chain.head().beacon_state;
+        let mut builder =
+            TestingVoluntaryExitBuilder::new(state.current_epoch(), validator_index as u64);
+        builder.sign(
+            &harness.keypairs[validator_index].sk,
+            &state.fork,
+            &harness.chain.spec,
+        );
+        builder.build()
+    };
+
+    assert_eq!(harness.chain.import_voluntary_exit(exit.clone()), Ok(()));
+    assert!(harness
+        .chain
+        .op_pool
+        .has_voluntary_exit(validator_index as u64));
+
+    // The exit is pending in the pool, so it is not imported (or published) again.
+    assert_eq!(
+        harness.chain.import_voluntary_exit(exit.clone()),
+        Err(BeaconChainError::AlreadyExiting {
+            validator_index: validator_index as u64
+        })
+    );
+
+    // Include the exit in a block, initiating the validator's exit.
+    harness.extend_chain(
+        1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    let exit_epoch =
+        harness.chain.head().beacon_state.validator_registry[validator_index].exit_epoch;
+    assert_ne!(exit_epoch, harness.chain.spec.far_future_epoch);
+
+    assert_eq!(
+        harness.chain.import_voluntary_exit(exit),
+        Err(BeaconChainError::AlreadyExiting {
+            validator_index: validator_index as u64
+        })
+    );
+}
+
+#[test]
+fn get_historical_state_root() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    // Build past `SLOTS_PER_HISTORICAL_ROOT` so that earlier roots must be read from the store.
+    let num_blocks_produced =
+        MinimalEthSpec::slots_per_historical_root() + MinimalEthSpec::slots_per_epoch() as usize;
+    harness.extend_chain(
+        num_blocks_produced,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let (head_slot, head_state_root) = {
+        let head = harness.chain.head();
+        (head.beacon_state.slot, head.beacon_state_root)
+    };
+
+    assert_eq!(
+        harness.chain.get_historical_state_root(head_slot),
+        Ok(head_state_root)
+    );
+
+    let blocks: Vec<BeaconBlock> = harness.chain.rev_iter_blocks(head_slot - 1).collect();
+    assert_eq!(blocks.len() as u64, head_slot.as_u64());
+    for block in blocks {
+        assert_eq!(
+            harness.chain.get_historical_state_root(block.slot),
+            Ok(block.state_root),
+            "slot {}",
+            block.slot
+        );
+    }
+
+    assert_eq!(
+        harness.chain.get_historical_state_root(head_slot + 1),
+        Err(BeaconChainError::NoStateForSlot(head_slot + 1))
+    );
+}
+
+#[test]
+fn compute_builder_bid_validity() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let keypair = Keypair::random();
+    let domain = {
+        let state = harness.chain.current_state();
+        harness
+            .spec
+            .get_domain(state.current_epoch(), Domain::BuilderBid, &state.fork)
+    };
+
+    let mut bid = BuilderBid {
+        header: ExecutionPayloadHeader::default(),
+        value: EthBalance::from(100),
+        pubkey: keypair.pk.clone(),
+        signature: Signature::empty_signature(),
+    };
+    bid.signature = Signature::new(&bid.signed_root(), domain, &keypair.sk);
+
+    assert_eq!(harness.chain.compute_builder_bid_validity(&bid), Ok(true));
+
+    let mut forged = bid.clone();
+    forged.pubkey = Keypair::random().pk;
+    assert_eq!(
+        harness.chain.compute_builder_bid_validity(&forged),
+        Ok(false)
+    );
+
+    let mut tampered = bid.clone();
+    tampered.value = EthBalance::from(1_000);
+    assert_eq!(
+        harness.chain.compute_builder_bid_validity(&tampered),
+        Ok(false)
+    );
+
+    let chain = harness
+        .chain
+        .with_min_builder_bid_value(EthBalance::from(101));
+    assert_eq!(chain.compute_builder_bid_validity(&bid), Ok(false));
+}
+
+#[test]
+fn notify_new_payload_without_execution_engine() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    assert_eq!(
+        harness
+            .chain
+            .notify_new_payload(&ExecutionPayload::default())
+            .wait(),
+        Err(BeaconChainError::NoExecutionEngine)
+    );
+}
+
+/// Returns the blocks of `harness`'s canonical chain, excluding genesis, in ascending slot order.
+fn get_chain_segment(
+    harness: &BeaconChainHarness<TestForkChoice, MinimalEthSpec>,
+) -> Vec<BeaconBlock> {
+    let mut blocks: Vec<BeaconBlock> = harness
+        .chain
+        .chain_dump()
+        .expect("should dump chain")
+        .into_iter()
+        .map(|checkpoint| checkpoint.beacon_block)
+        .filter(|block| block.slot > 0)
+        .collect();
+    blocks.reverse();
+    blocks
+}
+
+/// Returns a harness whose slot clock matches that of `harness`, without any blocks.
+fn get_syncing_harness(
+    harness: &BeaconChainHarness<TestForkChoice, MinimalEthSpec>,
+) -> BeaconChainHarness<TestForkChoice, MinimalEthSpec> {
+    let syncing = get_harness(VALIDATOR_COUNT);
+    while syncing.chain.read_slot_clock() < harness.chain.read_slot_clock() {
+        syncing.advance_slot();
+    }
+    syncing
+}
+
+#[test]
+fn process_chain_segment_imports_valid_segment() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let num_blocks = MinimalEthSpec::slots_per_epoch() as usize * 2;
+
+    harness.extend_chain(
+        num_blocks,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let blocks = get_chain_segment(&harness);
+    assert_eq!(blocks.len(), num_blocks);
+
+    let syncing = get_syncing_harness(&harness);
+    let outcomes = syncing
+        .chain
+        .process_chain_segment(blocks.clone())
+        .expect("should process segment");
+
+    assert_eq!(outcomes.len(), num_blocks);
+    for (block, outcome) in blocks.iter().zip(outcomes) {
+        assert_eq!(
+            outcome,
+            BlockProcessingOutcome::Processed {
+                block_root: block.canonical_root()
+            }
+        );
+    }
+}
+
+#[test]
+fn process_chain_segment_rejects_invalid_signature() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let num_blocks = MinimalEthSpec::slots_per_epoch() as usize * 2;
+
+    harness.extend_chain(
+        num_blocks,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // Give one block in the second epoch the signature of the block after it. The block root is
+    // unchanged, so the segment remains a chain.
+    let invalid_index = MinimalEthSpec::slots_per_epoch() as usize + 2;
+    let mut blocks = get_chain_segment(&harness);
+    blocks[invalid_index].signature = blocks[invalid_index + 1].signature.clone();
+
+    let syncing = get_syncing_harness(&harness);
+    let outcomes = syncing
+        .chain
+        .process_chain_segment(blocks.clone())
+        .expect("should process segment");
+
+    // Blocks prior to the invalid block are imported, processing stops at the invalid block.
+    assert_eq!(outcomes.len(), invalid_index + 1);
+    for (block, outcome) in blocks.iter().zip(&outcomes).take(invalid_index) {
+        assert_eq!(
+            *outcome,
+            BlockProcessingOutcome::Processed {
+                block_root: block.canonical_root()
+            }
+        );
+    }
+    match outcomes[invalid_index] {
+        BlockProcessingOutcome::PerBlockProcessingError(_) => {}
+        ref other => panic!("expected invalid signature, got {:?}", other),
+    }
+    assert_eq!(
+        syncing
+            .chain
+            .is_new_block_root(&blocks[invalid_index].canonical_root()),
+        Ok(true)
+    );
+}
+
+#[test]
+fn get_blob_sidecars_skips_missing_indices() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        2,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let (head_root, parent_root) = {
+        let head = harness.chain.head();
+        (
+            head.beacon_block_root,
+            head.beacon_block.previous_block_root,
+        )
+    };
+
+    assert_eq!(harness.chain.get_blob_sidecars(head_root), Ok(vec![]));
+
+    // Sidecars at indices 0 and 2, but not 1.
+    let sidecars: Vec<BlobSidecar> = [0, 2]
+        .iter()
+        .map(|&index| BlobSidecar {
+            index,
+            blob: vec![index as u8; 128].into_boxed_slice(),
+            kzg_commitment: [1; 48],
+            kzg_proof: [2; 48],
+        })
+        .collect();
+    for sidecar in &sidecars {
+        harness
+            .chain
+            .store
+            .put_blob_sidecar(&head_root, sidecar)
+            .unwrap();
+    }
+    harness
+        .chain
+        .store
+        .put_blob_sidecar(&parent_root, &sidecars[0])
+        .unwrap();
+
+    assert_eq!(harness.chain.get_blob_sidecars(head_root), Ok(sidecars));
+
+    let unknown_root = Hash256::from_slice(&[42; 32]);
+    assert_eq!(
+        harness.chain.get_blob_sidecars(unknown_root),
+        Err(BeaconChainError::MissingBeaconBlock(unknown_root))
+    );
 }
@@ -1,3 +1,4 @@
+use crate::config::ClientGenesis;
 use beacon_chain::{
     lmd_ghost::{LmdGhost, ThreadSafeReducedTree},
     slot_clock::SystemTimeSlotClock,
@@ -9,7 +10,10 @@ use slot_clock::SlotClock;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use tree_hash::TreeHash;
-use types::{test_utils::TestingBeaconStateBuilder, BeaconBlock, ChainSpec, EthSpec, Hash256};
+use types::{
+    test_utils::TestingBeaconStateBuilder, BeaconBlock, BeaconState, ChainSpec, EthSpec, Hash256,
+    Keypair, Slot,
+};
 
 /// The number initial validators when starting the `Minimal`.
 const TESTNET_VALIDATOR_COUNT: usize = 16;
@@ -19,12 +23,24 @@ pub trait InitialiseBeaconChain<T: BeaconChainTypes> {
     fn initialise_beacon_chain(
         store: Arc<T::Store>,
         spec: ChainSpec,
+        genesis: &ClientGenesis,
         log: Logger,
-    ) -> BeaconChain<T> {
-        maybe_load_from_store_for_testnet::<_, T::Store, T::EthSpec>(store, spec, log)
+    ) -> Result<BeaconChain<T>, String> {
+        maybe_load_from_store_for_testnet::<_, T::Store, T::EthSpec>(store, spec, genesis, log)
     }
 }
 
+/// Returns the keypairs of the validators in the testnet genesis state.
+pub fn testnet_keypairs<E: EthSpec>(spec: &ChainSpec) -> Vec<Keypair> {
+    let (_state, keypairs) = TestingBeaconStateBuilder::<E>::from_default_keypairs_file_if_exists(
+        TESTNET_VALIDATOR_COUNT,
+        spec,
+    )
+    .build();
+
+    keypairs
+}
+
 #[derive(Clone)]
 pub struct ClientType<S: Store, E: EthSpec> {
     _phantom_t: PhantomData<S>,
@@ -39,12 +55,14 @@ impl<S: Store, E: EthSpec + Clone> BeaconChainTypes for ClientType<S, E> {
 }
 impl<T: Store, E: EthSpec, X: BeaconChainTypes> InitialiseBeaconChain<X> for ClientType<T, E> {}
 
-/// Loads a `BeaconChain` from `store`, if it exists. Otherwise, create a new chain from genesis.
+/// Loads a `BeaconChain` from `store`, if it exists. Otherwise, create a new chain as described by
+/// `genesis`.
 fn maybe_load_from_store_for_testnet<T, U: Store, V: EthSpec>(
     store: Arc<U>,
     spec: ChainSpec,
+    genesis: &ClientGenesis,
     log: Logger,
-) -> BeaconChain<T>
+) -> Result<BeaconChain<T>, String>
 where
     T: BeaconChainTypes<Store = U, EthSpec = V>,
     T::LmdGhost: LmdGhost<U, V>,
@@ -59,7 +77,9 @@ where
             "best_slot" => beacon_chain.best_slot(),
         );
 
-        beacon_chain
+        Ok(beacon_chain)
+    } else if let ClientGenesis::TrustedBlockRoot { root, slot } = genesis {
+        load_from_trusted_block_root(store, *root, *slot, spec, log)
     } else {
         info!(log, "Initializing new BeaconChain from genesis");
         let state_builder = TestingBeaconStateBuilder::from_default_keypairs_file_if_exists(
@@ -79,7 +99,6 @@ where
         );
 
         // Genesis chain
-        //TODO: Handle error correctly
         BeaconChain::from_genesis(
             store,
             slot_clock,
@@ -88,6 +107,150 @@ where
             spec,
             log.clone(),
         )
-        .expect("Terminate if beacon chain generation fails")
+        .map_err(|e| format!("Failed to initialize beacon chain from genesis: {:?}", e))
+    }
+}
+
+/// Creates a new chain anchored at the trusted block at `root`, which must be at `slot`.
+///
+/// The block and its state are read from the local `store` (e.g., a restored snapshot). The
+/// anchor takes the place of the genesis block, so no blocks prior to it are available.
+fn load_from_trusted_block_root<T, U: Store, V: EthSpec>(
+    store: Arc<U>,
+    root: Hash256,
+    slot: Slot,
+    spec: ChainSpec,
+    log: Logger,
+) -> Result<BeaconChain<T>, String>
+where
+    T: BeaconChainTypes<Store = U, EthSpec = V>,
+    T::LmdGhost: LmdGhost<U, V>,
+{
+    info!(
+        log,
+        "Initializing new BeaconChain from trusted block root";
+        "root" => format!("{}", root),
+        "slot" => slot,
+    );
+
+    let block = store
+        .get::<BeaconBlock>(&root)
+        .map_err(|e| format!("Unable to read trusted block: {:?}", e))?
+        .ok_or_else(|| format!("Trusted block {} is not in the local store", root))?;
+
+    if block.slot != slot {
+        return Err(format!(
+            "Trusted block {} is at slot {}, not {}",
+            root, block.slot, slot
+        ));
+    }
+
+    let state = store
+        .get::<BeaconState<V>>(&block.state_root)
+        .map_err(|e| format!("Unable to read trusted state: {:?}", e))?
+        .ok_or_else(|| format!("State {} is not in the local store", block.state_root))?;
+
+    let slot_clock =
+        T::SlotClock::new(spec.genesis_slot, state.genesis_time, spec.seconds_per_slot);
+
+    BeaconChain::from_genesis(store, slot_clock, state, block, spec, log).map_err(|e| {
+        format!(
+            "Failed to initialize beacon chain from trusted block: {:?}",
+            e
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::store::MemoryStore;
+    use types::MinimalEthSpec;
+
+    type TestType = ClientType<MemoryStore, MinimalEthSpec>;
+
+    fn initialise(
+        store: Arc<MemoryStore>,
+        genesis: &ClientGenesis,
+    ) -> Result<BeaconChain<TestType>, String> {
+        <TestType as InitialiseBeaconChain<TestType>>::initialise_beacon_chain(
+            store,
+            MinimalEthSpec::default_spec(),
+            genesis,
+            Logger::root(slog::Discard, slog::o!()),
+        )
+    }
+
+    /// Stores a block at `slot` and its state in a new store, returning the store and block root.
+    fn store_with_trusted_block(slot: Slot) -> (Arc<MemoryStore>, Hash256) {
+        let spec = MinimalEthSpec::default_spec();
+        let store = Arc::new(MemoryStore::open());
+
+        let (mut state, _keypairs) =
+            TestingBeaconStateBuilder::<MinimalEthSpec>::from_deterministic_keypairs(8, &spec)
+                .build();
+        state.slot = slot;
+        let state_root = Hash256::from_slice(&state.tree_hash_root());
+
+        let mut block = BeaconBlock::empty(&spec);
+        block.slot = slot;
+        block.state_root = state_root;
+        let block_root = block.block_header().canonical_root();
+
+        store.put(&state_root, &state).unwrap();
+        store.put(&block_root, &block).unwrap();
+
+        (store, block_root)
+    }
+
+    #[test]
+    fn anchors_chain_at_trusted_block() {
+        let slot = Slot::new(16);
+        let (store, root) = store_with_trusted_block(slot);
+
+        let chain = initialise(store, &ClientGenesis::TrustedBlockRoot { root, slot })
+            .expect("should initialize chain from trusted block");
+
+        assert_eq!(chain.head().beacon_block_root, root);
+        assert_eq!(chain.tail_slot(), slot);
+    }
+
+    #[test]
+    fn rejects_unknown_or_misplaced_trusted_block() {
+        let slot = Slot::new(16);
+        let (store, root) = store_with_trusted_block(slot);
+        let unknown_root = Hash256::from_slice(&[42; 32]);
+
+        let result = initialise(
+            store.clone(),
+            &ClientGenesis::TrustedBlockRoot {
+                root: unknown_root,
+                slot,
+            },
+        );
+        assert_eq!(
+            result.err(),
+            Some(format!(
+                "Trusted block {} is not in the local store",
+                unknown_root
+            ))
+        );
+
+        let result = initialise(
+            store,
+            &ClientGenesis::TrustedBlockRoot {
+                root,
+                slot: slot + 1,
+            },
+        );
+        assert_eq!(
+            result.err(),
+            Some(format!(
+                "Trusted block {} is at slot {}, not {}",
+                root,
+                slot,
+                slot + 1
+            ))
+        );
     }
 }
@@ -0,0 +1,117 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes, BlockProcessingOutcome};
+use eth2_libp2p::{PubsubMessage, TopicBuilder, BEACON_PUBSUB_TOPIC};
+use network::NetworkMessage;
+use slog::{debug, error, info, warn};
+use tokio::sync::mpsc;
+use tree_hash::{SignedRoot, TreeHash};
+use types::{Domain, EthSpec, Keypair, Signature};
+
+/// If one of `keypairs` is the proposer at the present slot, produces a block, imports it into
+/// `chain` and publishes it to the network.
+pub fn produce_block_if_proposer<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    keypairs: &[Keypair],
+    network_send: &mut mpsc::UnboundedSender<NetworkMessage>,
+    log: &slog::Logger,
+) {
+    let slot = match chain.read_slot_clock() {
+        Some(slot) => slot,
+        None => return,
+    };
+
+    let proposer_index = match chain.block_proposer(slot) {
+        Ok(index) => index,
+        Err(e) => {
+            warn!(
+                log,
+                "Unable to determine block proposer";
+                "slot" => slot,
+                "error" => format!("{:?}", e),
+            );
+            return;
+        }
+    };
+
+    let (pubkey, fork) = {
+        let state = chain.current_state();
+        match state.validator_registry.get(proposer_index) {
+            Some(validator) => (validator.pubkey.clone(), state.fork.clone()),
+            None => return,
+        }
+    };
+
+    let sk = match keypairs.iter().find(|keypair| keypair.pk == pubkey) {
+        Some(keypair) => &keypair.sk,
+        None => {
+            debug!(
+                log,
+                "Not the block proposer";
+                "slot" => slot,
+                "proposer" => proposer_index,
+            );
+            return;
+        }
+    };
+
+    let epoch = slot.epoch(T::EthSpec::slots_per_epoch());
+    let randao_reveal = {
+        let domain = chain.spec.get_domain(epoch, Domain::Randao, &fork);
+        Signature::new(&epoch.tree_hash_root(), domain, sk)
+    };
+
+    let mut block = match chain.produce_block(randao_reveal) {
+        Ok((block, _state)) => block,
+        Err(e) => {
+            error!(
+                log,
+                "Block production failed";
+                "slot" => slot,
+                "error" => format!("{:?}", e),
+            );
+            return;
+        }
+    };
+
+    block.signature = {
+        let domain = chain.spec.get_domain(epoch, Domain::BeaconProposer, &fork);
+        Signature::new(&block.signed_root(), domain, sk)
+    };
+
+    match chain.process_block(block.clone()) {
+        Ok(BlockProcessingOutcome::Processed { block_root }) => {
+            info!(
+                log,
+                "Produced block";
+                "slot" => slot,
+                "proposer" => proposer_index,
+                "block_root" => format!("{}", block_root),
+            );
+
+            let topic = TopicBuilder::new(BEACON_PUBSUB_TOPIC).build();
+            network_send
+                .try_send(NetworkMessage::Publish {
+                    topics: vec![topic],
+                    message: Box::new(PubsubMessage::Block(block)),
+                })
+                .unwrap_or_else(|e| {
+                    error!(
+                        log,
+                        "Unable to publish produced block";
+                        "error" => format!("{:?}", e),
+                    )
+                });
+        }
+        Ok(outcome) => warn!(
+            log,
+            "Produced block was not imported";
+            "slot" => slot,
+            "outcome" => format!("{:?}", outcome),
+        ),
+        Err(e) => error!(
+            log,
+            "Unable to import produced block";
+            "slot" => slot,
+            "error" => format!("{:?}", e),
+        ),
+    }
+}
@@ -1,11 +1,15 @@
+use crate::log_config::{parse_log_level, LogConfig, LogFormat};
+use beacon_chain::execution_engine::DEFAULT_EXECUTION_ENGINE_TIMEOUT;
 use clap::ArgMatches;
+use eth2_config::Eth2Config;
 use http_server::HttpServerConfig;
 use network::NetworkConfig;
 use serde_derive::{Deserialize, Serialize};
-use slog::{info, o, Drain};
-use std::fs::{self, OpenOptions};
+use slog::{info, Level};
+use std::fs;
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::str::FromStr;
+use types::{Hash256, Slot};
 
 /// The core configuration of a Lighthouse beacon node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,9 +18,43 @@ pub struct Config {
     pub db_type: String,
     db_name: String,
     pub log_file: PathBuf,
+    /// The minimum level of records written to the log.
+    #[serde(with = "serde_log_level", default = "default_log_level")]
+    pub log_level: Level,
     pub network: network::NetworkConfig,
     pub rpc: rpc::RPCConfig,
     pub http: HttpServerConfig,
+    /// If `true`, produce blocks for the testnet validators instead of relying on a validator
+    /// client.
+    #[serde(default)]
+    pub produce_blocks: bool,
+    /// The Engine API endpoint of the execution engine, if any.
+    #[serde(default)]
+    pub execution_endpoint: Option<String>,
+    /// A file holding the hex-encoded secret used to authenticate with the execution engine.
+    #[serde(default)]
+    pub execution_jwt_secret: Option<PathBuf>,
+    /// The time allowed for the execution engine to respond, in milliseconds.
+    #[serde(default = "default_execution_timeout_ms")]
+    pub execution_timeout_ms: u64,
+    /// How to initialize the chain when the store is empty.
+    #[serde(default)]
+    pub genesis: ClientGenesis,
+}
+
+/// How the client initializes its `BeaconChain` when none exists in the store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClientGenesis {
+    /// Start from the deterministic testnet genesis state.
+    Testnet,
+    /// Start from the block at `root`, trusted by the operator, which must be in the local store.
+    TrustedBlockRoot { root: Hash256, slot: Slot },
+}
+
+impl Default for ClientGenesis {
+    fn default() -> Self {
+        ClientGenesis::Testnet
+    }
 }
 
 impl Default for Config {
@@ -24,6 +62,7 @@ impl Default for Config {
         Self {
             data_dir: PathBuf::from(".lighthouse"),
             log_file: PathBuf::from(""),
+            log_level: default_log_level(),
             db_type: "disk".to_string(),
             db_name: "chain_db".to_string(),
             // Note: there are no default bootnodes specified.
@@ -31,11 +70,47 @@ impl Default for Config {
             network: NetworkConfig::new(),
             rpc: rpc::RPCConfig::default(),
             http: HttpServerConfig::default(),
+            produce_blocks: false,
+            execution_endpoint: None,
+            execution_jwt_secret: None,
+            execution_timeout_ms: default_execution_timeout_ms(),
+            genesis: ClientGenesis::default(),
         }
     }
 }
 
+fn default_execution_timeout_ms() -> u64 {
+    DEFAULT_EXECUTION_ENGINE_TIMEOUT.as_millis() as u64
+}
+
 impl Config {
+    /// Returns a `ConfigBuilder`, starting from `Config::default()`.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    /// Returns an error if `self` is not a usable configuration.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.db_type.as_str() {
+            "disk" | "memory" => {}
+            other => return Err(format!("Unknown db_type: {}", other)),
+        }
+
+        if self.data_dir.as_os_str().is_empty() {
+            return Err("data_dir must not be empty".to_string());
+        }
+
+        if self.db_name.is_empty() {
+            return Err("db_name must not be empty".to_string());
+        }
+
+        if self.network.target_peer_count == 0 {
+            return Err("network.target_peer_count must be greater than zero".to_string());
+        }
+
+        Ok(())
+    }
+
     /// Returns the path to which the client may initialize an on-disk database.
     pub fn db_path(&self) -> Option<PathBuf> {
         self.data_dir()
@@ -51,16 +126,15 @@ impl Config {
 
     // Update the logger to output in JSON to specified file
     fn update_logger(&mut self, log: &mut slog::Logger) -> Result<(), &'static str> {
-        let file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(&self.log_file);
-
-        if file.is_err() {
-            return Err("Cannot open log file");
-        }
-        let file = file.unwrap();
+        let log_config = LogConfig {
+            log_level: self.log_level.as_str().to_string(),
+            log_format: LogFormat::Json,
+            log_file: Some(self.log_file.clone()),
+        };
+
+        let new_log = log_config
+            .init_logger()
+            .map_err(|_| "Cannot open log file")?;
 
         if let Some(file) = self.log_file.to_str() {
             info!(
@@ -74,9 +148,7 @@ impl Config {
             );
         }
 
-        let drain = Mutex::new(slog_json::Json::default(file)).fuse();
-        let drain = slog_async::Async::new(drain).build().fuse();
-        *log = slog::Logger::root(drain, o!());
+        *log = new_log;
 
         Ok(())
     }
@@ -102,6 +174,42 @@ impl Config {
         self.rpc.apply_cli_args(args)?;
         self.http.apply_cli_args(args)?;
 
+        if args.is_present("produce-blocks") {
+            self.produce_blocks = true;
+        }
+
+        if let Some(endpoint) = args.value_of("execution-endpoint") {
+            self.execution_endpoint = Some(endpoint.to_string());
+        }
+
+        if let Some(path) = args.value_of("execution-jwt") {
+            self.execution_jwt_secret = Some(PathBuf::from(path));
+        }
+
+        if let Some(timeout) = args.value_of("execution-timeout") {
+            self.execution_timeout_ms = timeout
+                .parse()
+                .map_err(|_| "execution-timeout is not a valid integer".to_string())?;
+        }
+
+        if let (Some(root), Some(slot)) = (
+            args.value_of("trusted-block-root"),
+            args.value_of("trusted-block-slot"),
+        ) {
+            self.genesis = ClientGenesis::TrustedBlockRoot {
+                root: Hash256::from_str(root.trim_start_matches("0x"))
+                    .map_err(|_| "trusted-block-root is not a valid hash".to_string())?,
+                slot: Slot::new(
+                    slot.parse()
+                        .map_err(|_| "trusted-block-slot is not a valid integer".to_string())?,
+                ),
+            };
+        }
+
+        if let Some(level) = args.value_of("log-level") {
+            self.log_level = parse_log_level(level)?;
+        }
+
         if let Some(log_file) = args.value_of("logfile") {
             self.log_file = PathBuf::from(log_file);
             self.update_logger(log)?;
@@ -109,4 +217,310 @@ impl Config {
 
         Ok(())
     }
+
+    /// Apply the `--network` argument, setting the spec constants in `eth2_config` and the boot
+    /// nodes of `self` to those of the named network.
+    ///
+    /// Returns an error if `--network` is combined with flags that it would override.
+    pub fn apply_network_cli_args(
+        &mut self,
+        args: &ArgMatches,
+        eth2_config: &mut Eth2Config,
+    ) -> Result<(), String> {
+        let network = match args.value_of("network") {
+            Some(network) => network,
+            None => return Ok(()),
+        };
+
+        if args.occurrences_of("spec-constants") > 0 {
+            return Err("--network cannot be used with --spec-constants".to_string());
+        }
+
+        *eth2_config = match network {
+            "mainnet" => Eth2Config::mainnet(),
+            "pyrmont" => Eth2Config::pyrmont(),
+            "prater" => Eth2Config::prater(),
+            "minimal" => Eth2Config::minimal(),
+            other => return Err(format!("Unknown network: {}", other)),
+        };
+
+        // Explicit boot nodes are added to (rather than replaced by) those of the network.
+        let mut boot_nodes = eth2_config
+            .spec
+            .boot_nodes
+            .iter()
+            .map(|enr| enr.parse().map_err(|_| format!("Invalid ENR: {}", enr)))
+            .collect::<Result<Vec<_>, _>>()?;
+        boot_nodes.append(&mut self.network.boot_nodes);
+        self.network.boot_nodes = boot_nodes;
+
+        Ok(())
+    }
+}
+
+fn default_log_level() -> Level {
+    Level::Info
+}
+
+/// (De)serializes a `slog::Level` as its lowercase name.
+mod serde_log_level {
+    use super::parse_log_level;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use slog::Level;
+
+    pub fn serialize<S: Serializer>(level: &Level, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&level.as_str().to_lowercase())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Level, D::Error> {
+        parse_log_level(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// Builds a `Config`, validating it on `build`.
+#[derive(Default)]
+pub struct ConfigBuilder {
+    config: Config,
+    log: Option<slog::Logger>,
+}
+
+impl ConfigBuilder {
+    pub fn data_dir(mut self, data_dir: PathBuf) -> Self {
+        self.config.data_dir = data_dir;
+        self
+    }
+
+    pub fn db_type(mut self, db_type: &str) -> Self {
+        self.config.db_type = db_type.to_string();
+        self
+    }
+
+    /// Store the chain in memory, rather than on disk.
+    pub fn memory_store(self) -> Self {
+        self.db_type("memory")
+    }
+
+    /// Store the chain on disk, in `data_dir`.
+    pub fn disk_store(self) -> Self {
+        self.db_type("disk")
+    }
+
+    pub fn network(mut self, network: NetworkConfig) -> Self {
+        self.config.network = network;
+        self
+    }
+
+    pub fn rpc(mut self, rpc: rpc::RPCConfig) -> Self {
+        self.config.rpc = rpc;
+        self
+    }
+
+    pub fn http(mut self, http: HttpServerConfig) -> Self {
+        self.config.http = http;
+        self
+    }
+
+    pub fn produce_blocks(mut self, produce_blocks: bool) -> Self {
+        self.config.produce_blocks = produce_blocks;
+        self
+    }
+
+    /// Initialize the chain from the trusted block at `root` and `slot`, read from the local
+    /// store.
+    pub fn with_trusted_block_root(mut self, root: Hash256, slot: Slot) -> Self {
+        self.config.genesis = ClientGenesis::TrustedBlockRoot { root, slot };
+        self
+    }
+
+    /// Builds the logger described by `log_config` and stores it on the builder, setting the log
+    /// level and file of the `Config` to match.
+    ///
+    /// Returns an error if the log level is unknown or the log file cannot be opened.
+    pub fn init_logger(mut self, log_config: LogConfig) -> Result<Self, String> {
+        self.log = Some(log_config.init_logger()?);
+        self.config.log_level = parse_log_level(&log_config.log_level)?;
+        self.config.log_file = log_config.log_file.unwrap_or_default();
+        Ok(self)
+    }
+
+    /// Returns the logger stored by `init_logger`, if any.
+    pub fn log(&self) -> Option<&slog::Logger> {
+        self.log.as_ref()
+    }
+
+    /// Returns the `Config`, or an error if it fails `Config::validate`.
+    pub fn build(self) -> Result<Config, String> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slog::error;
+    use std::fs;
+
+    #[test]
+    fn log_level_filters_json_output() {
+        let dir = tempfile::tempdir().expect("should create temp dir");
+        let mut config = Config {
+            log_file: dir.path().join("log.json"),
+            log_level: Level::Error,
+            ..Config::default()
+        };
+
+        let mut log = slog::Logger::root(slog::Discard, slog::o!());
+        config
+            .update_logger(&mut log)
+            .expect("should update logger");
+
+        info!(log, "hidden");
+        error!(log, "shown");
+        // Flushes the asynchronous drain.
+        drop(log);
+
+        let output = fs::read_to_string(&config.log_file).expect("should read log file");
+        assert!(output.contains("shown"));
+        assert!(!output.contains("hidden"));
+    }
+
+    #[test]
+    fn builder_init_logger() {
+        let dir = tempfile::tempdir().expect("should create temp dir");
+        let log_file = dir.path().join("log.json");
+
+        let builder = Config::builder()
+            .init_logger(LogConfig {
+                log_level: "error".to_string(),
+                log_format: LogFormat::Json,
+                log_file: Some(log_file.clone()),
+            })
+            .expect("should init logger");
+
+        let log = builder.log().expect("should store logger").clone();
+        info!(log, "hidden");
+        error!(log, "shown");
+        // Flushes the asynchronous drain.
+        drop(log);
+        drop(builder);
+
+        let output = fs::read_to_string(&log_file).expect("should read log file");
+        assert!(output.contains("shown"));
+        assert!(!output.contains("hidden"));
+
+        let config = Config::builder()
+            .init_logger(LogConfig {
+                log_level: "debug".to_string(),
+                ..LogConfig::default()
+            })
+            .expect("should init logger")
+            .build()
+            .expect("should build config");
+        assert_eq!(config.log_level, Level::Debug);
+
+        assert!(Config::builder()
+            .init_logger(LogConfig {
+                log_level: "loud".to_string(),
+                ..LogConfig::default()
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn builder_builds_valid_config() {
+        let network = NetworkConfig {
+            target_peer_count: 3,
+            ..NetworkConfig::default()
+        };
+
+        let config = Config::builder()
+            .data_dir(PathBuf::from("test-data-dir"))
+            .memory_store()
+            .network(network)
+            .produce_blocks(true)
+            .build()
+            .expect("should build config");
+
+        assert_eq!(config.data_dir, PathBuf::from("test-data-dir"));
+        assert_eq!(config.db_type, "memory");
+        assert_eq!(config.network.target_peer_count, 3);
+        assert!(config.produce_blocks);
+        assert_eq!(config.db_name, Config::default().db_name);
+    }
+
+    #[test]
+    fn builder_rejects_invalid_config() {
+        assert_eq!(
+            Config::builder().db_type("tape").build().unwrap_err(),
+            "Unknown db_type: tape".to_string()
+        );
+        assert_eq!(
+            Config::builder()
+                .data_dir(PathBuf::new())
+                .build()
+                .unwrap_err(),
+            "data_dir must not be empty".to_string()
+        );
+
+        let mut builder = Config::builder();
+        builder.config.db_name = String::new();
+        assert_eq!(
+            builder.build().unwrap_err(),
+            "db_name must not be empty".to_string()
+        );
+
+        let network = NetworkConfig {
+            target_peer_count: 0,
+            ..NetworkConfig::default()
+        };
+        assert_eq!(
+            Config::builder().network(network).build().unwrap_err(),
+            "network.target_peer_count must be greater than zero".to_string()
+        );
+    }
+
+    #[test]
+    fn apply_network_cli_args() {
+        let app = clap::App::new("test")
+            .arg(
+                clap::Arg::with_name("network")
+                    .long("network")
+                    .takes_value(true),
+            )
+            .arg(
+                clap::Arg::with_name("spec-constants")
+                    .long("spec-constants")
+                    .takes_value(true),
+            );
+
+        let mut config = Config::default();
+        let mut eth2_config = Eth2Config::minimal();
+        let matches = app
+            .clone()
+            .get_matches_from(vec!["test", "--network", "prater"]);
+        config
+            .apply_network_cli_args(&matches, &mut eth2_config)
+            .expect("should apply --network");
+        assert_eq!(eth2_config.spec_constants, "mainnet");
+        assert_eq!(
+            eth2_config.spec.genesis_time,
+            Eth2Config::prater().spec.genesis_time
+        );
+
+        let matches = app.get_matches_from(vec![
+            "test",
+            "--network",
+            "pyrmont",
+            "--spec-constants",
+            "mainnet",
+        ]);
+        assert_eq!(
+            config
+                .apply_network_cli_args(&matches, &mut eth2_config)
+                .unwrap_err(),
+            "--network cannot be used with --spec-constants".to_string()
+        );
+    }
 }
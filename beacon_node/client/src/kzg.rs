@@ -0,0 +1,26 @@
+use std::fs;
+use std::path::Path;
+
+/// The raw bytes of a KZG trusted setup (powers of tau) file.
+///
+/// The bytes are not parsed or used to verify proofs yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KzgTrustedSetup(Vec<u8>);
+
+impl KzgTrustedSetup {
+    /// Reads the trusted setup at `path`, failing if it cannot be read or is empty.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let bytes = fs::read(path)
+            .map_err(|e| format!("Unable to read KZG trusted setup {:?}: {:?}", path, e))?;
+
+        if bytes.is_empty() {
+            return Err(format!("KZG trusted setup {:?} is empty", path));
+        }
+
+        Ok(KzgTrustedSetup(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
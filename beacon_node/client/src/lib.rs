@@ -1,44 +1,67 @@
 extern crate slog;
 
 mod beacon_chain_types;
+mod block_producer;
 mod config;
 pub mod error;
+mod kzg;
+mod log_config;
 pub mod notifier;
+mod pre_start_check;
+mod subnet_subscriber;
 
+use beacon_chain::execution_engine::{parse_jwt_secret, ExecutionEngine};
 use beacon_chain::BeaconChain;
 use exit_future::Signal;
 use futures::{future::Future, Stream};
+use network::NetworkMessage;
 use network::Service as NetworkService;
 use prometheus::Registry;
 use slog::{error, info, o};
 use slot_clock::SlotClock;
+use std::fs;
 use std::marker::PhantomData;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use subnet_subscriber::SubnetSubscriber;
 use tokio::runtime::TaskExecutor;
+use tokio::sync::mpsc;
 use tokio::timer::Interval;
+use types::Keypair;
 
 pub use beacon_chain::BeaconChainTypes;
+pub use beacon_chain_types::testnet_keypairs;
 pub use beacon_chain_types::ClientType;
 pub use beacon_chain_types::InitialiseBeaconChain;
+pub use config::ClientGenesis;
 pub use config::Config as ClientConfig;
+pub use config::ConfigBuilder as ClientConfigBuilder;
 pub use eth2_config::Eth2Config;
+pub use kzg::KzgTrustedSetup;
+pub use log_config::{LogConfig, LogFormat};
 
 /// Main beacon node client service. This provides the connection and initialisation of the clients
 /// sub-services in multiple threads.
 pub struct Client<T: BeaconChainTypes> {
     /// Configuration for the lighthouse client.
-    _client_config: ClientConfig,
+    client_config: ClientConfig,
     /// The beacon chain for the running client.
     beacon_chain: Arc<BeaconChain<T>>,
     /// Reference to the network service.
     pub network: Arc<NetworkService<T>>,
+    /// Channel to send messages to the network service.
+    network_send: mpsc::UnboundedSender<NetworkMessage>,
     /// Signal to terminate the RPC server.
     pub rpc_exit_signal: Option<Signal>,
     /// Signal to terminate the HTTP server.
     pub http_exit_signal: Option<Signal>,
     /// Signal to terminate the slot timer.
     pub slot_timer_exit_signal: Option<Signal>,
+    /// Signal to terminate block production, `None` unless it has been enabled.
+    pub block_production_exit_signal: Option<Signal>,
+    /// The KZG trusted setup, `None` unless it has been loaded.
+    kzg_trusted_setup: Option<Arc<KzgTrustedSetup>>,
     /// The clients logger.
     log: slog::Logger,
     /// Marker to pin the beacon chain generics.
@@ -62,11 +85,23 @@ where
         let seconds_per_slot = eth2_config.spec.seconds_per_slot;
 
         // Load a `BeaconChain` from the store, or create a new one if it does not exist.
-        let beacon_chain = Arc::new(T::initialise_beacon_chain(
+        let mut beacon_chain = T::initialise_beacon_chain(
             store,
             eth2_config.spec.clone(),
+            &client_config.genesis,
             log.clone(),
-        ));
+        )?;
+        if let Some(endpoint) = &client_config.execution_endpoint {
+            let timeout = Duration::from_millis(client_config.execution_timeout_ms);
+            let mut execution_engine = ExecutionEngine::new(endpoint, timeout)?;
+            if let Some(path) = &client_config.execution_jwt_secret {
+                let hex_secret = fs::read_to_string(path)
+                    .map_err(|e| format!("Unable to read JWT secret {:?}: {}", path, e))?;
+                execution_engine = execution_engine.with_jwt_secret(parse_jwt_secret(&hex_secret)?);
+            }
+            beacon_chain = beacon_chain.with_execution_engine(execution_engine);
+        }
+        let beacon_chain = Arc::new(beacon_chain);
         // Registry all beacon chain metrics with the global registry.
         beacon_chain
             .metrics
@@ -102,6 +137,13 @@ where
             "wall_clock_slot" => beacon_chain.read_slot_clock().unwrap(),
         );
 
+        // A crash may have left the deposits out of step with the chain. Stale pool deposits are
+        // pruned; anything else prevents startup.
+        if let Err(e) = beacon_chain.verify_deposit_tree_consistency() {
+            error!(log, "Inconsistent deposit tree"; "error" => &e);
+            return Err(format!("Inconsistent deposit tree: {}", e).into());
+        }
+
         // Start the network service, libp2p and syncing threads
         // TODO: Add beacon_chain reference to network parameters
         let network_config = &client_config.network;
@@ -112,6 +154,11 @@ where
             executor,
             network_logger,
         )?;
+        if let Some(metrics) = network.metrics() {
+            metrics
+                .register(&metrics_registry)
+                .map_err(|e| format!("Failed to register network metrics: {:?}", e))?;
+        }
 
         // spawn the RPC server
         let rpc_exit_signal = if client_config.rpc.enabled {
@@ -133,8 +180,9 @@ where
             Some(http_server::start_service(
                 &client_config.http,
                 executor,
-                network_send,
+                network_send.clone(),
                 beacon_chain.clone(),
+                network.clone(),
                 client_config.db_path().expect("unable to read datadir"),
                 metrics_registry,
                 &log,
@@ -170,18 +218,106 @@ where
         }
 
         Ok(Client {
-            _client_config: client_config,
+            client_config,
             beacon_chain,
             http_exit_signal,
             rpc_exit_signal,
             slot_timer_exit_signal: Some(slot_timer_exit_signal),
+            block_production_exit_signal: None,
+            kzg_trusted_setup: None,
             log,
             network,
+            network_send,
             phantom: PhantomData,
         })
     }
 }
 
+impl<T: BeaconChainTypes + 'static> Client<T> {
+    /// Starts a timer, aligned to slot boundaries, that produces and publishes a block at each
+    /// slot for which one of `keypairs` is the scheduled proposer.
+    ///
+    /// The timer also subscribes to the attestation subnets that `keypairs` are assigned to: once
+    /// per epoch for the following epoch, and every slot for duties
+    /// `attestation_subnet_subscription_slots_before_attestation` slots ahead. Each subnet is left
+    /// once its last known duty slot has passed.
+    ///
+    /// Intended for single-process testing and small testnets, where there is no separate
+    /// validator client.
+    pub fn enable_block_production(
+        mut self,
+        keypairs: Vec<Keypair>,
+        executor: &TaskExecutor,
+    ) -> Result<Self, String> {
+        let duration_to_next_slot = self
+            .beacon_chain
+            .slot_clock
+            .duration_to_next_slot()
+            .map_err(|e| format!("Unable to read slot clock: {:?}", e))?
+            .ok_or_else(|| "Unable to produce blocks before genesis".to_string())?;
+        let slot_duration = Duration::from_secs(self.beacon_chain.spec.seconds_per_slot);
+        let interval = Interval::new(Instant::now() + duration_to_next_slot, slot_duration);
+
+        let (exit_signal, exit) = exit_future::signal();
+        let chain = self.beacon_chain.clone();
+        let mut network_send = self.network_send.clone();
+        let log = self.log.new(o!("Service" => "BlockProducer"));
+        let mut subnet_subscriber = SubnetSubscriber::new(
+            self.client_config
+                .network
+                .attestation_subnet_subscription_slots_before_attestation,
+        );
+
+        info!(log, "Block production enabled"; "keypairs" => keypairs.len());
+
+        executor.spawn(
+            exit.until(
+                interval
+                    .for_each(move |_| {
+                        subnet_subscriber.on_slot(&chain, &keypairs, &mut network_send, &log);
+                        block_producer::produce_block_if_proposer(
+                            &chain,
+                            &keypairs,
+                            &mut network_send,
+                            &log,
+                        );
+
+                        Ok(())
+                    })
+                    .map_err(|_| ()),
+            )
+            .map(|_| ()),
+        );
+
+        self.block_production_exit_signal = Some(exit_signal);
+
+        Ok(self)
+    }
+
+    /// Reads the KZG trusted setup file at `path` and stores it on the client.
+    ///
+    /// Nothing verifies KZG proofs yet, this only provides the loading seam.
+    pub fn load_trusted_setup_for_kzg(mut self, path: &Path) -> Result<Self, String> {
+        let trusted_setup = KzgTrustedSetup::from_file(path)?;
+
+        info!(
+            self.log,
+            "Loaded KZG trusted setup";
+            "path" => format!("{:?}", path),
+            "bytes" => trusted_setup.as_bytes().len(),
+        );
+
+        self.kzg_trusted_setup = Some(Arc::new(trusted_setup));
+
+        Ok(self)
+    }
+
+    /// Returns the KZG trusted setup, if it has been loaded.
+    pub fn kzg_trusted_setup(&self) -> Option<Arc<KzgTrustedSetup>> {
+        self.kzg_trusted_setup.clone()
+    }
+}
+
 impl<T: BeaconChainTypes> Drop for Client<T> {
     fn drop(&mut self) {
         // Save the beacon chain to it's store before dropping.
@@ -0,0 +1,104 @@
+use serde_derive::{Deserialize, Serialize};
+use slog::{o, Drain, Level};
+use std::fs::{File, OpenOptions};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// The output format of the client logger.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LogFormat {
+    /// Human-readable, compact terminal output.
+    Terminal,
+    /// One JSON object per log record.
+    Json,
+}
+
+/// Configuration for the `slog::Logger` used throughout the client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogConfig {
+    /// A level accepted by `parse_log_level`.
+    pub log_level: String,
+    pub log_format: LogFormat,
+    /// If `Some`, logs are written to this file instead of stdout.
+    pub log_file: Option<PathBuf>,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            log_level: "info".to_string(),
+            log_format: LogFormat::Terminal,
+            log_file: None,
+        }
+    }
+}
+
+impl LogConfig {
+    /// Builds the root `slog::Logger` described by `self`.
+    ///
+    /// Returns an error if the log level is unknown or the log file cannot be opened.
+    pub fn init_logger(&self) -> Result<slog::Logger, String> {
+        let level = parse_log_level(&self.log_level)?;
+
+        let log = match (self.log_format, self.open_log_file()?) {
+            (LogFormat::Terminal, None) => {
+                let decorator = slog_term::TermDecorator::new().build();
+                let drain = slog_term::CompactFormat::new(decorator).build().fuse();
+                root_logger(drain, level)
+            }
+            (LogFormat::Terminal, Some(file)) => {
+                let decorator = slog_term::PlainDecorator::new(file);
+                let drain = slog_term::CompactFormat::new(decorator).build().fuse();
+                root_logger(drain, level)
+            }
+            (LogFormat::Json, None) => {
+                let drain = Mutex::new(slog_json::Json::default(std::io::stdout())).fuse();
+                root_logger(drain, level)
+            }
+            (LogFormat::Json, Some(file)) => {
+                let drain = Mutex::new(slog_json::Json::default(file)).fuse();
+                root_logger(drain, level)
+            }
+        };
+
+        Ok(log)
+    }
+
+    fn open_log_file(&self) -> Result<Option<File>, String> {
+        match &self.log_file {
+            Some(path) => OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(path)
+                .map(Some)
+                .map_err(|e| format!("Cannot open log file {:?}: {:?}", path, e)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Parses one of `crit`, `error`, `warn`, `info`, `debug` or `trace`, or the full names
+/// `critical` and `warning`, ignoring case.
+pub fn parse_log_level(level: &str) -> Result<Level, String> {
+    match level.to_lowercase().as_str() {
+        "crit" | "critical" => Ok(Level::Critical),
+        "error" => Ok(Level::Error),
+        "warn" | "warning" => Ok(Level::Warning),
+        "info" => Ok(Level::Info),
+        "debug" => Ok(Level::Debug),
+        "trace" => Ok(Level::Trace),
+        _ => Err(format!("Invalid log level: {}", level)),
+    }
+}
+
+fn root_logger<D>(drain: D, level: Level) -> slog::Logger
+where
+    D: Drain<Ok = (), Err = slog::Never> + Send + 'static,
+{
+    let drain = slog_async::Async::new(drain)
+        .build()
+        .filter_level(level)
+        .fuse();
+    slog::Logger::root(drain, o!())
+}
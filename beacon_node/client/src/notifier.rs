@@ -2,7 +2,7 @@ use crate::Client;
 use beacon_chain::BeaconChainTypes;
 use exit_future::Exit;
 use futures::{Future, Stream};
-use slog::{debug, o};
+use slog::{debug, info, o};
 use std::time::{Duration, Instant};
 use tokio::runtime::TaskExecutor;
 use tokio::timer::Interval;
@@ -10,10 +10,7 @@ use tokio::timer::Interval;
 /// The interval between heartbeat events.
 pub const HEARTBEAT_INTERVAL_SECONDS: u64 = 5;
 
-/// Spawns a thread that can be used to run code periodically, on `HEARTBEAT_INTERVAL_SECONDS`
-/// durations.
-///
-/// Presently unused, but remains for future use.
+/// Spawns a thread that logs the sync status of the client every `HEARTBEAT_INTERVAL_SECONDS`.
 pub fn run<T: BeaconChainTypes + Send + Sync + 'static>(
     client: &Client<T>,
     executor: TaskExecutor,
@@ -25,12 +22,23 @@ pub fn run<T: BeaconChainTypes + Send + Sync + 'static>(
         Duration::from_secs(HEARTBEAT_INTERVAL_SECONDS),
     );
 
-    let _log = client.log.new(o!("Service" => "Notifier"));
+    let log = client.log.new(o!("Service" => "Notifier"));
+    let network = client.network.clone();
+
+    let heartbeat = move |_| {
+        let status = network.get_sync_status();
+
+        info!(
+            log,
+            "Sync status";
+            "sync_state" => format!("{:?}", status.sync_state),
+            "head_slot" => status.head_slot,
+            "head_root" => format!("{}", status.head_root),
+            "finalized_slot" => status.finalized_slot,
+            "estimated_highest_slot" => status.estimated_highest_slot,
+            "peers_with_higher_slot" => status.peers_with_higher_slot,
+        );
 
-    let heartbeat = |_| {
-        // There is not presently any heartbeat logic.
-        //
-        // We leave this function empty for future use.
         Ok(())
     };
 
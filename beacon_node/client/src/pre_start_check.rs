@@ -0,0 +1,277 @@
+use crate::config::{ClientGenesis, Config};
+use eth2_config::Eth2Config;
+use slog::{info, warn};
+#[cfg(unix)]
+use std::ffi::CString;
+use std::fs;
+#[cfg(unix)]
+use std::mem;
+#[cfg(unix)]
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::time::SystemTime;
+
+const GB: u64 = 1024 * 1024 * 1024;
+
+/// Below this, the client refuses to start.
+const MIN_FREE_DISK_BYTES: u64 = 10 * GB;
+/// Below this, the client starts but warns the operator.
+const WARN_FREE_DISK_BYTES: u64 = 20 * GB;
+/// Below this, the client starts but warns the operator.
+const WARN_FILE_DESCRIPTOR_LIMIT: u64 = 8_192;
+/// Below this, the client refuses to start.
+const MIN_AVAILABLE_MEMORY_BYTES: u64 = 4 * GB;
+
+/// The outcome of comparing a host resource against its thresholds.
+#[derive(Debug, PartialEq)]
+enum Check {
+    Sufficient,
+    /// The client can run, but the operator should be warned.
+    Low,
+    /// The client must not start.
+    Insufficient,
+}
+
+impl Config {
+    /// Checks that the genesis time of `eth2_config` is in the past, unless starting from the
+    /// interop testnet genesis, and that the host has enough free disk, file descriptors and
+    /// memory to run the client.
+    ///
+    /// Returns an error for critical shortfalls and logs a warning for marginal ones. Resources
+    /// that cannot be read from the OS are logged and skipped.
+    pub fn run_pre_start_check(
+        self,
+        eth2_config: &Eth2Config,
+        log: &slog::Logger,
+    ) -> Result<Self, String> {
+        // The interop testnet genesis may be in the future, the client waits for it.
+        if self.genesis != ClientGenesis::Testnet {
+            let now = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_err(|e| format!("Unable to read system time: {:?}", e))?
+                .as_secs();
+
+            if !is_genesis_time_past(eth2_config.spec.genesis_time, now) {
+                return Err(format!(
+                    "Genesis time {} is in the future, the present time is {}",
+                    eth2_config.spec.genesis_time, now
+                ));
+            }
+        }
+
+        let data_dir = self
+            .data_dir()
+            .ok_or_else(|| "Unable to access data directory".to_string())?;
+
+        match free_disk_bytes(&data_dir) {
+            Some(free) => match check_free_disk(free) {
+                Check::Insufficient => {
+                    return Err(format!(
+                        "Insufficient disk space in {:?}: {} GB free, {} GB required",
+                        data_dir,
+                        free / GB,
+                        MIN_FREE_DISK_BYTES / GB
+                    ));
+                }
+                Check::Low => warn!(
+                    log,
+                    "Low disk space";
+                    "data_dir" => format!("{:?}", data_dir),
+                    "free_gb" => free / GB,
+                    "recommended_gb" => WARN_FREE_DISK_BYTES / GB,
+                ),
+                Check::Sufficient => {}
+            },
+            None => info!(log, "Unable to read free disk space, skipping check"),
+        }
+
+        match file_descriptor_limit() {
+            Some(limit) => match check_file_descriptor_limit(limit) {
+                Check::Sufficient => {}
+                _ => warn!(
+                    log,
+                    "Low file descriptor limit";
+                    "limit" => limit,
+                    "recommended" => WARN_FILE_DESCRIPTOR_LIMIT,
+                ),
+            },
+            None => info!(log, "Unable to read file descriptor limit, skipping check"),
+        }
+
+        match available_memory_bytes() {
+            Some(available) => match check_available_memory(available) {
+                Check::Sufficient => {}
+                _ => {
+                    return Err(format!(
+                        "Insufficient available memory: {} GB, {} GB required",
+                        available / GB,
+                        MIN_AVAILABLE_MEMORY_BYTES / GB
+                    ));
+                }
+            },
+            None => info!(log, "Unable to read available memory, skipping check"),
+        }
+
+        Ok(self)
+    }
+}
+
+fn is_genesis_time_past(genesis_time: u64, now: u64) -> bool {
+    genesis_time <= now
+}
+
+fn check_free_disk(free: u64) -> Check {
+    if free < MIN_FREE_DISK_BYTES {
+        Check::Insufficient
+    } else if free < WARN_FREE_DISK_BYTES {
+        Check::Low
+    } else {
+        Check::Sufficient
+    }
+}
+
+fn check_file_descriptor_limit(limit: u64) -> Check {
+    if limit < WARN_FILE_DESCRIPTOR_LIMIT {
+        Check::Low
+    } else {
+        Check::Sufficient
+    }
+}
+
+fn check_available_memory(available: u64) -> Check {
+    if available < MIN_AVAILABLE_MEMORY_BYTES {
+        Check::Insufficient
+    } else {
+        Check::Sufficient
+    }
+}
+
+#[cfg(unix)]
+fn free_disk_bytes(path: &Path) -> Option<u64> {
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { mem::zeroed() };
+
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } == 0 {
+        Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn free_disk_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(unix)]
+fn file_descriptor_limit() -> Option<u64> {
+    let mut limit: libc::rlimit = unsafe { mem::zeroed() };
+
+    if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) } == 0 {
+        Some(limit.rlim_cur as u64)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn file_descriptor_limit() -> Option<u64> {
+    None
+}
+
+/// Returns the memory available for new processes without swapping, as reported by the kernel.
+///
+/// Unlike the free page count, this includes reclaimable page cache.
+fn available_memory_bytes() -> Option<u64> {
+    parse_mem_available(&fs::read_to_string("/proc/meminfo").ok()?)
+}
+
+/// Parses the `MemAvailable` line of `/proc/meminfo`, e.g. `MemAvailable:   8048576 kB`.
+fn parse_mem_available(meminfo: &str) -> Option<u64> {
+    let line = meminfo
+        .lines()
+        .find(|line| line.starts_with("MemAvailable:"))?;
+    let mut fields = line["MemAvailable:".len()..].split_whitespace();
+
+    let value = fields.next()?.parse::<u64>().ok()?;
+    match fields.next() {
+        Some("kB") => Some(value * 1024),
+        None => Some(value),
+        Some(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Hash256, Slot};
+
+    #[test]
+    fn genesis_time_must_be_past() {
+        assert!(is_genesis_time_past(0, 10));
+        assert!(is_genesis_time_past(10, 10));
+        assert!(!is_genesis_time_past(11, 10));
+
+        let log = slog::Logger::root(slog::Discard, slog::o!());
+        let mut eth2_config = Eth2Config::minimal();
+        eth2_config.spec.genesis_time = u64::max_value();
+
+        let config = Config::builder()
+            .with_trusted_block_root(Hash256::zero(), Slot::new(0))
+            .build()
+            .expect("should build config");
+        assert!(config
+            .run_pre_start_check(&eth2_config, &log)
+            .unwrap_err()
+            .starts_with("Genesis time"));
+    }
+
+    #[test]
+    fn disk_thresholds() {
+        assert_eq!(check_free_disk(0), Check::Insufficient);
+        assert_eq!(
+            check_free_disk(MIN_FREE_DISK_BYTES - 1),
+            Check::Insufficient
+        );
+        assert_eq!(check_free_disk(MIN_FREE_DISK_BYTES), Check::Low);
+        assert_eq!(check_free_disk(WARN_FREE_DISK_BYTES - 1), Check::Low);
+        assert_eq!(check_free_disk(WARN_FREE_DISK_BYTES), Check::Sufficient);
+    }
+
+    #[test]
+    fn low_file_descriptor_limit_only_warns() {
+        assert_eq!(check_file_descriptor_limit(0), Check::Low);
+        assert_eq!(
+            check_file_descriptor_limit(WARN_FILE_DESCRIPTOR_LIMIT - 1),
+            Check::Low
+        );
+        assert_eq!(
+            check_file_descriptor_limit(WARN_FILE_DESCRIPTOR_LIMIT),
+            Check::Sufficient
+        );
+    }
+
+    #[test]
+    fn memory_thresholds() {
+        assert_eq!(
+            check_available_memory(MIN_AVAILABLE_MEMORY_BYTES - 1),
+            Check::Insufficient
+        );
+        assert_eq!(
+            check_available_memory(MIN_AVAILABLE_MEMORY_BYTES),
+            Check::Sufficient
+        );
+    }
+
+    #[test]
+    fn parses_mem_available() {
+        let meminfo = "MemTotal:       16318576 kB\n\
+                       MemFree:          412344 kB\n\
+                       MemAvailable:    8048576 kB\n\
+                       Buffers:          263716 kB\n";
+
+        assert_eq!(parse_mem_available(meminfo), Some(8_048_576 * 1024));
+        assert_eq!(parse_mem_available("MemFree: 412344 kB\n"), None);
+        assert_eq!(parse_mem_available("MemAvailable: lots kB\n"), None);
+    }
+}
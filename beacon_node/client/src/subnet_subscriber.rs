@@ -0,0 +1,352 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use network::NetworkMessage;
+use slog::{debug, error};
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+use types::{AttestationSubnetFlags, CommitteeCache, Epoch, EthSpec, Keypair, Slot};
+
+/// Maintains the attestation subnet subscriptions of the local validators.
+///
+/// Subnets are joined ahead of each attestation duty and left once the duty slot has passed.
+pub struct SubnetSubscriber {
+    slots_before_attestation: u64,
+    /// The epoch whose subnets have been subscribed to by `subscribe_to_next_epoch_subnets`.
+    subscribed_epoch: Option<Epoch>,
+    committee_caches: CommitteeCaches,
+    subscriptions: Subscriptions,
+}
+
+impl SubnetSubscriber {
+    /// Subscribes to the subnet of each upcoming duty `slots_before_attestation` slots ahead.
+    pub fn new(slots_before_attestation: u64) -> Self {
+        Self {
+            slots_before_attestation,
+            subscribed_epoch: None,
+            committee_caches: CommitteeCaches::default(),
+            subscriptions: Subscriptions::default(),
+        }
+    }
+
+    /// Updates the subnet subscriptions of `keypairs`, to be called at the start of every slot.
+    pub fn on_slot<T: BeaconChainTypes>(
+        &mut self,
+        chain: &BeaconChain<T>,
+        keypairs: &[Keypair],
+        network_send: &mut mpsc::UnboundedSender<NetworkMessage>,
+        log: &slog::Logger,
+    ) {
+        let present_slot = match chain.read_slot_clock() {
+            Some(slot) => slot,
+            None => return,
+        };
+
+        for subnet_id in self.subscriptions.expire(present_slot) {
+            if let Err(e) =
+                network_send.try_send(NetworkMessage::UnsubscribeFromSubnet { subnet_id })
+            {
+                error!(
+                    log,
+                    "Unable to unsubscribe from attestation subnet";
+                    "subnet" => subnet_id,
+                    "error" => format!("{:?}", e),
+                );
+            }
+        }
+        self.committee_caches
+            .prune(present_slot.epoch(T::EthSpec::slots_per_epoch()));
+
+        let validator_indices = validator_indices(chain, keypairs);
+        self.subscribe_to_next_epoch_subnets(chain, &validator_indices, network_send, log);
+        self.subscribe_to_upcoming_subnets(
+            chain,
+            &validator_indices,
+            present_slot,
+            network_send,
+            log,
+        );
+    }
+
+    /// Subscribes to the attestation subnets that any of `validator_indices` are assigned to in
+    /// the epoch after the present head, unless this has already been done for that epoch.
+    fn subscribe_to_next_epoch_subnets<T: BeaconChainTypes>(
+        &mut self,
+        chain: &BeaconChain<T>,
+        validator_indices: &[usize],
+        network_send: &mut mpsc::UnboundedSender<NetworkMessage>,
+        log: &slog::Logger,
+    ) {
+        let next_epoch = chain.head().beacon_state.next_epoch();
+
+        if self.subscribed_epoch == Some(next_epoch) {
+            return;
+        }
+
+        let duties = match self.duties(chain, next_epoch, validator_indices, log) {
+            Some(duties) => duties,
+            None => return,
+        };
+
+        let mut flags = AttestationSubnetFlags::default();
+        for (_, shard) in &duties {
+            flags.insert_shard(*shard);
+        }
+        debug!(
+            log,
+            "Subscribing to attestation subnets";
+            "epoch" => next_epoch,
+            "subnets" => format!("{:?}", flags.subnets()),
+        );
+
+        for (slot, shard) in duties {
+            let subnet_id = AttestationSubnetFlags::subnet_for_shard(shard);
+            if !self.subscribe(subnet_id, slot, network_send, log) {
+                return;
+            }
+        }
+
+        self.subscribed_epoch = Some(next_epoch);
+    }
+
+    /// Subscribes to the attestation subnet of each of `validator_indices` that has an attestation
+    /// duty `slots_before_attestation` slots after `present_slot`.
+    ///
+    /// This ensures subnets are joined in time even for duties that
+    /// `subscribe_to_next_epoch_subnets` did not see, such as those in the epoch the node started.
+    fn subscribe_to_upcoming_subnets<T: BeaconChainTypes>(
+        &mut self,
+        chain: &BeaconChain<T>,
+        validator_indices: &[usize],
+        present_slot: Slot,
+        network_send: &mut mpsc::UnboundedSender<NetworkMessage>,
+        log: &slog::Logger,
+    ) {
+        let attestation_slot = present_slot + self.slots_before_attestation;
+        let epoch = attestation_slot.epoch(T::EthSpec::slots_per_epoch());
+
+        let duties = match self.duties(chain, epoch, validator_indices, log) {
+            Some(duties) => duties,
+            None => return,
+        };
+
+        for (_, shard) in duties
+            .into_iter()
+            .filter(|(slot, _)| *slot == attestation_slot)
+        {
+            let subnet_id = AttestationSubnetFlags::subnet_for_shard(shard);
+            self.subscribe(subnet_id, attestation_slot, network_send, log);
+        }
+    }
+
+    /// Returns the `(slot, shard)` of the attestation duty of each of `validator_indices` in
+    /// `epoch`, building the committee cache of `epoch` if it has not been built yet.
+    fn duties<T: BeaconChainTypes>(
+        &mut self,
+        chain: &BeaconChain<T>,
+        epoch: Epoch,
+        validator_indices: &[usize],
+        log: &slog::Logger,
+    ) -> Option<Vec<(Slot, u64)>> {
+        let committee_cache = self.committee_caches.get_or_build(epoch, || {
+            CommitteeCache::initialized(&chain.head().beacon_state, epoch, &chain.spec)
+        });
+
+        match committee_cache {
+            Ok(committee_cache) => Some(
+                validator_indices
+                    .iter()
+                    .filter_map(|&validator_index| {
+                        committee_cache.get_attestation_duties(validator_index)
+                    })
+                    .map(|duty| (duty.slot, duty.shard))
+                    .collect(),
+            ),
+            Err(e) => {
+                // The epoch may be beyond the next epoch of a head that lags the wall clock.
+                debug!(
+                    log,
+                    "Unable to read attestation duties";
+                    "epoch" => epoch,
+                    "error" => format!("{:?}", e),
+                );
+                None
+            }
+        }
+    }
+
+    /// Asks the network service to subscribe to `subnet_id` ahead of a duty at `slot`, returning
+    /// `false` if the message could not be sent.
+    ///
+    /// Subnets that are already subscribed to are kept until after `slot`, without another
+    /// message.
+    fn subscribe(
+        &mut self,
+        subnet_id: u64,
+        slot: Slot,
+        network_send: &mut mpsc::UnboundedSender<NetworkMessage>,
+        log: &slog::Logger,
+    ) -> bool {
+        if self.subscriptions.extend(subnet_id, slot) {
+            return true;
+        }
+
+        match network_send.try_send(NetworkMessage::SubscribeToSubnet { subnet_id, slot }) {
+            Ok(()) => {
+                self.subscriptions.insert(subnet_id, slot);
+                true
+            }
+            Err(e) => {
+                error!(
+                    log,
+                    "Unable to subscribe to attestation subnet";
+                    "subnet" => subnet_id,
+                    "error" => format!("{:?}", e),
+                );
+                false
+            }
+        }
+    }
+}
+
+/// Returns the indices of `keypairs` in the head state, skipping unknown validators.
+fn validator_indices<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    keypairs: &[Keypair],
+) -> Vec<usize> {
+    let state = &chain.head().beacon_state;
+
+    keypairs
+        .iter()
+        .filter_map(|keypair| state.get_validator_index(&keypair.pk).ok()?)
+        .collect()
+}
+
+/// The committee caches of the epochs that duties have been read for, so each is built once.
+#[derive(Default)]
+struct CommitteeCaches {
+    caches: HashMap<Epoch, CommitteeCache>,
+}
+
+impl CommitteeCaches {
+    /// Returns the cache for `epoch`, calling `build` to create it if it is not present.
+    fn get_or_build<F, E>(&mut self, epoch: Epoch, build: F) -> Result<&CommitteeCache, E>
+    where
+        F: FnOnce() -> Result<CommitteeCache, E>,
+    {
+        if !self.caches.contains_key(&epoch) {
+            let committee_cache = build()?;
+            self.caches.insert(epoch, committee_cache);
+        }
+
+        Ok(&self.caches[&epoch])
+    }
+
+    /// Drops the caches of epochs prior to `current_epoch`.
+    fn prune(&mut self, current_epoch: Epoch) {
+        self.caches.retain(|epoch, _| *epoch >= current_epoch);
+    }
+}
+
+/// The subscribed attestation subnets, each with the slot of its last known duty.
+#[derive(Default)]
+struct Subscriptions {
+    last_duty_slots: HashMap<u64, Slot>,
+}
+
+impl Subscriptions {
+    /// Records a subscription to `subnet_id` for a duty at `slot`.
+    fn insert(&mut self, subnet_id: u64, slot: Slot) {
+        self.last_duty_slots.insert(subnet_id, slot);
+    }
+
+    /// If `subnet_id` is already subscribed to, keeps it until after `slot` and returns `true`.
+    fn extend(&mut self, subnet_id: u64, slot: Slot) -> bool {
+        match self.last_duty_slots.get_mut(&subnet_id) {
+            Some(last_duty_slot) => {
+                *last_duty_slot = std::cmp::max(*last_duty_slot, slot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes and returns the subnets whose last duty was prior to `present_slot`.
+    fn expire(&mut self, present_slot: Slot) -> Vec<u64> {
+        let expired: Vec<u64> = self
+            .last_duty_slots
+            .iter()
+            .filter(|(_, last_duty_slot)| **last_duty_slot < present_slot)
+            .map(|(subnet_id, _)| *subnet_id)
+            .collect();
+
+        for subnet_id in &expired {
+            self.last_duty_slots.remove(subnet_id);
+        }
+
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_committee_cache_once_per_epoch() {
+        let mut caches = CommitteeCaches::default();
+        let mut builds = 0;
+
+        for _ in 0..3 {
+            caches
+                .get_or_build(Epoch::new(1), || -> Result<_, ()> {
+                    builds += 1;
+                    Ok(CommitteeCache::default())
+                })
+                .unwrap();
+        }
+        assert_eq!(builds, 1);
+
+        // A failed build is retried.
+        assert_eq!(caches.get_or_build(Epoch::new(2), || Err(())), Err(()));
+        caches
+            .get_or_build(Epoch::new(2), || -> Result<_, ()> {
+                builds += 1;
+                Ok(CommitteeCache::default())
+            })
+            .unwrap();
+        assert_eq!(builds, 2);
+
+        caches.prune(Epoch::new(2));
+        caches
+            .get_or_build(Epoch::new(2), || -> Result<_, ()> {
+                panic!("should be cached")
+            })
+            .unwrap();
+        caches
+            .get_or_build(Epoch::new(1), || -> Result<_, ()> {
+                builds += 1;
+                Ok(CommitteeCache::default())
+            })
+            .unwrap();
+        assert_eq!(builds, 3);
+    }
+
+    #[test]
+    fn expires_subscriptions_after_duty_slot() {
+        let mut subscriptions = Subscriptions::default();
+
+        assert!(!subscriptions.extend(1, Slot::new(3)));
+        subscriptions.insert(1, Slot::new(3));
+        subscriptions.insert(2, Slot::new(5));
+
+        // A later duty on the same subnet keeps the subscription.
+        assert!(subscriptions.extend(2, Slot::new(7)));
+        // An earlier duty does not shorten it.
+        assert!(subscriptions.extend(2, Slot::new(4)));
+
+        assert!(subscriptions.expire(Slot::new(3)).is_empty());
+        assert_eq!(subscriptions.expire(Slot::new(4)), vec![1]);
+        assert!(subscriptions.expire(Slot::new(7)).is_empty());
+        assert_eq!(subscriptions.expire(Slot::new(8)), vec![2]);
+        assert!(!subscriptions.extend(2, Slot::new(9)));
+    }
+}
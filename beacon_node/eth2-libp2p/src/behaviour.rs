@@ -10,6 +10,7 @@ use libp2p::{
     },
     discv5::Discv5Event,
     gossipsub::{Gossipsub, GossipsubEvent},
+    identify::{Identify, IdentifyEvent},
     ping::{Ping, PingConfig, PingEvent},
     tokio_io::{AsyncRead, AsyncWrite},
     NetworkBehaviour, PeerId,
@@ -18,7 +19,7 @@ use slog::{o, trace, warn};
 use ssz::{ssz_encode, Decode, DecodeError, Encode};
 use std::num::NonZeroU32;
 use std::time::Duration;
-use types::{Attestation, BeaconBlock};
+use types::{Attestation, BeaconBlock, VoluntaryExit};
 
 /// Builds the network behaviour that manages the core protocols of eth2.
 /// This core behaviour is managed by `Behaviour` which adds peer management to all core
@@ -34,6 +35,8 @@ pub struct Behaviour<TSubstream: AsyncRead + AsyncWrite> {
     ping: Ping<TSubstream>,
     /// Kademlia for peer discovery.
     discovery: Discovery<TSubstream>,
+    /// Exchanges the agent and protocol versions of peers.
+    identify: Identify<TSubstream>,
     #[behaviour(ignore)]
     /// The events generated by this behaviour to be consumed in the swarm poll.
     events: Vec<BehaviourEvent>,
@@ -58,9 +61,14 @@ impl<TSubstream: AsyncRead + AsyncWrite> Behaviour<TSubstream> {
 
         Ok(Behaviour {
             serenity_rpc: Rpc::new(log),
-            gossipsub: Gossipsub::new(local_peer_id.clone(), net_conf.gs_config.clone()),
+            gossipsub: Gossipsub::new(local_peer_id.clone(), net_conf.gossipsub_config()),
             discovery: Discovery::new(local_key, net_conf, log)?,
             ping: Ping::new(ping_config),
+            identify: Identify::new(
+                "lighthouse/libp2p".into(),
+                net_conf.identify_agent_string.clone(),
+                local_key.public(),
+            ),
             events: Vec::new(),
             log: behaviour_log,
         })
@@ -109,6 +117,9 @@ impl<TSubstream: AsyncRead + AsyncWrite> NetworkBehaviourEventProcess<RPCMessage
             RPCMessage::PeerDialed(peer_id) => {
                 self.events.push(BehaviourEvent::PeerDialed(peer_id))
             }
+            RPCMessage::PeerDisconnected(peer_id) => {
+                self.events.push(BehaviourEvent::PeerDisconnected(peer_id))
+            }
             RPCMessage::RPC(peer_id, rpc_event) => {
                 self.events.push(BehaviourEvent::RPC(peer_id, rpc_event))
             }
@@ -124,6 +135,29 @@ impl<TSubstream: AsyncRead + AsyncWrite> NetworkBehaviourEventProcess<PingEvent>
     }
 }
 
+impl<TSubstream: AsyncRead + AsyncWrite> NetworkBehaviourEventProcess<IdentifyEvent>
+    for Behaviour<TSubstream>
+{
+    fn inject_event(&mut self, event: IdentifyEvent) {
+        match event {
+            IdentifyEvent::Identified { peer_id, info, .. } => {
+                trace!(
+                    self.log,
+                    "Identified peer";
+                    "peer" => format!("{:?}", peer_id),
+                    "agent_version" => &info.agent_version,
+                );
+                self.events.push(BehaviourEvent::PeerIdentified {
+                    peer_id,
+                    agent_version: info.agent_version,
+                });
+            }
+            IdentifyEvent::Error { .. } => {}
+            IdentifyEvent::SendBack { .. } => {}
+        }
+    }
+}
+
 impl<TSubstream: AsyncRead + AsyncWrite> Behaviour<TSubstream> {
     /// Consumes the events list when polled.
     fn poll<TBehaviourIn>(
@@ -154,6 +188,11 @@ impl<TSubstream: AsyncRead + AsyncWrite> Behaviour<TSubstream> {
         self.gossipsub.subscribe(topic)
     }
 
+    /// Unsubscribes from a gossipsub topic.
+    pub fn unsubscribe(&mut self, topic: Topic) -> bool {
+        self.gossipsub.unsubscribe(topic)
+    }
+
     /// Publishes a message on the pubsub (gossipsub) behaviour.
     pub fn publish(&mut self, topics: Vec<Topic>, message: PubsubMessage) {
         let message_bytes = ssz_encode(&message);
@@ -174,6 +213,11 @@ impl<TSubstream: AsyncRead + AsyncWrite> Behaviour<TSubstream> {
 pub enum BehaviourEvent {
     RPC(PeerId, RPCEvent),
     PeerDialed(PeerId),
+    PeerDisconnected(PeerId),
+    PeerIdentified {
+        peer_id: PeerId,
+        agent_version: String,
+    },
     GossipMessage {
         source: PeerId,
         topics: Vec<TopicHash>,
@@ -188,6 +232,8 @@ pub enum PubsubMessage {
     Block(BeaconBlock),
     /// Gossipsub message providing notification of a new attestation.
     Attestation(Attestation),
+    /// Gossipsub message providing notification of a new voluntary exit.
+    VoluntaryExit(VoluntaryExit),
 }
 
 //TODO: Correctly encode/decode enums. Prefixing with integer for now.
@@ -214,6 +260,12 @@ impl Encode for PubsubMessage {
                 // Encode the gossip as a Vec<u8>;
                 encoder.append(&attestation_gossip.as_ssz_bytes());
             }
+            PubsubMessage::VoluntaryExit(exit_gossip) => {
+                encoder.append(&2_u32);
+
+                // Encode the gossip as a Vec<u8>;
+                encoder.append(&exit_gossip.as_ssz_bytes());
+            }
         }
 
         encoder.finalize();
@@ -241,6 +293,9 @@ impl Decode for PubsubMessage {
             1 => Ok(PubsubMessage::Attestation(Attestation::from_ssz_bytes(
                 &body,
             )?)),
+            2 => Ok(PubsubMessage::VoluntaryExit(VoluntaryExit::from_ssz_bytes(
+                &body,
+            )?)),
             _ => Err(DecodeError::BytesInvalid(
                 "Invalid PubsubMessage id".to_string(),
             )),
@@ -263,4 +318,19 @@ mod test {
 
         assert_eq!(original, decoded);
     }
+
+    #[test]
+    fn ssz_encoding_voluntary_exit() {
+        let original = PubsubMessage::VoluntaryExit(VoluntaryExit {
+            epoch: Epoch::new(1),
+            validator_index: 2,
+            signature: Signature::empty_signature(),
+        });
+
+        let encoded = ssz_encode(&original);
+
+        let decoded = PubsubMessage::from_ssz_bytes(&encoded).unwrap();
+
+        assert_eq!(original, decoded);
+    }
 }
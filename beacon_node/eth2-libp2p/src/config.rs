@@ -2,13 +2,17 @@ use clap::ArgMatches;
 use enr::Enr;
 use libp2p::gossipsub::{GossipsubConfig, GossipsubConfigBuilder};
 use serde_derive::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::fs::File;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// The beacon node topic string to subscribe to.
 pub const BEACON_PUBSUB_TOPIC: &str = "beacon_block";
 pub const BEACON_ATTESTATION_TOPIC: &str = "beacon_attestation";
+pub const BEACON_VOLUNTARY_EXIT_TOPIC: &str = "beacon_voluntary_exit";
 pub const SHARD_TOPIC_PREFIX: &str = "shard";
+/// Attestations for subnet `n` are published on the topic `<prefix>_<n>`.
+pub const ATTESTATION_SUBNET_TOPIC_PREFIX: &str = "beacon_attestation_subnet";
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(default)]
@@ -32,9 +36,21 @@ pub struct Config {
     /// Target number of connected peers.
     pub max_peers: usize,
 
-    /// Gossipsub configuration parameters.
-    #[serde(skip)]
-    pub gs_config: GossipsubConfig,
+    /// Discovery searches for more peers while fewer than this many are connected. Capped at
+    /// `max_peers`.
+    pub target_peer_count: usize,
+
+    /// The interval between gossipsub heartbeats, in milliseconds.
+    pub gossipsub_heartbeat_interval_ms: u64,
+
+    /// Target number of peers in each gossipsub topic mesh.
+    pub gossipsub_mesh_n: usize,
+
+    /// Minimum number of peers in a gossipsub topic mesh before more are grafted.
+    pub gossipsub_mesh_n_low: usize,
+
+    /// Maximum number of peers in a gossipsub topic mesh before some are pruned.
+    pub gossipsub_mesh_n_high: usize,
 
     /// List of nodes to initially connect to.
     pub boot_nodes: Vec<Enr>,
@@ -42,8 +58,22 @@ pub struct Config {
     /// Client version
     pub client_version: String,
 
+    /// The agent version sent to peers by the identify protocol.
+    pub identify_agent_string: String,
+
     /// List of extra topics to initially subscribe to as strings.
     pub topics: Vec<String>,
+
+    /// If `true`, count the RPC and gossip messages sent by this node.
+    pub metrics_enabled: bool,
+
+    /// The number of recently imported gossip attestations remembered in order to ignore
+    /// duplicates. Zero disables deduplication.
+    pub attestation_cache_size: usize,
+
+    /// The number of slots before an attestation duty that local validators subscribe to its
+    /// attestation subnet, allowing the subnet mesh to form in time.
+    pub attestation_subnet_subscription_slots_before_attestation: u64,
 }
 
 impl Default for Config {
@@ -59,15 +89,18 @@ impl Default for Config {
             discovery_address: "127.0.0.1".parse().expect("valid ip address"),
             discovery_port: 9000,
             max_peers: 10,
-            //TODO: Set realistic values for production
-            gs_config: GossipsubConfigBuilder::new()
-                .max_gossip_size(4_000_000)
-                .inactivity_timeout(Duration::from_secs(90))
-                .heartbeat_interval(Duration::from_secs(20))
-                .build(),
+            target_peer_count: 25,
+            gossipsub_heartbeat_interval_ms: 700,
+            gossipsub_mesh_n: 8,
+            gossipsub_mesh_n_low: 6,
+            gossipsub_mesh_n_high: 12,
             boot_nodes: vec![],
             client_version: version::version(),
+            identify_agent_string: version::version(),
             topics: Vec::new(),
+            metrics_enabled: true,
+            attestation_cache_size: 8_192,
+            attestation_subnet_subscription_slots_before_attestation: 2,
         }
     }
 }
@@ -78,7 +111,68 @@ impl Config {
         Config::default()
     }
 
+    /// Returns the default configuration with larger gossipsub meshes and a faster heartbeat,
+    /// trading bandwidth for faster block and attestation propagation during mainnet sync.
+    pub fn high_throughput_defaults() -> Self {
+        Config {
+            gossipsub_heartbeat_interval_ms: 500,
+            gossipsub_mesh_n: 12,
+            gossipsub_mesh_n_low: 8,
+            gossipsub_mesh_n_high: 16,
+            ..Config::default()
+        }
+    }
+
+    /// Reads a configuration from the YAML file at `path`.
+    ///
+    /// The file may specify any subset of fields; the remaining fields take their default values.
+    pub fn from_yaml_file(path: &Path) -> Result<Self, String> {
+        let mut config = Config::default();
+        config.apply_yaml_file(path)?;
+        Ok(config)
+    }
+
+    /// Overrides the fields of `self` with those present in the YAML file at `path`, leaving
+    /// fields absent from the file unchanged.
+    fn apply_yaml_file(&mut self, path: &Path) -> Result<(), String> {
+        let file = File::open(path)
+            .map_err(|e| format!("Unable to open network config file {:?}: {:?}", path, e))?;
+        let overrides: serde_yaml::Mapping = serde_yaml::from_reader(file)
+            .map_err(|e| format!("Unable to parse network config file {:?}: {:?}", path, e))?;
+
+        let mut fields = match serde_yaml::to_value(&*self) {
+            Ok(serde_yaml::Value::Mapping(fields)) => fields,
+            _ => return Err("Unable to serialize network config".to_string()),
+        };
+        for (key, value) in overrides {
+            fields.insert(key, value);
+        }
+
+        *self = serde_yaml::from_value(serde_yaml::Value::Mapping(fields))
+            .map_err(|e| format!("Invalid network config file {:?}: {:?}", path, e))?;
+
+        Ok(())
+    }
+
+    /// Builds the gossipsub configuration from the tuning parameters in `self`.
+    pub fn gossipsub_config(&self) -> GossipsubConfig {
+        //TODO: Set realistic values for production
+        GossipsubConfigBuilder::new()
+            .max_gossip_size(4_000_000)
+            .inactivity_timeout(Duration::from_secs(90))
+            .heartbeat_interval(Duration::from_millis(self.gossipsub_heartbeat_interval_ms))
+            .mesh_n(self.gossipsub_mesh_n)
+            .mesh_n_low(self.gossipsub_mesh_n_low)
+            .mesh_n_high(self.gossipsub_mesh_n_high)
+            .build()
+    }
+
     pub fn apply_cli_args(&mut self, args: &ArgMatches) -> Result<(), String> {
+        // Apply the file first, so that explicit CLI flags take precedence over it.
+        if let Some(path) = args.value_of("network-config-file") {
+            self.apply_yaml_file(Path::new(path))?;
+        }
+
         if let Some(dir) = args.value_of("datadir") {
             self.network_dir = PathBuf::from(dir).join("network");
         };
@@ -97,6 +191,16 @@ impl Config {
                 .map_err(|_| format!("Invalid number of max peers: {}", max_peers_str))?;
         }
 
+        if let Some(target_peers_str) = args.value_of("target-peers") {
+            self.target_peer_count = target_peers_str
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid target peer count: {}", target_peers_str))?;
+
+            if self.target_peer_count == 0 {
+                return Err("The target peer count must be greater than zero".to_string());
+            }
+        }
+
         if let Some(port_str) = args.value_of("port") {
             let port = port_str
                 .parse::<u16>()
@@ -118,6 +222,10 @@ impl Config {
                 .map_err(|_| format!("Invalid discovery address: {:?}", discovery_address_str))?
         }
 
+        if let Some(agent_string) = args.value_of("identify-agent") {
+            self.identify_agent_string = agent_string.to_string();
+        }
+
         if let Some(disc_port_str) = args.value_of("disc-port") {
             self.discovery_port = disc_port_str
                 .parse::<u16>()
@@ -127,3 +235,55 @@ impl Config {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn write_yaml(contents: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn yaml_file_round_trip() {
+        let config = Config {
+            max_peers: 42,
+            topics: vec!["shard0".to_string()],
+            ..Config::high_throughput_defaults()
+        };
+        let file = write_yaml(&serde_yaml::to_string(&config).unwrap());
+
+        let decoded = Config::from_yaml_file(file.path()).unwrap();
+
+        assert_eq!(
+            serde_yaml::to_string(&decoded).unwrap(),
+            serde_yaml::to_string(&config).unwrap()
+        );
+    }
+
+    #[test]
+    fn yaml_file_partial_override() {
+        let file = write_yaml("max_peers: 3\nlibp2p_port: 9100\n");
+
+        let mut config = Config {
+            gossipsub_mesh_n: 20,
+            ..Config::default()
+        };
+        config.apply_yaml_file(file.path()).unwrap();
+
+        assert_eq!(config.max_peers, 3);
+        assert_eq!(config.libp2p_port, 9100);
+        assert_eq!(config.gossipsub_mesh_n, 20);
+
+        let from_file = Config::from_yaml_file(file.path()).unwrap();
+        assert_eq!(from_file.max_peers, 3);
+        assert_eq!(
+            from_file.gossipsub_mesh_n,
+            Config::default().gossipsub_mesh_n
+        );
+    }
+}
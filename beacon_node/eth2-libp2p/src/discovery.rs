@@ -37,6 +37,9 @@ pub struct Discovery<TSubstream> {
     /// The target number of connected peers on the libp2p interface.
     max_peers: usize,
 
+    /// Peers are searched for while fewer than this many are connected.
+    target_peers: usize,
+
     /// The delay between peer discovery searches.
     peer_discovery_delay: Delay,
 
@@ -94,6 +97,7 @@ impl<TSubstream> Discovery<TSubstream> {
         Ok(Self {
             connected_peers: HashSet::new(),
             max_peers: config.max_peers,
+            target_peers: config.target_peer_count.min(config.max_peers),
             peer_discovery_delay: Delay::new(Instant::now()),
             past_discovery_delay: INITIAL_SEARCH_DELAY,
             tcp_port: config.libp2p_port,
@@ -191,8 +195,12 @@ where
         loop {
             match self.peer_discovery_delay.poll() {
                 Ok(Async::Ready(_)) => {
-                    if self.connected_peers.len() < self.max_peers {
+                    if self.connected_peers.len() < self.target_peers {
                         self.find_peers();
+                    } else {
+                        // Check again later, without backing off further.
+                        self.peer_discovery_delay
+                            .reset(Instant::now() + Duration::from_secs(self.past_discovery_delay));
                     }
                 }
                 Ok(Async::NotReady) => break,
@@ -11,7 +11,8 @@ mod service;
 
 pub use behaviour::PubsubMessage;
 pub use config::{
-    Config as NetworkConfig, BEACON_ATTESTATION_TOPIC, BEACON_PUBSUB_TOPIC, SHARD_TOPIC_PREFIX,
+    Config as NetworkConfig, ATTESTATION_SUBNET_TOPIC_PREFIX, BEACON_ATTESTATION_TOPIC,
+    BEACON_PUBSUB_TOPIC, BEACON_VOLUNTARY_EXIT_TOPIC, SHARD_TOPIC_PREFIX,
 };
 pub use libp2p::floodsub::{Topic, TopicBuilder, TopicHash};
 pub use libp2p::multiaddr;
@@ -4,7 +4,7 @@ use crate::multiaddr::Protocol;
 use crate::rpc::RPCEvent;
 use crate::NetworkConfig;
 use crate::{TopicBuilder, TopicHash};
-use crate::{BEACON_ATTESTATION_TOPIC, BEACON_PUBSUB_TOPIC};
+use crate::{BEACON_ATTESTATION_TOPIC, BEACON_PUBSUB_TOPIC, BEACON_VOLUNTARY_EXIT_TOPIC};
 use futures::prelude::*;
 use futures::Stream;
 use libp2p::core::{
@@ -81,6 +81,7 @@ impl Service {
         //attestations
         topics.push(BEACON_ATTESTATION_TOPIC.to_string());
         topics.push(BEACON_PUBSUB_TOPIC.to_string());
+        topics.push(BEACON_VOLUNTARY_EXIT_TOPIC.to_string());
         topics.append(&mut config.topics.clone());
 
         let mut subscribed_topics = vec![];
@@ -131,6 +132,18 @@ impl Stream for Service {
                     BehaviourEvent::PeerDialed(peer_id) => {
                         return Ok(Async::Ready(Some(Libp2pEvent::PeerDialed(peer_id))));
                     }
+                    BehaviourEvent::PeerDisconnected(peer_id) => {
+                        return Ok(Async::Ready(Some(Libp2pEvent::PeerDisconnected(peer_id))));
+                    }
+                    BehaviourEvent::PeerIdentified {
+                        peer_id,
+                        agent_version,
+                    } => {
+                        return Ok(Async::Ready(Some(Libp2pEvent::PeerIdentified {
+                            peer_id,
+                            agent_version,
+                        })));
+                    }
                 },
                 Ok(Async::Ready(None)) => unreachable!("Swarm stream shouldn't end"),
                 Ok(Async::NotReady) => break,
@@ -180,6 +193,13 @@ pub enum Libp2pEvent {
     RPC(PeerId, RPCEvent),
     /// Initiated the connection to a new peer.
     PeerDialed(PeerId),
+    /// A peer has disconnected.
+    PeerDisconnected(PeerId),
+    /// A peer sent its identify information.
+    PeerIdentified {
+        peer_id: PeerId,
+        agent_version: String,
+    },
     /// Received pubsub message.
     PubsubMessage {
         source: PeerId,
@@ -1,28 +1,158 @@
-use crate::{key::BeaconChainKey, map_persistent_err_to_500};
-use beacon_chain::{BeaconChain, BeaconChainTypes};
+use crate::{
+    key::{BeaconChainKey, NetworkServiceKey},
+    map_persistent_err_to_500,
+};
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes, GRAFFITI};
+use bls::{PublicKey, Signature};
+use eth2_libp2p::PeerId;
 use iron::prelude::*;
 use iron::{
     headers::{CacheControl, CacheDirective, ContentType},
     status::Status,
     AfterMiddleware, Handler, IronResult, Request, Response,
 };
+use network::Service as NetworkService;
 use persistent::Read;
 use router::Router;
+use serde::de::DeserializeOwned;
+use serde_derive::Deserialize;
 use serde_json::json;
+use ssz::Decode;
+use std::fmt::Debug;
+use std::io::Read as _;
+use std::str::FromStr;
 use std::sync::Arc;
+use types::{
+    Attestation, AttesterSlashing, BeaconStateError, Epoch, Hash256, ProposerSlashing, Slot,
+    VoluntaryExit,
+};
 
 /// Yields a handler for the HTTP API.
 pub fn build_handler<T: BeaconChainTypes + 'static>(
     beacon_chain: Arc<BeaconChain<T>>,
+    network: Arc<NetworkService<T>>,
 ) -> impl Handler {
     let mut router = Router::new();
 
     router.get("/node/fork", handle_fork::<T>, "fork");
+    router.get("/node/sync_state", handle_sync_state::<T>, "sync_state");
+    router.get("/node/syncing", handle_syncing::<T>, "syncing");
+    router.get("/network/peers/:peer_id", handle_peer::<T>, "peer");
+    router.get(
+        "/eth1/deposit_snapshot",
+        handle_deposit_snapshot::<T>,
+        "deposit_snapshot",
+    );
+    router.get(
+        "/beacon/eth1/vote_tally",
+        handle_eth1_vote_tally::<T>,
+        "eth1_vote_tally",
+    );
+    router.get(
+        "/beacon/expected_withdrawals",
+        handle_expected_withdrawals::<T>,
+        "expected_withdrawals",
+    );
+    router.get(
+        "/eth/v1/light_client/updates",
+        handle_light_client_updates::<T>,
+        "light_client_updates",
+    );
+    router.get("/beacon/genesis", handle_genesis::<T>, "genesis");
+    router.get("/beacon/state_root", handle_state_root::<T>, "state_root");
+    router.get("/beacon/fork", handle_fork_at_epoch::<T>, "fork_at_epoch");
+    router.get("/beacon/committees", handle_committees::<T>, "committees");
+    router.get("/beacon/shuffling", handle_shuffling::<T>, "shuffling");
+    router.get("/beacon/rewards", handle_rewards::<T>, "rewards");
+    router.get(
+        "/beacon/pool/slashings",
+        handle_get_pool_slashings::<T>,
+        "get_pool_slashings",
+    );
+    router.get(
+        "/beacon/validator_inclusion",
+        handle_validator_inclusion::<T>,
+        "validator_inclusion",
+    );
+
+    router.get(
+        "/beacon/validators/:index/withdrawal_credentials",
+        handle_withdrawal_credentials::<T>,
+        "withdrawal_credentials",
+    );
+
+    router.get(
+        "/beacon/block/:root/rewards",
+        handle_block_rewards::<T>,
+        "block_rewards",
+    );
+    router.get(
+        "/validator/block",
+        handle_produce_block::<T>,
+        "produce_block",
+    );
+    router.get(
+        "/validator/proof",
+        handle_shuffling_proof::<T>,
+        "shuffling_proof",
+    );
+    router.get(
+        "/validator/subnet_subscriptions",
+        handle_subnet_subscriptions::<T>,
+        "subnet_subscriptions",
+    );
+    router.get(
+        "/validator/:index/exit_epoch",
+        handle_voluntary_exit_epoch::<T>,
+        "voluntary_exit_epoch",
+    );
+    router.get(
+        "/validator/:index/activation_epoch",
+        handle_activation_epoch::<T>,
+        "activation_epoch",
+    );
+    router.post(
+        "/beacon/attestation_reward",
+        handle_attestation_reward::<T>,
+        "attestation_reward",
+    );
+    router.post(
+        "/beacon/validators/bulk_withdrawal_credentials",
+        handle_bulk_withdrawal_credentials::<T>,
+        "bulk_withdrawal_credentials",
+    );
+    router.post(
+        "/validator/status_bulk",
+        handle_validator_status_bulk::<T>,
+        "validator_status_bulk",
+    );
+    router.post(
+        "/beacon/verify_proposer_slashing",
+        handle_verify_proposer_slashing::<T>,
+        "verify_proposer_slashing",
+    );
+    router.post(
+        "/beacon/pool/slashings",
+        handle_post_pool_slashings::<T>,
+        "post_pool_slashings",
+    );
+    router.post(
+        "/beacon/pool/voluntary_exits",
+        handle_post_pool_voluntary_exit::<T>,
+        "post_pool_voluntary_exit",
+    );
+    router.post(
+        "/beacon/indexed_attestation",
+        handle_indexed_attestation::<T>,
+        "indexed_attestation",
+    );
 
     let mut chain = Chain::new(router);
 
     // Insert `BeaconChain` so it may be accessed in a request.
     chain.link(Read::<BeaconChainKey<T>>::both(beacon_chain.clone()));
+    // Insert the network service so the sync state may be accessed in a request.
+    chain.link(Read::<NetworkServiceKey<T>>::both(network));
     // Set the content-type headers.
     chain.link_after(SetJsonContentType);
     // Set the cache headers.
@@ -69,3 +199,824 @@ fn handle_fork<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<R
 
     Ok(Response::with((Status::Ok, response.to_string())))
 }
+
+fn handle_sync_state<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let network = req
+        .get::<Read<NetworkServiceKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let response = json!({
+        "sync_state": format!("{:?}", network.sync_state()),
+    });
+
+    Ok(Response::with((Status::Ok, response.to_string())))
+}
+
+fn handle_syncing<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let network = req
+        .get::<Read<NetworkServiceKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let status = network.get_sync_status();
+
+    let response = json!({
+        "sync_state": format!("{:?}", status.sync_state),
+        "head_slot": status.head_slot,
+        "head_root": status.head_root,
+        "finalized_slot": status.finalized_slot,
+        "finalized_root": status.finalized_root,
+        "estimated_highest_slot": status.estimated_highest_slot,
+        "peers_with_higher_slot": status.peers_with_higher_slot,
+    });
+
+    Ok(Response::with((Status::Ok, response.to_string())))
+}
+
+fn handle_peer<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let network = req
+        .get::<Read<NetworkServiceKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let peer_id = match req
+        .extensions
+        .get::<Router>()
+        .and_then(|params| params.find("peer_id"))
+        .and_then(|peer_id| peer_id.parse::<PeerId>().ok())
+    {
+        Some(peer_id) => peer_id,
+        None => {
+            let response = json!({ "error": "`peer_id` must be a base58-encoded peer id" });
+            return Ok(Response::with((Status::BadRequest, response.to_string())));
+        }
+    };
+
+    let agent_string = network.peer_agent_string(&peer_id);
+    let sync_info = network.peer_sync_info(&peer_id);
+
+    if agent_string.is_none() && sync_info.is_none() {
+        let response = json!({ "error": "unknown peer" });
+        return Ok(Response::with((Status::NotFound, response.to_string())));
+    }
+
+    let response = json!({
+        "peer_id": peer_id.to_base58(),
+        "agent_string": agent_string,
+        "best_slot": sync_info.map(|info| info.best_slot()),
+    });
+
+    Ok(Response::with((Status::Ok, response.to_string())))
+}
+
+fn handle_deposit_snapshot<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let (eth1_data, deposit_index) = beacon_chain.get_deposit_snapshot();
+
+    let response = json!({
+        "deposit_root": eth1_data.deposit_root,
+        "deposit_count": eth1_data.deposit_count,
+        "block_hash": eth1_data.block_hash,
+        "deposit_index": deposit_index,
+    });
+
+    Ok(Response::with((Status::Ok, response.to_string())))
+}
+
+fn handle_eth1_vote_tally<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let mut tally: Vec<_> = beacon_chain
+        .get_eth1_data_vote_tally()
+        .into_iter()
+        .collect();
+    tally.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    let response = json!(tally
+        .iter()
+        .map(|(eth1_data, count)| json!({ "eth1_data": eth1_data, "count": count }))
+        .collect::<Vec<_>>());
+
+    Ok(Response::with((Status::Ok, response.to_string())))
+}
+
+fn handle_expected_withdrawals<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let epoch = match integer_query_param(req, "epoch") {
+        Ok(epoch) => Epoch::new(epoch),
+        Err(response) => return Ok(response),
+    };
+
+    let withdrawals = match beacon_chain.compute_expected_withdrawals(epoch) {
+        Ok(withdrawals) => withdrawals,
+        Err(e) => return Ok(internal_server_error(e)),
+    };
+
+    let response = json!(withdrawals
+        .iter()
+        .map(|(address, balance)| json!({ "address": address, "balance": balance }))
+        .collect::<Vec<_>>());
+
+    Ok(Response::with((Status::Ok, response.to_string())))
+}
+
+/// The maximum `count` of a light client updates request.
+const MAX_LIGHT_CLIENT_UPDATES: u64 = 128;
+
+fn handle_light_client_updates<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let start_period = query_param(req, "start_period").and_then(|p| p.parse::<u64>().ok());
+    let count = query_param(req, "count").and_then(|c| c.parse::<u64>().ok());
+    let (start_period, count) = match (start_period, count) {
+        (Some(start_period), Some(count)) if count <= MAX_LIGHT_CLIENT_UPDATES => {
+            (start_period, count)
+        }
+        _ => {
+            let response = json!({
+                "error": format!(
+                    "integer `start_period` and `count` (at most {}) query parameters are required",
+                    MAX_LIGHT_CLIENT_UPDATES
+                )
+            });
+            return Ok(Response::with((Status::BadRequest, response.to_string())));
+        }
+    };
+
+    let mut updates = vec![];
+    for period in start_period..start_period.saturating_add(count) {
+        match beacon_chain.get_light_client_update(period) {
+            Ok(update) => updates.push(update),
+            // Stop at the first period that has not started.
+            Err(BeaconChainError::PeriodNotStarted { .. }) => break,
+            Err(e) => return Ok(internal_server_error(e)),
+        }
+    }
+
+    Ok(Response::with((Status::Ok, json!(updates).to_string())))
+}
+
+fn handle_genesis<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let response = json!({
+        "genesis_time": beacon_chain.head().beacon_state.genesis_time,
+        "genesis_slot": beacon_chain.spec.genesis_slot,
+        "genesis_block_root": beacon_chain.genesis_block_root(),
+        "genesis_validators_root": beacon_chain.genesis_validators_root(),
+    });
+
+    Ok(Response::with((Status::Ok, response.to_string())))
+}
+
+fn handle_state_root<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let slot = match integer_query_param(req, "slot") {
+        Ok(slot) => Slot::new(slot),
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.get_historical_state_root(slot) {
+        Ok(root) => {
+            let response = json!({ "slot": slot, "state_root": root });
+            Ok(Response::with((Status::Ok, response.to_string())))
+        }
+        Err(BeaconChainError::NotArchived(_)) | Err(BeaconChainError::NoStateForSlot(_)) => {
+            let response = json!({ "error": format!("no state root known for slot {}", slot) });
+            Ok(Response::with((Status::NotFound, response.to_string())))
+        }
+        Err(e) => Ok(internal_server_error(e)),
+    }
+}
+
+fn handle_fork_at_epoch<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let epoch = match integer_query_param(req, "epoch") {
+        Ok(epoch) => Epoch::new(epoch),
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.get_fork_at_epoch(epoch) {
+        Ok(fork) => Ok(Response::with((Status::Ok, json!(fork).to_string()))),
+        Err(e) => {
+            let response = json!({ "error": format!("{:?}", e) });
+            Ok(Response::with((Status::BadRequest, response.to_string())))
+        }
+    }
+}
+
+fn handle_committees<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let slot = match integer_query_param(req, "slot") {
+        Ok(slot) => Slot::new(slot),
+        Err(response) => return Ok(response),
+    };
+
+    let committees = match beacon_chain.get_beacon_committees_at_slot(slot) {
+        Ok(committees) => committees,
+        Err(e) => return Ok(internal_server_error(e)),
+    };
+
+    let response = json!(committees
+        .iter()
+        .map(|(shard, committee)| json!({ "shard": shard, "committee": committee }))
+        .collect::<Vec<_>>());
+
+    Ok(Response::with((Status::Ok, response.to_string())))
+}
+
+fn handle_shuffling<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let epoch = match integer_query_param::<u64>(req, "epoch") {
+        Ok(epoch) if epoch > 0 => Epoch::new(epoch),
+        _ => {
+            let response =
+                json!({ "error": "a positive integer `epoch` query parameter is required" });
+            return Ok(Response::with((Status::BadRequest, response.to_string())));
+        }
+    };
+
+    match beacon_chain.compute_next_shuffling(epoch - 1) {
+        Ok(shuffling) => Ok(Response::with((Status::Ok, json!(shuffling).to_string()))),
+        Err(e) => Ok(internal_server_error(e)),
+    }
+}
+
+fn handle_rewards<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let epoch = match integer_query_param(req, "epoch") {
+        Ok(epoch) => Epoch::new(epoch),
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.compute_rewards_and_penalties(epoch) {
+        Ok(rewards) => Ok(Response::with((Status::Ok, json!(rewards).to_string()))),
+        Err(e) => Ok(internal_server_error(e)),
+    }
+}
+
+fn handle_validator_inclusion<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let epoch = match integer_query_param(req, "epoch") {
+        Ok(epoch) => Epoch::new(epoch),
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.get_validator_inclusion_data(epoch) {
+        Ok(data) => Ok(Response::with((Status::Ok, json!(data).to_string()))),
+        Err(e) => Ok(internal_server_error(e)),
+    }
+}
+
+fn handle_withdrawal_credentials<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let index = match validator_index_param(req) {
+        Ok(index) => index,
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.get_validator_withdrawal_credentials(index) {
+        Some(credentials) => Ok(Response::with((Status::Ok, json!(credentials).to_string()))),
+        None => {
+            let response = json!({ "error": "unknown validator index" });
+            Ok(Response::with((Status::NotFound, response.to_string())))
+        }
+    }
+}
+
+fn handle_voluntary_exit_epoch<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let index = match validator_index_param(req) {
+        Ok(index) => index,
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.compute_voluntary_exit_epoch(index) {
+        Ok(exit_epoch) => {
+            let response = json!({
+                "validator_index": index,
+                "exit_epoch": exit_epoch,
+                "withdrawable_epoch":
+                    exit_epoch + beacon_chain.spec.min_validator_withdrawability_delay,
+            });
+            Ok(Response::with((Status::Ok, response.to_string())))
+        }
+        Err(BeaconChainError::BeaconStateError(BeaconStateError::UnknownValidator)) => {
+            let response = json!({ "error": "unknown validator index" });
+            Ok(Response::with((Status::NotFound, response.to_string())))
+        }
+        Err(e) => Ok(internal_server_error(e)),
+    }
+}
+
+fn handle_activation_epoch<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let index = match validator_index_param(req) {
+        Ok(index) => index,
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.compute_expected_activation_epoch(index) {
+        Ok(activation_epoch) => {
+            let response = json!({
+                "validator_index": index,
+                "activation_epoch": activation_epoch,
+            });
+            Ok(Response::with((Status::Ok, response.to_string())))
+        }
+        Err(BeaconChainError::BeaconStateError(BeaconStateError::UnknownValidator)) => {
+            let response = json!({ "error": "unknown validator index" });
+            Ok(Response::with((Status::NotFound, response.to_string())))
+        }
+        Err(BeaconChainError::InsufficientBalanceForActivation { .. }) => {
+            let response = json!({ "error": "balance is too low to join the activation queue" });
+            Ok(Response::with((Status::BadRequest, response.to_string())))
+        }
+        Err(e) => Ok(internal_server_error(e)),
+    }
+}
+
+/// Expects a JSON list of validator indices as the request body. Unknown validators are `null`.
+fn handle_bulk_withdrawal_credentials<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let indices = match read_json_body::<Vec<usize>>(req, "expected a list of indices") {
+        Ok(indices) => indices,
+        Err(response) => return Ok(response),
+    };
+
+    let credentials = beacon_chain.get_validators_withdrawal_credentials(&indices);
+
+    Ok(Response::with((Status::Ok, json!(credentials).to_string())))
+}
+
+fn handle_block_rewards<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let block_root = match req
+        .extensions
+        .get::<Router>()
+        .and_then(|params| params.find("root"))
+        .and_then(|root| Hash256::from_str(root.trim_start_matches("0x")).ok())
+    {
+        Some(block_root) => block_root,
+        None => {
+            let response = json!({ "error": "`root` must be a hex-encoded block root" });
+            return Ok(Response::with((Status::BadRequest, response.to_string())));
+        }
+    };
+
+    match beacon_chain.get_block_rewards(block_root) {
+        Ok(rewards) => Ok(Response::with((Status::Ok, json!(rewards).to_string()))),
+        Err(BeaconChainError::MissingBeaconBlock(root)) if root == block_root => {
+            let response = json!({ "error": "unknown block root" });
+            Ok(Response::with((Status::NotFound, response.to_string())))
+        }
+        Err(e) => Ok(internal_server_error(e)),
+    }
+}
+
+fn handle_attestation_reward<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let attestation = match read_json_body::<Attestation>(req, "invalid attestation") {
+        Ok(attestation) => attestation,
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.get_attestation_reward_data(&attestation) {
+        Ok(data) => Ok(Response::with((Status::Ok, json!(data).to_string()))),
+        Err(e) => {
+            let response = json!({ "error": format!("{:?}", e) });
+            Ok(Response::with((Status::BadRequest, response.to_string())))
+        }
+    }
+}
+
+fn handle_validator_status_bulk<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let pubkeys = match read_json_body::<Vec<PublicKey>>(req, "expected a list of pubkeys") {
+        Ok(pubkeys) => pubkeys,
+        Err(response) => return Ok(response),
+    };
+
+    let statuses = beacon_chain.list_attached_validators(&pubkeys);
+
+    Ok(Response::with((Status::Ok, json!(statuses).to_string())))
+}
+
+fn handle_verify_proposer_slashing<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let slashing = match read_json_body::<ProposerSlashing>(req, "invalid proposer slashing") {
+        Ok(slashing) => slashing,
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.verify_proposer_slashing(&slashing) {
+        Ok(slashable) => {
+            let response = json!({ "valid": true, "slashable": slashable });
+            Ok(Response::with((Status::Ok, response.to_string())))
+        }
+        Err(BeaconChainError::ProposerSlashingError(e)) => {
+            let response = json!({ "valid": false, "error": format!("{:?}", e) });
+            Ok(Response::with((Status::Ok, response.to_string())))
+        }
+        Err(e) => Ok(internal_server_error(e)),
+    }
+}
+
+fn handle_get_pool_slashings<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let (proposer_slashings, attester_slashings) = beacon_chain.get_slashings_from_pool();
+    let response = json!({
+        "proposer_slashings": proposer_slashings,
+        "attester_slashings": attester_slashings,
+    });
+
+    Ok(Response::with((Status::Ok, response.to_string())))
+}
+
+/// The body of a `POST /beacon/pool/slashings` request.
+#[derive(Deserialize)]
+struct PoolSlashings {
+    #[serde(default)]
+    proposer_slashings: Vec<ProposerSlashing>,
+    #[serde(default)]
+    attester_slashings: Vec<AttesterSlashing>,
+}
+
+fn handle_post_pool_slashings<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let slashings = match read_json_body::<PoolSlashings>(req, "invalid slashings") {
+        Ok(slashings) => slashings,
+        Err(response) => return Ok(response),
+    };
+
+    // Slashings before the first invalid one remain in the pool.
+    for (i, slashing) in slashings.proposer_slashings.into_iter().enumerate() {
+        if let Err(e) = beacon_chain.process_proposer_slashing(slashing) {
+            let response = json!({
+                "error": format!("invalid proposer slashing {}: {:?}", i, e),
+            });
+            return Ok(Response::with((Status::BadRequest, response.to_string())));
+        }
+    }
+
+    for (i, slashing) in slashings.attester_slashings.into_iter().enumerate() {
+        if let Err(e) = beacon_chain.process_attester_slashing(slashing) {
+            let response = json!({
+                "error": format!("invalid attester slashing {}: {:?}", i, e),
+            });
+            return Ok(Response::with((Status::BadRequest, response.to_string())));
+        }
+    }
+
+    Ok(Response::with((Status::Ok, json!({}).to_string())))
+}
+
+fn handle_post_pool_voluntary_exit<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let network = req
+        .get::<Read<NetworkServiceKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let exit = match read_json_body::<VoluntaryExit>(req, "invalid voluntary exit") {
+        Ok(exit) => exit,
+        Err(response) => return Ok(response),
+    };
+
+    match network.broadcast_voluntary_exit(exit) {
+        Ok(()) => Ok(Response::with((Status::Ok, json!({}).to_string()))),
+        Err(BeaconChainError::AlreadyExiting { .. }) => {
+            let response = json!({ "error": "validator has already initiated an exit" });
+            Ok(Response::with((Status::BadRequest, response.to_string())))
+        }
+        Err(BeaconChainError::BeaconStateError(BeaconStateError::UnknownValidator)) => {
+            let response = json!({ "error": "unknown validator index" });
+            Ok(Response::with((Status::NotFound, response.to_string())))
+        }
+        Err(BeaconChainError::ExitValidationError(e)) => {
+            let response = json!({ "error": format!("invalid voluntary exit: {:?}", e) });
+            Ok(Response::with((Status::BadRequest, response.to_string())))
+        }
+        Err(e) => Ok(internal_server_error(e)),
+    }
+}
+
+fn handle_indexed_attestation<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let attestation = match read_json_body::<Attestation>(req, "invalid attestation") {
+        Ok(attestation) => attestation,
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.get_indexed_attestation(&attestation) {
+        Ok(indexed) => Ok(Response::with((Status::Ok, json!(indexed).to_string()))),
+        Err(e) => {
+            let response = json!({ "error": format!("{:?}", e) });
+            Ok(Response::with((Status::BadRequest, response.to_string())))
+        }
+    }
+}
+
+/// The furthest ahead of the present slot that a block may be requested for, allowing for clock
+/// skew.
+/// Producing a block advances a copy of the state to `slot`, so far-future slots are expensive.
+const MAX_PRODUCE_BLOCK_SLOTS_AHEAD: u64 = 1;
+
+fn handle_produce_block<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let slot = match integer_query_param(req, "slot") {
+        Ok(slot) => Slot::new(slot),
+        Err(response) => return Ok(response),
+    };
+
+    let randao_reveal = match query_param(req, "randao_reveal")
+        .and_then(|reveal| hex::decode(reveal.trim_start_matches("0x")).ok())
+        .and_then(|bytes| Signature::from_ssz_bytes(&bytes).ok())
+    {
+        Some(randao_reveal) => randao_reveal,
+        None => {
+            let response = json!({ "error": "`randao_reveal` must be a hex-encoded signature" });
+            return Ok(Response::with((Status::BadRequest, response.to_string())));
+        }
+    };
+
+    let mut graffiti = [0; 32];
+    match query_param(req, "graffiti") {
+        Some(hex_graffiti) => match hex::decode(hex_graffiti.trim_start_matches("0x")) {
+            Ok(ref bytes) if bytes.len() <= graffiti.len() => {
+                graffiti[..bytes.len()].copy_from_slice(bytes)
+            }
+            _ => {
+                let response =
+                    json!({ "error": "`graffiti` must be at most 32 hex-encoded bytes" });
+                return Ok(Response::with((Status::BadRequest, response.to_string())));
+            }
+        },
+        None => graffiti.copy_from_slice(GRAFFITI.as_bytes()),
+    }
+
+    let present_slot = match beacon_chain.read_slot_clock() {
+        Some(present_slot) => present_slot,
+        None => {
+            let response = json!({ "error": "unable to read the slot clock" });
+            return Ok(Response::with((
+                Status::InternalServerError,
+                response.to_string(),
+            )));
+        }
+    };
+    if slot > present_slot + MAX_PRODUCE_BLOCK_SLOTS_AHEAD {
+        let response = json!({
+            "error": format!(
+                "`slot` must be at most {} ahead of the present slot {}",
+                MAX_PRODUCE_BLOCK_SLOTS_AHEAD, present_slot
+            )
+        });
+        return Ok(Response::with((Status::BadRequest, response.to_string())));
+    }
+
+    let state = beacon_chain.current_state().clone();
+    if slot < state.slot {
+        let response = json!({ "error": "`slot` must not be prior to the current state" });
+        return Ok(Response::with((Status::BadRequest, response.to_string())));
+    }
+
+    match beacon_chain.produce_block_on_state_with_graffiti(state, slot, randao_reveal, graffiti) {
+        Ok((block, _state)) => Ok(Response::with((Status::Ok, json!(block).to_string()))),
+        Err(e) => Ok(internal_server_error(e)),
+    }
+}
+
+fn handle_shuffling_proof<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let index = match integer_query_param(req, "index") {
+        Ok(index) => index,
+        Err(response) => return Ok(response),
+    };
+
+    let epoch = match integer_query_param(req, "epoch") {
+        Ok(epoch) => Epoch::new(epoch),
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.compute_shuffling_proof(index, epoch) {
+        Ok(proof) => Ok(Response::with((Status::Ok, json!(proof).to_string()))),
+        Err(e) => {
+            let response = json!({ "error": format!("{:?}", e) });
+            Ok(Response::with((Status::BadRequest, response.to_string())))
+        }
+    }
+}
+
+fn handle_subnet_subscriptions<T: BeaconChainTypes + 'static>(
+    req: &mut Request,
+) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let index = match integer_query_param(req, "index") {
+        Ok(index) => index,
+        Err(response) => return Ok(response),
+    };
+
+    let epoch = match integer_query_param(req, "epoch") {
+        Ok(epoch) => Epoch::new(epoch),
+        Err(response) => return Ok(response),
+    };
+
+    match beacon_chain.compute_participation_flags(index, epoch) {
+        Ok(flags) => {
+            let response = json!({
+                "validator_index": index,
+                "epoch": epoch,
+                "flags": flags.as_u64(),
+                "subnets": flags.subnets(),
+            });
+            Ok(Response::with((Status::Ok, response.to_string())))
+        }
+        Err(e) => {
+            let response = json!({ "error": format!("{:?}", e) });
+            Ok(Response::with((Status::BadRequest, response.to_string())))
+        }
+    }
+}
+
+/// Returns the value of the first `key=value` pair in the query string of `req`, if any.
+fn query_param<'a>(req: &'a Request, key: &str) -> Option<&'a str> {
+    req.url.query()?.split('&').find_map(|pair| {
+        let mut split = pair.splitn(2, '=');
+        match (split.next(), split.next()) {
+            (Some(k), Some(value)) if k == key => Some(value),
+            _ => None,
+        }
+    })
+}
+
+/// Parses the integer query parameter `key` of `req`.
+///
+/// Returns the error response to send if `key` is absent or not an integer.
+fn integer_query_param<T: FromStr>(req: &Request, key: &str) -> Result<T, Response> {
+    query_param(req, key)
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| {
+            let response =
+                json!({ "error": format!("an integer `{}` query parameter is required", key) });
+            Response::with((Status::BadRequest, response.to_string()))
+        })
+}
+
+/// Parses the `index` path parameter of `req` as a validator index.
+///
+/// Returns the error response to send if `index` is not an integer.
+fn validator_index_param(req: &Request) -> Result<usize, Response> {
+    req.extensions
+        .get::<Router>()
+        .and_then(|params| params.find("index"))
+        .and_then(|index| index.parse().ok())
+        .ok_or_else(|| {
+            let response = json!({ "error": "`index` must be an integer validator index" });
+            Response::with((Status::BadRequest, response.to_string()))
+        })
+}
+
+/// Returns a `500` response describing `e`.
+fn internal_server_error<E: Debug>(e: E) -> Response {
+    let response = json!({ "error": format!("{:?}", e) });
+    Response::with((Status::InternalServerError, response.to_string()))
+}
+
+/// The largest request body accepted by the POST handlers, in bytes.
+const MAX_REQUEST_BODY_BYTES: u64 = 1_048_576;
+
+/// Reads a JSON `T` from the body of `req`.
+///
+/// Returns the error response to send if the body exceeds `MAX_REQUEST_BODY_BYTES` or is not a
+/// valid `T`, in which case the error is prefixed with `description`.
+fn read_json_body<T: DeserializeOwned>(
+    req: &mut Request,
+    description: &str,
+) -> Result<T, Response> {
+    let mut body = vec![];
+    if let Err(e) = (&mut req.body)
+        .take(MAX_REQUEST_BODY_BYTES + 1)
+        .read_to_end(&mut body)
+    {
+        let response = json!({ "error": format!("unable to read request body: {}", e) });
+        return Err(Response::with((Status::BadRequest, response.to_string())));
+    }
+
+    if body.len() as u64 > MAX_REQUEST_BODY_BYTES {
+        let response = json!({
+            "error": format!("request body exceeds {} bytes", MAX_REQUEST_BODY_BYTES)
+        });
+        return Err(Response::with((
+            Status::PayloadTooLarge,
+            response.to_string(),
+        )));
+    }
+
+    serde_json::from_slice(&body).map_err(|e| {
+        let response = json!({ "error": format!("{}: {}", description, e) });
+        Response::with((Status::BadRequest, response.to_string()))
+    })
+}
@@ -1,6 +1,7 @@
 use crate::metrics::LocalMetrics;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use iron::typemap::Key;
+use network::Service as NetworkService;
 use prometheus::Registry;
 use std::marker::PhantomData;
 use std::path::PathBuf;
@@ -31,3 +32,11 @@ pub struct DBPathKey;
 impl Key for DBPathKey {
     type Value = PathBuf;
 }
+
+pub struct NetworkServiceKey<T> {
+    _phantom: PhantomData<T>,
+}
+
+impl<T: BeaconChainTypes + 'static> Key for NetworkServiceKey<T> {
+    type Value = Arc<NetworkService<T>>;
+}
@@ -7,6 +7,7 @@ use clap::ArgMatches;
 use futures::Future;
 use iron::prelude::*;
 use network::NetworkMessage;
+use network::Service as NetworkService;
 use prometheus::Registry;
 use router::Router;
 use serde_derive::{Deserialize, Serialize};
@@ -54,6 +55,7 @@ impl HttpServerConfig {
 /// Build the `iron` HTTP server, defining the core routes.
 pub fn create_iron_http_server<T: BeaconChainTypes + 'static>(
     beacon_chain: Arc<BeaconChain<T>>,
+    network: Arc<NetworkService<T>>,
     db_path: PathBuf,
     metrics_registry: Registry,
 ) -> Iron<Router> {
@@ -67,7 +69,11 @@ pub fn create_iron_http_server<T: BeaconChainTypes + 'static>(
     );
 
     // Any request to all other endpoints is handled by the `api` module.
-    router.any("/*", api::build_handler(beacon_chain.clone()), "api");
+    router.any(
+        "/*",
+        api::build_handler(beacon_chain.clone(), network),
+        "api",
+    );
 
     Iron::new(router)
 }
@@ -78,6 +84,7 @@ pub fn start_service<T: BeaconChainTypes + 'static>(
     executor: &TaskExecutor,
     _network_chan: mpsc::UnboundedSender<NetworkMessage>,
     beacon_chain: Arc<BeaconChain<T>>,
+    network: Arc<NetworkService<T>>,
     db_path: PathBuf,
     metrics_registry: Registry,
     log: &slog::Logger,
@@ -90,7 +97,7 @@ pub fn start_service<T: BeaconChainTypes + 'static>(
     let (shutdown_trigger, wait_for_shutdown) = exit_future::signal();
 
     // Create an `iron` http, without starting it yet.
-    let iron = create_iron_http_server(beacon_chain, db_path, metrics_registry);
+    let iron = create_iron_http_server(beacon_chain, network, db_path, metrics_registry);
 
     // Create a HTTP server future.
     //
@@ -14,6 +14,7 @@ pub struct LocalMetrics {
     present_epoch: IntGauge,
     best_slot: IntGauge,
     validator_count: IntGauge,
+    active_validator_count: IntGauge,
     justified_epoch: IntGauge,
     finalized_epoch: IntGauge,
     validator_balances_sum: IntGauge,
@@ -40,6 +41,10 @@ impl LocalMetrics {
                 let opts = Opts::new("validator_count", "number_of_validators");
                 IntGauge::with_opts(opts)?
             },
+            active_validator_count: {
+                let opts = Opts::new("active_validator_count", "number_of_active_validators");
+                IntGauge::with_opts(opts)?
+            },
             justified_epoch: {
                 let opts = Opts::new("justified_epoch", "state_justified_epoch");
                 IntGauge::with_opts(opts)?
@@ -65,6 +70,7 @@ impl LocalMetrics {
         registry.register(Box::new(self.present_epoch.clone()))?;
         registry.register(Box::new(self.best_slot.clone()))?;
         registry.register(Box::new(self.validator_count.clone()))?;
+        registry.register(Box::new(self.active_validator_count.clone()))?;
         registry.register(Box::new(self.finalized_epoch.clone()))?;
         registry.register(Box::new(self.justified_epoch.clone()))?;
         registry.register(Box::new(self.validator_balances_sum.clone()))?;
@@ -75,6 +81,10 @@ impl LocalMetrics {
 
     /// Update the metrics in `self` to the latest values.
     pub fn update<T: BeaconChainTypes>(&self, beacon_chain: &BeaconChain<T>, db_path: &PathBuf) {
+        // Read from the current state, so this is done before the head is locked.
+        self.active_validator_count
+            .set(beacon_chain.count_active_validators() as i64);
+
         let state = &beacon_chain.head().beacon_state;
 
         let present_slot = beacon_chain
@@ -1,6 +1,8 @@
 use crate::error;
+use crate::metrics::{rpc_method_label, Metrics};
 use crate::service::{NetworkMessage, OutgoingMessage};
-use crate::sync::SimpleSync;
+use crate::sync::{PeerSyncInfo, SimpleSync, SyncState};
+use beacon_chain::parking_lot::RwLock;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use eth2_libp2p::{
     behaviour::PubsubMessage,
@@ -50,6 +52,10 @@ impl<T: BeaconChainTypes + 'static> MessageHandler<T> {
     pub fn spawn(
         beacon_chain: Arc<BeaconChain<T>>,
         network_send: mpsc::UnboundedSender<NetworkMessage>,
+        sync_state: Arc<RwLock<SyncState>>,
+        known_peers: Arc<RwLock<HashMap<PeerId, PeerSyncInfo>>>,
+        metrics: Option<Arc<Metrics>>,
+        attestation_cache_size: usize,
         executor: &tokio::runtime::TaskExecutor,
         log: slog::Logger,
     ) -> error::Result<mpsc::UnboundedSender<HandlerMessage>> {
@@ -57,16 +63,16 @@ impl<T: BeaconChainTypes + 'static> MessageHandler<T> {
 
         let (handler_send, handler_recv) = mpsc::unbounded_channel();
 
-        // Initialise sync and begin processing in thread
         // generate the Message handler
-        let sync = SimpleSync::new(beacon_chain.clone(), &log);
-
-        let mut handler = MessageHandler {
-            _chain: beacon_chain.clone(),
-            sync,
-            network_context: NetworkContext::new(network_send, log.clone()),
-            log: log.clone(),
-        };
+        let mut handler = MessageHandler::new(
+            beacon_chain,
+            network_send,
+            sync_state,
+            known_peers,
+            metrics,
+            attestation_cache_size,
+            log.clone(),
+        );
 
         // spawn handler task
         // TODO: Handle manual termination of thread
@@ -81,6 +87,32 @@ impl<T: BeaconChainTypes + 'static> MessageHandler<T> {
         Ok(handler_send)
     }
 
+    /// Instantiates a `MessageHandler` without spawning it.
+    pub fn new(
+        beacon_chain: Arc<BeaconChain<T>>,
+        network_send: mpsc::UnboundedSender<NetworkMessage>,
+        sync_state: Arc<RwLock<SyncState>>,
+        known_peers: Arc<RwLock<HashMap<PeerId, PeerSyncInfo>>>,
+        metrics: Option<Arc<Metrics>>,
+        attestation_cache_size: usize,
+        log: slog::Logger,
+    ) -> Self {
+        let sync = SimpleSync::new(
+            beacon_chain.clone(),
+            sync_state,
+            known_peers,
+            attestation_cache_size,
+            &log,
+        );
+
+        MessageHandler {
+            _chain: beacon_chain,
+            sync,
+            network_context: NetworkContext::new(network_send, metrics, log.clone()),
+            log,
+        }
+    }
+
     /// Handle all messages incoming from the network service.
     fn handle_message(&mut self, message: HandlerMessage) {
         match message {
@@ -90,7 +122,7 @@ impl<T: BeaconChainTypes + 'static> MessageHandler<T> {
             }
             // we have received an RPC message request/response
             HandlerMessage::RPC(peer_id, rpc_event) => {
-                self.handle_rpc_message(peer_id, rpc_event);
+                self.process_rpc_event(peer_id, rpc_event);
             }
             // we have received an RPC message request/response
             HandlerMessage::PubsubMessage(peer_id, gossip) => {
@@ -103,9 +135,9 @@ impl<T: BeaconChainTypes + 'static> MessageHandler<T> {
 
     /* RPC - Related functionality */
 
-    /// Handle RPC messages
-    fn handle_rpc_message(&mut self, peer_id: PeerId, rpc_message: RPCEvent) {
-        match rpc_message {
+    /// Dispatches an RPC request or response from `peer_id` to the appropriate sync handler.
+    pub fn process_rpc_event(&mut self, peer_id: PeerId, event: RPCEvent) {
+        match event {
             RPCEvent::Request { id, body, .. // TODO: Clean up RPC Message types, have a cleaner type by this point.
             } => self.handle_rpc_request(peer_id, id, body),
             RPCEvent::Response { id, result, .. } => self.handle_rpc_response(peer_id, id, result),
@@ -217,6 +249,7 @@ impl<T: BeaconChainTypes + 'static> MessageHandler<T> {
                 self.sync
                     .on_attestation_gossip(peer_id, message, &mut self.network_context)
             }
+            PubsubMessage::VoluntaryExit(message) => self.sync.on_voluntary_exit_gossip(message),
         }
     }
 }
@@ -228,20 +261,32 @@ pub struct NetworkContext {
     outstanding_outgoing_request_ids: HashMap<(PeerId, RequestId), Instant>,
     /// Stores the next `RequestId` we should include on an outgoing `RPCRequest` to a `PeerId`.
     outgoing_request_ids: HashMap<PeerId, RequestId>,
+    /// Counters for sent RPC messages, `None` if metrics are disabled.
+    metrics: Option<Arc<Metrics>>,
     /// The `MessageHandler` logger.
     log: slog::Logger,
 }
 
 impl NetworkContext {
-    pub fn new(network_send: mpsc::UnboundedSender<NetworkMessage>, log: slog::Logger) -> Self {
+    pub fn new(
+        network_send: mpsc::UnboundedSender<NetworkMessage>,
+        metrics: Option<Arc<Metrics>>,
+        log: slog::Logger,
+    ) -> Self {
         Self {
             network_send,
             outstanding_outgoing_request_ids: HashMap::new(),
             outgoing_request_ids: HashMap::new(),
+            metrics,
             log,
         }
     }
 
+    /// Returns the network metrics, if they are enabled.
+    pub fn metrics(&self) -> Option<&Metrics> {
+        self.metrics.as_ref().map(|metrics| &**metrics)
+    }
+
     pub fn disconnect(&mut self, peer_id: PeerId, reason: GoodbyeReason) {
         self.send_rpc_request(peer_id, RPCRequest::Goodbye(reason))
         // TODO: disconnect peers.
@@ -253,6 +298,13 @@ impl NetworkContext {
         self.outstanding_outgoing_request_ids
             .insert((peer_id.clone(), id), Instant::now());
 
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .rpc_requests_total
+                .with_label_values(&[rpc_method_label(rpc_request.method_id())])
+                .inc();
+        }
+
         self.send_rpc_event(
             peer_id,
             RPCEvent::Request {
@@ -269,6 +321,13 @@ impl NetworkContext {
         request_id: RequestId,
         rpc_response: RPCResponse,
     ) {
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .rpc_responses_total
+                .with_label_values(&[rpc_method_label(rpc_response.method_id())])
+                .inc();
+        }
+
         self.send_rpc_event(
             peer_id,
             RPCEvent::Response {
@@ -306,3 +365,108 @@ impl NetworkContext {
         next_id.previous()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::DEFAULT_RECENT_ATTESTATIONS_SIZE;
+    use beacon_chain::lmd_ghost::ThreadSafeReducedTree;
+    use beacon_chain::store::MemoryStore;
+    use beacon_chain::test_utils::BeaconChainHarness;
+    use eth2_libp2p::rpc::methods::{
+        BeaconBlockBodiesRequest, BeaconBlockHeadersRequest, BeaconBlockRootsRequest,
+        BeaconBlockRootsResponse,
+    };
+    use types::{Hash256, MinimalEthSpec, Slot};
+
+    fn count(metric: &prometheus::IntCounterVec, label: &str) -> i64 {
+        metric.with_label_values(&[label]).get()
+    }
+
+    #[test]
+    fn rpc_counters_increment_during_sync() {
+        let (network_send, _network_recv) = mpsc::unbounded_channel();
+        let metrics = Arc::new(Metrics::new().unwrap());
+        let log = slog::Logger::root(slog::Discard, slog::o!());
+        let mut context = NetworkContext::new(network_send, Some(metrics.clone()), log);
+        let peer_id = PeerId::random();
+
+        context.send_rpc_request(
+            peer_id.clone(),
+            RPCRequest::BeaconBlockRoots(BeaconBlockRootsRequest {
+                start_slot: Slot::new(0),
+                count: 8,
+            }),
+        );
+        context.send_rpc_request(
+            peer_id.clone(),
+            RPCRequest::BeaconBlockHeaders(BeaconBlockHeadersRequest {
+                start_root: Hash256::zero(),
+                start_slot: Slot::new(0),
+                max_headers: 8,
+                skip_slots: 0,
+            }),
+        );
+        context.send_rpc_request(
+            peer_id.clone(),
+            RPCRequest::BeaconBlockBodies(BeaconBlockBodiesRequest {
+                block_roots: vec![],
+            }),
+        );
+        context.send_rpc_response(
+            peer_id.clone(),
+            RequestId::from(1),
+            RPCResponse::BeaconBlockRoots(BeaconBlockRootsResponse { roots: vec![] }),
+        );
+        context.disconnect(peer_id, GoodbyeReason::ClientShutdown);
+
+        let requests = &metrics.rpc_requests_total;
+        assert_eq!(count(requests, "beacon_block_roots"), 1);
+        assert_eq!(count(requests, "beacon_block_headers"), 1);
+        assert_eq!(count(requests, "beacon_block_bodies"), 1);
+        assert_eq!(count(requests, "goodbye"), 1);
+        assert_eq!(count(requests, "hello"), 0);
+        assert_eq!(count(&metrics.rpc_responses_total, "beacon_block_roots"), 1);
+    }
+
+    #[test]
+    fn process_rpc_event_answers_hello() {
+        let log = slog::Logger::root(slog::Discard, slog::o!());
+        let harness: BeaconChainHarness<
+            ThreadSafeReducedTree<MemoryStore, MinimalEthSpec>,
+            MinimalEthSpec,
+        > = BeaconChainHarness::new(8, log.clone());
+        let (network_send, network_recv) = mpsc::unbounded_channel();
+        let mut handler = MessageHandler::new(
+            Arc::new(harness.chain),
+            network_send,
+            Arc::new(RwLock::new(SyncState::Idle)),
+            Arc::new(RwLock::new(HashMap::new())),
+            None,
+            DEFAULT_RECENT_ATTESTATIONS_SIZE,
+            log,
+        );
+        let peer_id = PeerId::random();
+        let hello = RPCRequest::Hello(handler.sync.generate_hello());
+
+        handler.process_rpc_event(
+            peer_id.clone(),
+            RPCEvent::Request {
+                id: RequestId::from(1),
+                method_id: hello.method_id(),
+                body: hello,
+            },
+        );
+
+        match network_recv.wait().next() {
+            Some(Ok(NetworkMessage::Send(
+                peer,
+                OutgoingMessage::RPC(RPCEvent::Response {
+                    result: RPCResponse::Hello(_),
+                    ..
+                }),
+            ))) => assert_eq!(peer, peer_id),
+            other => panic!("expected a hello response, got {:?}", other),
+        }
+    }
+}
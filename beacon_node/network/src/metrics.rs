@@ -0,0 +1,62 @@
+use eth2_libp2p::rpc::RPCMethod;
+pub use prometheus::Error;
+use prometheus::{IntCounter, IntCounterVec, Opts, Registry};
+
+/// Counters for the messages this node sends to the network.
+pub struct Metrics {
+    pub rpc_requests_total: IntCounterVec,
+    pub rpc_responses_total: IntCounterVec,
+    pub gossip_messages_total: IntCounterVec,
+    pub clock_skew_events_total: IntCounter,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self, Error> {
+        Ok(Self {
+            rpc_requests_total: {
+                let opts = Opts::new("lighthouse_rpc_requests_total", "total_rpc_requests_sent");
+                IntCounterVec::new(opts, &["type"])?
+            },
+            rpc_responses_total: {
+                let opts = Opts::new("lighthouse_rpc_responses_total", "total_rpc_responses_sent");
+                IntCounterVec::new(opts, &["type"])?
+            },
+            gossip_messages_total: {
+                let opts = Opts::new(
+                    "lighthouse_gossip_messages_total",
+                    "total_gossip_messages_published",
+                );
+                IntCounterVec::new(opts, &["topic"])?
+            },
+            clock_skew_events_total: {
+                let opts = Opts::new(
+                    "lighthouse_clock_skew_events_total",
+                    "peers_with_a_head_beyond_our_clock",
+                );
+                IntCounter::with_opts(opts)?
+            },
+        })
+    }
+
+    pub fn register(&self, registry: &Registry) -> Result<(), Error> {
+        registry.register(Box::new(self.rpc_requests_total.clone()))?;
+        registry.register(Box::new(self.rpc_responses_total.clone()))?;
+        registry.register(Box::new(self.gossip_messages_total.clone()))?;
+        registry.register(Box::new(self.clock_skew_events_total.clone()))?;
+
+        Ok(())
+    }
+}
+
+/// Returns the label used for an RPC message with the given `method_id`.
+pub fn rpc_method_label(method_id: u16) -> &'static str {
+    match RPCMethod::from(method_id) {
+        RPCMethod::Hello => "hello",
+        RPCMethod::Goodbye => "goodbye",
+        RPCMethod::BeaconBlockRoots => "beacon_block_roots",
+        RPCMethod::BeaconBlockHeaders => "beacon_block_headers",
+        RPCMethod::BeaconBlockBodies => "beacon_block_bodies",
+        RPCMethod::BeaconChainState => "beacon_chain_state",
+        RPCMethod::Unknown => "unknown",
+    }
+}
@@ -1,24 +1,40 @@
 use crate::error;
 use crate::message_handler::{HandlerMessage, MessageHandler};
+use crate::metrics::Metrics;
+use crate::sync::{PeerSyncInfo, SyncState, SyncStatus};
 use crate::NetworkConfig;
-use beacon_chain::{BeaconChain, BeaconChainTypes};
+use beacon_chain::parking_lot::RwLock;
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use eth2_libp2p::Service as LibP2PService;
-use eth2_libp2p::Topic;
 use eth2_libp2p::{Libp2pEvent, PeerId};
 use eth2_libp2p::{PubsubMessage, RPCEvent};
+use eth2_libp2p::{
+    Topic, TopicBuilder, ATTESTATION_SUBNET_TOPIC_PREFIX, BEACON_VOLUNTARY_EXIT_TOPIC,
+};
 use futures::prelude::*;
 use futures::Stream;
 use slog::{debug, info, o, trace};
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use tokio::runtime::TaskExecutor;
 use tokio::sync::{mpsc, oneshot};
+use types::{Slot, VoluntaryExit};
 
 /// Service that handles communication between internal services and the eth2_libp2p network service.
 pub struct Service<T: BeaconChainTypes> {
     //libp2p_service: Arc<Mutex<LibP2PService>>,
     _libp2p_exit: oneshot::Sender<()>,
     network_send: mpsc::UnboundedSender<NetworkMessage>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    /// The state of the syncing protocol, updated by the message handler.
+    sync_state: Arc<RwLock<SyncState>>,
+    /// The sync info of connected peers, updated by the message handler.
+    known_peers: Arc<RwLock<HashMap<PeerId, PeerSyncInfo>>>,
+    /// The identify agent versions of peers, updated by the libp2p service.
+    peer_agents: Arc<RwLock<HashMap<PeerId, String>>>,
+    /// Counters for sent network messages, `None` if metrics are disabled.
+    metrics: Option<Arc<Metrics>>,
     _phantom: PhantomData<T>, //message_handler: MessageHandler,
                               //message_handler_send: Sender<HandlerMessage>
 }
@@ -32,11 +48,25 @@ impl<T: BeaconChainTypes + 'static> Service<T> {
     ) -> error::Result<(Arc<Self>, mpsc::UnboundedSender<NetworkMessage>)> {
         // build the network channel
         let (network_send, network_recv) = mpsc::unbounded_channel::<NetworkMessage>();
+        let sync_state = Arc::new(RwLock::new(SyncState::Idle));
+        let known_peers = Arc::new(RwLock::new(HashMap::new()));
+        let peer_agents = Arc::new(RwLock::new(HashMap::new()));
+        let metrics = if config.metrics_enabled {
+            Some(Arc::new(Metrics::new().map_err(|e| {
+                format!("Failed to create network metrics: {:?}", e)
+            })?))
+        } else {
+            None
+        };
         // launch message handler thread
         let message_handler_log = log.new(o!("Service" => "MessageHandler"));
         let message_handler_send = MessageHandler::spawn(
-            beacon_chain,
+            beacon_chain.clone(),
             network_send.clone(),
+            sync_state.clone(),
+            known_peers.clone(),
+            metrics.clone(),
+            config.attestation_cache_size,
             executor,
             message_handler_log,
         )?;
@@ -50,18 +80,80 @@ impl<T: BeaconChainTypes + 'static> Service<T> {
             libp2p_service,
             network_recv,
             message_handler_send,
+            peer_agents.clone(),
+            metrics.clone(),
             executor,
             log,
         )?;
         let network_service = Service {
             _libp2p_exit: libp2p_exit,
             network_send: network_send.clone(),
+            beacon_chain,
+            sync_state,
+            known_peers,
+            peer_agents,
+            metrics,
             _phantom: PhantomData,
         };
 
         Ok((Arc::new(network_service), network_send))
     }
 
+    /// Returns the current state of the syncing protocol.
+    pub fn sync_state(&self) -> SyncState {
+        *self.sync_state.read()
+    }
+
+    /// Returns a detailed snapshot of sync progress, including the head slots of known peers.
+    pub fn get_sync_status(&self) -> SyncStatus {
+        let sync_state = self.sync_state();
+        let known_peers = self.known_peers.read();
+
+        SyncStatus::new(
+            &self.beacon_chain,
+            sync_state,
+            known_peers.values().map(PeerSyncInfo::best_slot),
+        )
+    }
+
+    /// Returns the identify agent version of `peer_id`, if it has been received.
+    pub fn peer_agent_string(&self, peer_id: &PeerId) -> Option<String> {
+        self.peer_agents.read().get(peer_id).cloned()
+    }
+
+    /// Returns the sync info of `peer_id`, if it has completed a hello handshake.
+    pub fn peer_sync_info(&self, peer_id: &PeerId) -> Option<PeerSyncInfo> {
+        self.known_peers.read().get(peer_id).cloned()
+    }
+
+    /// Returns a shared handle to the state of the syncing protocol.
+    pub fn sync_state_handle(&self) -> Arc<RwLock<SyncState>> {
+        self.sync_state.clone()
+    }
+
+    /// Returns the network message counters, if metrics are enabled.
+    pub fn metrics(&self) -> Option<Arc<Metrics>> {
+        self.metrics.clone()
+    }
+
+    /// Verifies `exit` against the head, adds it to the operation pool and publishes it on the
+    /// voluntary exit gossip topic.
+    ///
+    /// Returns `Err(BeaconChainError::AlreadyExiting)` if the validator has already initiated an
+    /// exit or has one pending in the operation pool, in which case nothing is published.
+    pub fn broadcast_voluntary_exit(&self, exit: VoluntaryExit) -> Result<(), BeaconChainError> {
+        self.beacon_chain.import_voluntary_exit(exit.clone())?;
+
+        let topic = TopicBuilder::new(BEACON_VOLUNTARY_EXIT_TOPIC).build();
+        // The exit stays in the pool even if the network service has shut down.
+        let _ = self.network_send.clone().try_send(NetworkMessage::Publish {
+            topics: vec![topic],
+            message: Box::new(PubsubMessage::VoluntaryExit(exit)),
+        });
+
+        Ok(())
+    }
+
     // TODO: Testing only
     pub fn send_message(&mut self) {
         self.network_send
@@ -77,6 +169,8 @@ fn spawn_service(
     libp2p_service: LibP2PService,
     network_recv: mpsc::UnboundedReceiver<NetworkMessage>,
     message_handler_send: mpsc::UnboundedSender<HandlerMessage>,
+    peer_agents: Arc<RwLock<HashMap<PeerId, String>>>,
+    metrics: Option<Arc<Metrics>>,
     executor: &TaskExecutor,
     log: slog::Logger,
 ) -> error::Result<tokio::sync::oneshot::Sender<()>> {
@@ -88,6 +182,8 @@ fn spawn_service(
             libp2p_service,
             network_recv,
             message_handler_send,
+            peer_agents,
+            metrics,
             log.clone(),
         )
         // allow for manual termination
@@ -106,6 +202,8 @@ fn network_service(
     mut libp2p_service: LibP2PService,
     mut network_recv: mpsc::UnboundedReceiver<NetworkMessage>,
     mut message_handler_send: mpsc::UnboundedSender<HandlerMessage>,
+    peer_agents: Arc<RwLock<HashMap<PeerId, String>>>,
+    metrics: Option<Arc<Metrics>>,
     log: slog::Logger,
 ) -> impl futures::Future<Item = (), Error = eth2_libp2p::error::Error> {
     futures::future::poll_fn(move || -> Result<_, eth2_libp2p::error::Error> {
@@ -133,8 +231,40 @@ fn network_service(
                         }
                         NetworkMessage::Publish { topics, message } => {
                             debug!(log, "Sending pubsub message"; "topics" => format!("{:?}",topics));
+                            if let Some(metrics) = &metrics {
+                                for topic in &topics {
+                                    metrics
+                                        .gossip_messages_total
+                                        .with_label_values(&[topic.id()])
+                                        .inc();
+                                }
+                            }
                             libp2p_service.swarm.publish(topics, *message);
                         }
+                        NetworkMessage::SubscribeToSubnet { subnet_id, slot } => {
+                            let topic = TopicBuilder::new(format!(
+                                "{}_{}",
+                                ATTESTATION_SUBNET_TOPIC_PREFIX, subnet_id
+                            ))
+                            .build();
+                            if libp2p_service.swarm.subscribe(topic) {
+                                debug!(log, "Subscribed to attestation subnet"; "subnet" => subnet_id, "slot" => slot);
+                            } else {
+                                trace!(log, "Already subscribed to attestation subnet"; "subnet" => subnet_id);
+                            }
+                        }
+                        NetworkMessage::UnsubscribeFromSubnet { subnet_id } => {
+                            let topic = TopicBuilder::new(format!(
+                                "{}_{}",
+                                ATTESTATION_SUBNET_TOPIC_PREFIX, subnet_id
+                            ))
+                            .build();
+                            if libp2p_service.swarm.unsubscribe(topic) {
+                                debug!(log, "Unsubscribed from attestation subnet"; "subnet" => subnet_id);
+                            } else {
+                                trace!(log, "Not subscribed to attestation subnet"; "subnet" => subnet_id);
+                            }
+                        }
                     }
                 }
                 Ok(Async::NotReady) => not_ready_count += 1,
@@ -161,6 +291,16 @@ fn network_service(
                             .try_send(HandlerMessage::PeerDialed(peer_id))
                             .map_err(|_| "failed to send rpc to handler")?;
                     }
+                    Libp2pEvent::PeerDisconnected(peer_id) => {
+                        debug!(log, "Peer Disconnected: {:?}", peer_id);
+                        peer_agents.write().remove(&peer_id);
+                    }
+                    Libp2pEvent::PeerIdentified {
+                        peer_id,
+                        agent_version,
+                    } => {
+                        peer_agents.write().insert(peer_id, agent_version);
+                    }
                     Libp2pEvent::PubsubMessage {
                         source, message, ..
                     } => {
@@ -191,6 +331,12 @@ pub enum NetworkMessage {
         topics: Vec<Topic>,
         message: Box<PubsubMessage>,
     },
+    /// Subscribe to the gossip topic of attestation subnet `subnet_id`, if not already
+    /// subscribed, ahead of an attestation duty at `slot`.
+    SubscribeToSubnet { subnet_id: u64, slot: Slot },
+    /// Unsubscribe from the gossip topic of attestation subnet `subnet_id`, once its attestation
+    /// duties have passed.
+    UnsubscribeFromSubnet { subnet_id: u64 },
 }
 
 /// Type of outgoing messages that can be sent through the network service.
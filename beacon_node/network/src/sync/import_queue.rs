@@ -58,6 +58,26 @@ impl<T: BeaconChainTypes> ImportQueue<T> {
         }
     }
 
+    /// Returns the complete blocks that form a chain ending at `block_root`, in ascending slot
+    /// order, by following parent roots back through the queue until a block is missing or
+    /// incomplete.
+    ///
+    /// Does not remove the blocks from the `import_queue`.
+    pub fn complete_chain_segment(&self, block_root: Hash256) -> Vec<(Hash256, BeaconBlock)> {
+        let mut segment = vec![];
+        let mut root = block_root;
+
+        while let PartialBeaconBlockCompletion::Complete(block) = self.attempt_complete_block(root)
+        {
+            let parent = block.previous_block_root;
+            segment.push((root, block));
+            root = parent;
+        }
+
+        segment.reverse();
+        segment
+    }
+
     /// Removes the first `PartialBeaconBlock` with a matching `block_root`, returning the partial
     /// if it exists.
     pub fn remove(&mut self, block_root: Hash256) -> Option<PartialBeaconBlock> {
@@ -1,10 +1,12 @@
 mod import_queue;
+mod recent_attestations;
 /// Syncing for lighthouse.
 ///
 /// Stores the various syncing methods for the beacon chain.
 mod simple_sync;
 
-pub use simple_sync::SimpleSync;
+pub use recent_attestations::DEFAULT_RECENT_ATTESTATIONS_SIZE;
+pub use simple_sync::{PeerSyncInfo, SimpleSync, SyncState, SyncStatus};
 
 /// Currently implemented sync methods.
 pub enum SyncMethod {
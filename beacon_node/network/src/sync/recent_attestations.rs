@@ -0,0 +1,68 @@
+use lru::LruCache;
+use types::Hash256;
+
+/// The default number of attestations remembered by `RecentAttestations`.
+pub const DEFAULT_RECENT_ATTESTATIONS_SIZE: usize = 8_192;
+
+/// A bounded, least-recently-used set of the roots of attestations recently imported from gossip,
+/// used to drop duplicates without processing them again.
+///
+/// A `capacity` of zero disables the cache.
+pub struct RecentAttestations {
+    cache: Option<LruCache<Hash256, ()>>,
+}
+
+impl RecentAttestations {
+    pub fn new(capacity: usize) -> Self {
+        let cache = if capacity == 0 {
+            None
+        } else {
+            Some(LruCache::new(capacity))
+        };
+
+        Self { cache }
+    }
+
+    /// Returns `true` if `attestation_root` has been recorded and not since evicted.
+    pub fn contains(&mut self, attestation_root: &Hash256) -> bool {
+        self.cache
+            .as_mut()
+            .map_or(false, |cache| cache.get(attestation_root).is_some())
+    }
+
+    /// Records `attestation_root`, evicting the least-recently-used root if at capacity.
+    pub fn insert(&mut self, attestation_root: Hash256) {
+        if let Some(cache) = self.cache.as_mut() {
+            cache.put(attestation_root, ());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let roots: Vec<Hash256> = (0..3).map(|_| Hash256::random()).collect();
+        let mut recent = RecentAttestations::new(2);
+
+        recent.insert(roots[0]);
+        recent.insert(roots[1]);
+        assert!(recent.contains(&roots[0]));
+
+        recent.insert(roots[2]);
+        assert!(recent.contains(&roots[0]));
+        assert!(!recent.contains(&roots[1]));
+        assert!(recent.contains(&roots[2]));
+    }
+
+    #[test]
+    fn zero_capacity_is_disabled() {
+        let root = Hash256::random();
+        let mut recent = RecentAttestations::new(0);
+
+        recent.insert(root);
+        assert!(!recent.contains(&root));
+    }
+}
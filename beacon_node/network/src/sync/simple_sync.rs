@@ -1,5 +1,7 @@
 use super::import_queue::{ImportQueue, PartialBeaconBlockCompletion};
+use super::recent_attestations::RecentAttestations;
 use crate::message_handler::NetworkContext;
+use beacon_chain::parking_lot::RwLock;
 use beacon_chain::{BeaconChain, BeaconChainTypes, BlockProcessingOutcome};
 use eth2_libp2p::rpc::methods::*;
 use eth2_libp2p::rpc::{RPCRequest, RPCResponse, RequestId};
@@ -9,8 +11,10 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use store::Store;
+use tree_hash::TreeHash;
 use types::{
     Attestation, BeaconBlock, BeaconBlockBody, BeaconBlockHeader, Epoch, EthSpec, Hash256, Slot,
+    VoluntaryExit,
 };
 
 /// The number of slots that we can import blocks ahead of us, before going into full Sync mode.
@@ -23,6 +27,10 @@ const QUEUE_STALE_SECS: u64 = 100;
 /// Otherwise we queue it.
 const FUTURE_SLOT_TOLERANCE: u64 = 1;
 
+/// If a peer's head is more than `MAX_CLOCK_DISPARITY_SLOTS` ahead of our slot clock, one of our
+/// clocks is likely skewed.
+const MAX_CLOCK_DISPARITY_SLOTS: u64 = 2;
+
 const SHOULD_FORWARD_GOSSIP_BLOCK: bool = true;
 const SHOULD_NOT_FORWARD_GOSSIP_BLOCK: bool = false;
 
@@ -36,6 +44,22 @@ pub struct PeerSyncInfo {
     best_slot: Slot,
 }
 
+impl PeerSyncInfo {
+    /// The slot of the peer's head block.
+    pub fn best_slot(&self) -> Slot {
+        self.best_slot
+    }
+
+    /// Records that the peer has the block `block_root` at `slot`, advancing its head if the block
+    /// is later than the head from its last `Hello`.
+    pub fn on_block(&mut self, block_root: Hash256, slot: Slot) {
+        if slot > self.best_slot {
+            self.best_root = block_root;
+            self.best_slot = slot;
+        }
+    }
+}
+
 impl From<HelloMessage> for PeerSyncInfo {
     fn from(hello: HelloMessage) -> PeerSyncInfo {
         PeerSyncInfo {
@@ -55,29 +79,119 @@ impl<T: BeaconChainTypes> From<&Arc<BeaconChain<T>>> for PeerSyncInfo {
 }
 
 /// The current syncing state.
-#[derive(PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SyncState {
+    /// No sync has been attempted yet.
     Idle,
-    Downloading,
-    _Stopped,
+    /// Downloading the ancestors of a block with an unknown parent.
+    BackFilling,
+    /// Downloading blocks towards the head of a peer's chain.
+    HeadSync,
+    /// The local head is within `SLOT_IMPORT_TOLERANCE` slots of the present slot.
+    Synced,
+}
+
+impl SyncState {
+    /// Returns the state after requesting `count` block roots from a peer.
+    ///
+    /// `is_parent_lookup` is `true` if the roots are the ancestors of a block with an unknown
+    /// parent, rather than the path to the peer's head.
+    pub fn on_block_roots_request(self, count: u64, is_parent_lookup: bool) -> Self {
+        if count <= SLOT_IMPORT_TOLERANCE {
+            self
+        } else if is_parent_lookup {
+            SyncState::BackFilling
+        } else {
+            SyncState::HeadSync
+        }
+    }
+
+    /// Returns the state after a block at `head_slot` has been imported when the present slot is
+    /// `present_slot`.
+    pub fn on_block_imported(self, head_slot: Slot, present_slot: Slot) -> Self {
+        if head_slot + SLOT_IMPORT_TOLERANCE >= present_slot {
+            SyncState::Synced
+        } else {
+            self
+        }
+    }
+}
+
+/// A detailed snapshot of sync progress, for operator dashboards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncStatus {
+    pub sync_state: SyncState,
+    pub head_slot: Slot,
+    pub head_root: Hash256,
+    pub finalized_slot: Slot,
+    pub finalized_root: Hash256,
+    /// The highest head slot of the local chain and all known peers.
+    pub estimated_highest_slot: Slot,
+    /// The number of known peers with a head slot higher than ours.
+    pub peers_with_higher_slot: usize,
+}
+
+impl SyncStatus {
+    /// Builds the status of `chain` given the head slots of all known peers.
+    pub fn new<T: BeaconChainTypes, I>(
+        chain: &BeaconChain<T>,
+        sync_state: SyncState,
+        peer_best_slots: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = Slot>,
+    {
+        let head = chain.head();
+        let head_slot = head.beacon_state.slot;
+
+        let mut estimated_highest_slot = head_slot;
+        let mut peers_with_higher_slot = 0;
+        for best_slot in peer_best_slots {
+            if best_slot > head_slot {
+                peers_with_higher_slot += 1;
+            }
+            estimated_highest_slot = std::cmp::max(estimated_highest_slot, best_slot);
+        }
+
+        Self {
+            sync_state,
+            head_slot,
+            head_root: head.beacon_block_root,
+            finalized_slot: head
+                .beacon_state
+                .finalized_epoch
+                .start_slot(T::EthSpec::slots_per_epoch()),
+            finalized_root: head.beacon_state.finalized_root,
+            estimated_highest_slot,
+            peers_with_higher_slot,
+        }
+    }
 }
 
 /// Simple Syncing protocol.
 pub struct SimpleSync<T: BeaconChainTypes> {
     /// A reference to the underlying beacon chain.
     chain: Arc<BeaconChain<T>>,
-    /// A mapping of Peers to their respective PeerSyncInfo.
-    known_peers: HashMap<PeerId, PeerSyncInfo>,
+    /// A mapping of Peers to their respective PeerSyncInfo, shared with the `NetworkService`.
+    known_peers: Arc<RwLock<HashMap<PeerId, PeerSyncInfo>>>,
     /// A queue to allow importing of blocks
     import_queue: ImportQueue<T>,
-    /// The current state of the syncing protocol.
-    state: SyncState,
+    /// The current state of the syncing protocol, shared with the `NetworkService`.
+    state: Arc<RwLock<SyncState>>,
+    /// Attestations recently imported from gossip, used to ignore duplicates.
+    recent_attestations: RecentAttestations,
     log: slog::Logger,
 }
 
 impl<T: BeaconChainTypes> SimpleSync<T> {
     /// Instantiate a `SimpleSync` instance, with no peers and an empty queue.
-    pub fn new(beacon_chain: Arc<BeaconChain<T>>, log: &slog::Logger) -> Self {
+    pub fn new(
+        beacon_chain: Arc<BeaconChain<T>>,
+        state: Arc<RwLock<SyncState>>,
+        known_peers: Arc<RwLock<HashMap<PeerId, PeerSyncInfo>>>,
+        attestation_cache_size: usize,
+        log: &slog::Logger,
+    ) -> Self {
         let sync_logger = log.new(o!("Service"=> "Sync"));
 
         let queue_item_stale_time = Duration::from_secs(QUEUE_STALE_SECS);
@@ -86,9 +200,10 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
             ImportQueue::new(beacon_chain.clone(), queue_item_stale_time, log.clone());
         SimpleSync {
             chain: beacon_chain.clone(),
-            known_peers: HashMap::new(),
+            known_peers,
             import_queue,
-            state: SyncState::Idle,
+            state,
+            recent_attestations: RecentAttestations::new(attestation_cache_size),
             log: sync_logger,
         }
     }
@@ -103,7 +218,7 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
             "reason" => format!("{:?}", reason),
         );
 
-        self.known_peers.remove(&peer_id);
+        self.known_peers.write().remove(&peer_id);
     }
 
     /// Handle the connection of a new peer.
@@ -164,6 +279,22 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
 
         let start_slot = |epoch: Epoch| epoch.start_slot(T::EthSpec::slots_per_epoch());
 
+        let local_current_slot = self
+            .chain
+            .read_slot_clock()
+            .unwrap_or_else(|| self.chain.present_slot());
+        if remote.best_slot > local_current_slot + MAX_CLOCK_DISPARITY_SLOTS {
+            warn!(
+                self.log, "Possible clock skew detected";
+                "peer" => format!("{:?}", peer_id),
+                "remote_head_slot" => remote.best_slot,
+                "local_current_slot" => local_current_slot,
+            );
+            if let Some(metrics) = network.metrics() {
+                metrics.clock_skew_events_total.inc();
+            }
+        }
+
         if local.network_id != remote.network_id {
             // The node is on a different network, disconnect them.
             info!(
@@ -190,6 +321,8 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
             );
             network.disconnect(peer_id.clone(), GoodbyeReason::IrreleventNetwork);
         } else if remote.latest_finalized_epoch < local.latest_finalized_epoch {
+            self.known_peers.write().insert(peer_id.clone(), remote);
+
             // The node has a lower finalized epoch, their chain is not useful to us. There are two
             // cases where a node can have a lower finalized epoch:
             //
@@ -215,6 +348,8 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
             .exists::<BeaconBlock>(&remote.best_root)
             .unwrap_or_else(|_| false)
         {
+            self.known_peers.write().insert(peer_id.clone(), remote);
+
             // If the node's best-block is already known to us, we have nothing to request.
             debug!(
                 self.log,
@@ -227,6 +362,8 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
             //
             // Therefore, there are some blocks between the local finalized epoch and the remote
             // head that are worth downloading.
+            self.known_peers.write().insert(peer_id.clone(), remote);
+
             debug!(
                 self.log, "UsefulPeer";
                 "peer" => format!("{:?}", peer_id),
@@ -245,6 +382,7 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
                     start_slot,
                     count: required_slots.as_u64(),
                 },
+                false,
                 network,
             );
         }
@@ -274,6 +412,16 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
             "start_slot" => req.start_slot,
         );
 
+        // We do not have any blocks prior to the tail, respond with an empty list.
+        if req.start_slot + req.count <= self.chain.tail_slot() {
+            network.send_rpc_response(
+                peer_id,
+                request_id,
+                RPCResponse::BeaconBlockRoots(BeaconBlockRootsResponse { roots: vec![] }),
+            );
+            return;
+        }
+
         let mut roots: Vec<BlockRootSlot> = self
             .chain
             .rev_iter_best_block_roots(req.start_slot + req.count)
@@ -509,8 +657,15 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
                 .import_queue
                 .enqueue_bodies(res.block_bodies, peer_id.clone());
 
-            // Attempt to process all recieved bodies by recursively processing the latest block
+            // Import the complete chain segment ending at the latest block.
             if let Some(root) = last_root {
+                self.process_chain_segment(&peer_id, root);
+            }
+
+            // Attempt to process any remaining bodies by recursively processing the latest block
+            if let Some(root) =
+                last_root.filter(|root| self.import_queue.contains_block_root(*root))
+            {
                 match self.attempt_process_partial_block(peer_id, root, network, &"rpc") {
                     Some(BlockProcessingOutcome::Processed { block_root: _ }) => {
                         // If processing is successful remove from `import_queue`
@@ -536,11 +691,30 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
         block: BeaconBlock,
         network: &mut NetworkContext,
     ) -> bool {
+        match self.chain.optimistic_update(&block) {
+            Ok(result) if result.definitely_invalid => {
+                warn!(
+                    self.log, "InvalidGossipBlock";
+                    "reason" => result.reason.unwrap_or_default(),
+                    "peer" => format!("{:?}", peer_id),
+                );
+                return SHOULD_NOT_FORWARD_GOSSIP_BLOCK;
+            }
+            Err(e) => {
+                warn!(self.log, "GossipBlockPreValidationFailed"; "error" => format!("{:?}", e));
+            }
+            _ => {}
+        }
+
         if let Some(outcome) =
             self.process_block(peer_id.clone(), block.clone(), network, &"gossip")
         {
             match outcome {
-                BlockProcessingOutcome::Processed { .. } => SHOULD_FORWARD_GOSSIP_BLOCK,
+                BlockProcessingOutcome::Processed { .. } => {
+                    self.insert_block_attestations_into_pool(&block);
+
+                    SHOULD_FORWARD_GOSSIP_BLOCK
+                }
                 BlockProcessingOutcome::ParentUnknown { parent } => {
                     // Add this block to the queue
                     self.import_queue
@@ -566,6 +740,7 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
                             start_slot,
                             count: block.slot.as_u64() - start_slot.as_u64(),
                         },
+                        true,
                         network,
                     );
 
@@ -595,35 +770,88 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
         }
     }
 
+    /// Adds the attestations included in `block` to the operation pool, so they may be aggregated
+    /// with, or included alongside, attestations received via gossip.
+    ///
+    /// Attestations that cannot be added (e.g., because they are too old) are ignored.
+    fn insert_block_attestations_into_pool(&self, block: &BeaconBlock) {
+        let state = self.chain.current_state();
+
+        for attestation in &block.body.attestations {
+            if let Err(e) =
+                self.chain
+                    .op_pool
+                    .insert_attestation(attestation.clone(), &state, &self.chain.spec)
+            {
+                trace!(
+                    self.log, "BlockAttestationNotPooled";
+                    "block_slot" => block.slot,
+                    "error" => format!("{:?}", e),
+                );
+            }
+        }
+    }
+
     /// Process a gossip message declaring a new attestation.
     ///
-    /// Not currently implemented.
+    /// Attestations identical to one recently imported are ignored.
     pub fn on_attestation_gossip(
         &mut self,
         _peer_id: PeerId,
         msg: Attestation,
         _network: &mut NetworkContext,
     ) {
-        match self.chain.process_attestation(msg) {
-            Ok(()) => info!(self.log, "ImportedAttestation"; "source" => "gossip"),
+        let attestation_root = Hash256::from_slice(&msg.tree_hash_root());
+        if self.recent_attestations.contains(&attestation_root) {
+            return;
+        }
+
+        let verified = match self.chain.verify_aggregate_attestation(&msg) {
+            Ok(verified) => verified,
+            Err(e) => {
+                warn!(self.log, "InvalidAttestation"; "source" => "gossip", "error" => format!("{:?}", e));
+                return;
+            }
+        };
+
+        match self.chain.process_verified_attestation(verified) {
+            Ok(()) => {
+                self.recent_attestations.insert(attestation_root);
+                info!(self.log, "ImportedAttestation"; "source" => "gossip")
+            }
             Err(e) => {
                 warn!(self.log, "InvalidAttestation"; "source" => "gossip", "error" => format!("{:?}", e))
             }
         }
     }
 
+    /// Process a gossip message declaring a new voluntary exit.
+    pub fn on_voluntary_exit_gossip(&mut self, msg: VoluntaryExit) {
+        let validator_index = msg.validator_index;
+
+        match self.chain.import_voluntary_exit(msg) {
+            Ok(()) => {
+                info!(self.log, "ImportedVoluntaryExit"; "source" => "gossip", "validator_index" => validator_index)
+            }
+            Err(e) => {
+                debug!(self.log, "InvalidVoluntaryExit"; "source" => "gossip", "error" => format!("{:?}", e))
+            }
+        }
+    }
+
     /// Request some `BeaconBlockRoots` from the remote peer.
+    ///
+    /// `is_parent_lookup` should be `true` if the roots are the ancestors of a block with an
+    /// unknown parent.
     fn request_block_roots(
         &mut self,
         peer_id: PeerId,
         req: BeaconBlockRootsRequest,
+        is_parent_lookup: bool,
         network: &mut NetworkContext,
     ) {
         // Potentially set state to sync.
-        if self.state == SyncState::Idle && req.count > SLOT_IMPORT_TOLERANCE {
-            debug!(self.log, "Entering downloading sync state.");
-            self.state = SyncState::Downloading;
-        }
+        self.update_state(|state| state.on_block_roots_request(req.count, is_parent_lookup));
 
         debug!(
             self.log,
@@ -670,6 +898,29 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
         network.send_rpc_request(peer_id.clone(), RPCRequest::BeaconBlockBodies(req));
     }
 
+    /// Returns the current state of the syncing protocol.
+    pub fn state(&self) -> SyncState {
+        *self.state.read()
+    }
+
+    /// Applies `transition` to the current state, logging any change.
+    fn update_state<F>(&self, transition: F)
+    where
+        F: FnOnce(SyncState) -> SyncState,
+    {
+        let mut state = self.state.write();
+        let new_state = transition(*state);
+
+        if new_state != *state {
+            debug!(
+                self.log, "SyncStateChange";
+                "from" => format!("{:?}", *state),
+                "to" => format!("{:?}", new_state),
+            );
+            *state = new_state;
+        }
+    }
+
     /// Returns `true` if `self.chain` has not yet processed this block.
     pub fn chain_has_seen_block(&self, block_root: &Hash256) -> bool {
         !self
@@ -681,6 +932,15 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
             })
     }
 
+    /// Updates the head of `peer_id` in `known_peers` after the block `block_root` at `slot`, which
+    /// it sent, was successfully processed. This keeps the estimated highest slot from going
+    /// stale between `Hello` messages, without trusting blocks that may be invalid.
+    fn on_peer_block(&self, peer_id: &PeerId, block_root: Hash256, slot: Slot) {
+        if let Some(info) = self.known_peers.write().get_mut(peer_id) {
+            info.on_block(block_root, slot);
+        }
+    }
+
     /// Generates our current state in the form of a HELLO RPC message.
     pub fn generate_hello(&self) -> HelloMessage {
         hello_message(&self.chain)
@@ -769,6 +1029,74 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
         }
     }
 
+    /// Imports the complete chain segment in the `import_queue` that ends at `block_root`,
+    /// removing each successfully processed block from the queue.
+    ///
+    /// Blocks that fail to import are left in the queue for `attempt_process_partial_block`.
+    fn process_chain_segment(&mut self, peer_id: &PeerId, block_root: Hash256) {
+        let (roots, blocks): (Vec<Hash256>, Vec<BeaconBlock>) = self
+            .import_queue
+            .complete_chain_segment(block_root)
+            .into_iter()
+            .unzip();
+
+        if blocks.is_empty() {
+            return;
+        }
+
+        let slots: Vec<Slot> = blocks.iter().map(|block| block.slot).collect();
+
+        let outcomes = match self.chain.process_chain_segment(blocks) {
+            Ok(outcomes) => outcomes,
+            Err(e) => {
+                error!(
+                    self.log, "ChainSegmentProcessingError";
+                    "peer" => format!("{:?}", peer_id),
+                    "error" => format!("{:?}", e),
+                );
+                return;
+            }
+        };
+
+        let mut imported = 0;
+        let mut latest_imported = None;
+        for ((root, slot), outcome) in roots.into_iter().zip(slots).zip(outcomes) {
+            match outcome {
+                BlockProcessingOutcome::Processed { .. }
+                | BlockProcessingOutcome::BlockIsAlreadyKnown => {
+                    self.import_queue.remove(root);
+                    imported += 1;
+                    latest_imported = Some((root, slot));
+                }
+                outcome => {
+                    debug!(
+                        self.log, "ChainSegmentInterrupted";
+                        "block_root" => format!("{}", root),
+                        "outcome" => format!("{:?}", outcome),
+                        "peer" => format!("{:?}", peer_id),
+                    );
+                }
+            }
+        }
+
+        // The peer head only advances to blocks that were successfully processed.
+        if let Some((root, slot)) = latest_imported {
+            self.on_peer_block(peer_id, root, slot);
+        }
+
+        if imported > 0 {
+            debug!(
+                self.log, "Imported chain segment from network";
+                "count" => imported,
+                "peer" => format!("{:?}", peer_id),
+            );
+
+            let head_slot = self.chain.best_slot();
+            let present_slot = self.chain.present_slot();
+            self.update_state(|state| state.on_block_imported(head_slot, present_slot));
+        }
+    }
+
     /// Processes the `block` that was received from `peer_id`.
     ///
     /// If the block was submitted to the beacon chain without internal error, `Some(outcome)` is
@@ -803,6 +1131,12 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
                         "block_root" => format!("{}", block_root),
                         "peer" => format!("{:?}", peer_id),
                     );
+
+                    self.on_peer_block(&peer_id, block_root, block.slot);
+
+                    let head_slot = self.chain.best_slot();
+                    let present_slot = self.chain.present_slot();
+                    self.update_state(|state| state.on_block_imported(head_slot, present_slot));
                 }
                 BlockProcessingOutcome::ParentUnknown { parent } => {
                     // The parent has not been processed
@@ -815,14 +1149,27 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
                     );
 
                     // If the parent is in the `import_queue` attempt to complete it then process it.
-                    match self.attempt_process_partial_block(peer_id, parent, network, source) {
+                    match self.attempt_process_partial_block(
+                        peer_id.clone(),
+                        parent,
+                        network,
+                        source,
+                    ) {
                         // If processing parent is sucessful, re-process block and remove parent from queue
                         Some(BlockProcessingOutcome::Processed { block_root: _ }) => {
                             self.import_queue.remove(parent);
 
                             // Attempt to process `block` again
+                            let block_slot = block.slot;
                             match self.chain.process_block(block) {
-                                Ok(outcome) => return Some(outcome),
+                                Ok(outcome) => {
+                                    if let BlockProcessingOutcome::Processed { block_root } =
+                                        outcome
+                                    {
+                                        self.on_peer_block(&peer_id, block_root, block_slot);
+                                    }
+                                    return Some(outcome);
+                                }
                                 Err(_) => return None,
                             }
                         }
@@ -897,3 +1244,102 @@ fn hello_message<T: BeaconChainTypes>(beacon_chain: &BeaconChain<T>) -> HelloMes
         best_slot: state.slot,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer_sync_info(best_slot: u64) -> PeerSyncInfo {
+        PeerSyncInfo::from(HelloMessage {
+            network_id: 1,
+            latest_finalized_root: Hash256::zero(),
+            latest_finalized_epoch: Epoch::new(0),
+            best_root: Hash256::from_slice(&[1; 32]),
+            best_slot: Slot::new(best_slot),
+        })
+    }
+
+    #[test]
+    fn later_block_advances_peer_head() {
+        let mut info = peer_sync_info(10);
+        let block_root = Hash256::from_slice(&[2; 32]);
+
+        info.on_block(block_root, Slot::new(12));
+
+        assert_eq!(info.best_slot(), Slot::new(12));
+        assert_eq!(info.best_root, block_root);
+    }
+
+    #[test]
+    fn earlier_block_does_not_rewind_peer_head() {
+        let mut info = peer_sync_info(10);
+
+        info.on_block(Hash256::from_slice(&[2; 32]), Slot::new(9));
+        info.on_block(Hash256::from_slice(&[3; 32]), Slot::new(10));
+
+        assert_eq!(info.best_slot(), Slot::new(10));
+        assert_eq!(info.best_root, Hash256::from_slice(&[1; 32]));
+    }
+
+    #[test]
+    fn small_requests_do_not_change_state() {
+        for state in &[
+            SyncState::Idle,
+            SyncState::BackFilling,
+            SyncState::HeadSync,
+            SyncState::Synced,
+        ] {
+            assert_eq!(
+                state.on_block_roots_request(SLOT_IMPORT_TOLERANCE, false),
+                *state
+            );
+            assert_eq!(
+                state.on_block_roots_request(SLOT_IMPORT_TOLERANCE, true),
+                *state
+            );
+        }
+    }
+
+    #[test]
+    fn large_head_request_enters_head_sync() {
+        let count = SLOT_IMPORT_TOLERANCE + 1;
+
+        assert_eq!(
+            SyncState::Idle.on_block_roots_request(count, false),
+            SyncState::HeadSync
+        );
+        assert_eq!(
+            SyncState::Synced.on_block_roots_request(count, false),
+            SyncState::HeadSync
+        );
+    }
+
+    #[test]
+    fn large_parent_lookup_enters_back_filling() {
+        let count = SLOT_IMPORT_TOLERANCE + 1;
+
+        assert_eq!(
+            SyncState::Idle.on_block_roots_request(count, true),
+            SyncState::BackFilling
+        );
+        assert_eq!(
+            SyncState::HeadSync.on_block_roots_request(count, true),
+            SyncState::BackFilling
+        );
+    }
+
+    #[test]
+    fn importing_near_present_slot_enters_synced() {
+        let present_slot = Slot::new(1_000);
+        let near = present_slot - SLOT_IMPORT_TOLERANCE;
+        let far = near - 1;
+
+        for state in &[SyncState::Idle, SyncState::BackFilling, SyncState::HeadSync] {
+            assert_eq!(
+                state.on_block_imported(near, present_slot),
+                SyncState::Synced
+            );
+            assert_eq!(state.on_block_imported(far, present_slot), *state);
+        }
+    }
+}
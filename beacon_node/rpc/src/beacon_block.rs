@@ -1,4 +1,4 @@
-use beacon_chain::{BeaconChain, BeaconChainTypes, BlockProcessingOutcome};
+use beacon_chain::{BeaconChain, BeaconChainTypes, BlockProcessingOutcome, GRAFFITI};
 use eth2_libp2p::BEACON_PUBSUB_TOPIC;
 use eth2_libp2p::{PubsubMessage, TopicBuilder};
 use futures::Future;
@@ -51,7 +51,24 @@ impl<T: BeaconChainTypes> BeaconBlockService for BeaconBlockServiceInstance<T> {
             }
         };
 
-        let produced_block = match self.chain.produce_block(randao_reveal) {
+        // An empty `graffiti` falls back to the node's default.
+        let mut graffiti = [0; 32];
+        match req.get_graffiti().len() {
+            0 => graffiti.copy_from_slice(GRAFFITI.as_bytes()),
+            32 => graffiti.copy_from_slice(req.get_graffiti()),
+            _ => {
+                let log_clone = self.log.clone();
+                let f = sink
+                    .fail(RpcStatus::new(
+                        RpcStatusCode::InvalidArgument,
+                        Some("Graffiti must be 32 bytes".to_string()),
+                    ))
+                    .map_err(move |e| warn!(log_clone, "failed to reply {:?}: {:?}", req, e));
+                return ctx.spawn(f);
+            }
+        }
+
+        let produced_block = match self.chain.produce_block_v2(randao_reveal, graffiti) {
             Ok((block, _state)) => block,
             Err(e) => {
                 // could not produce a block
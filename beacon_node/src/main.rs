@@ -1,10 +1,10 @@
 mod run;
 
 use clap::{App, Arg};
-use client::{ClientConfig, Eth2Config};
+use client::{ClientConfig, Eth2Config, LogConfig, LogFormat};
 use env_logger::{Builder, Env};
 use eth2_config::{read_from_file, write_to_file};
-use slog::{crit, o, Drain, Level};
+use slog::crit;
 use std::fs;
 use std::path::PathBuf;
 
@@ -50,6 +50,13 @@ fn main() {
                 .help("The maximum number of peers (default 10).")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("target-peers")
+                .long("target-peers")
+                .value_name("COUNT")
+                .help("Search for peers while fewer than this many are connected, up to --maxpeers (default 25).")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("boot-nodes")
                 .long("boot-nodes")
@@ -72,6 +79,20 @@ fn main() {
                 .help("The discovery UDP port.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("network-config-file")
+                .long("network-config-file")
+                .value_name("FILE")
+                .help("A YAML file whose fields override the network configuration.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("identify-agent")
+                .long("identify-agent")
+                .value_name("AGENT")
+                .help("The agent version sent to peers by the libp2p identify protocol.")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("discovery-address")
                 .long("discovery-address")
@@ -129,6 +150,50 @@ fn main() {
                 .possible_values(&["disk", "memory"])
                 .default_value("memory"),
         )
+        .arg(
+            Arg::with_name("produce-blocks")
+                .long("produce-blocks")
+                .help("Produce blocks for the testnet validators, without a validator client.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("trusted-block-root")
+                .long("trusted-block-root")
+                .value_name("ROOT")
+                .help("Initialize an empty database from this trusted block root.")
+                .takes_value(true)
+                .requires("trusted-block-slot"),
+        )
+        .arg(
+            Arg::with_name("trusted-block-slot")
+                .long("trusted-block-slot")
+                .value_name("SLOT")
+                .help("The slot of the block given by --trusted-block-root.")
+                .takes_value(true)
+                .requires("trusted-block-root"),
+        )
+        .arg(
+            Arg::with_name("execution-endpoint")
+                .long("execution-endpoint")
+                .value_name("URL")
+                .help("The Engine API endpoint of the execution engine.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("execution-jwt")
+                .long("execution-jwt")
+                .value_name("PATH")
+                .help("A file holding the hex-encoded secret shared with the execution engine.")
+                .takes_value(true)
+                .requires("execution-endpoint"),
+        )
+        .arg(
+            Arg::with_name("execution-timeout")
+                .long("execution-timeout")
+                .value_name("MILLISECONDS")
+                .help("The time allowed for the execution engine to respond.")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("spec-constants")
                 .long("spec-constants")
@@ -139,12 +204,28 @@ fn main() {
                 .possible_values(&["mainnet", "minimal"])
                 .default_value("minimal"),
         )
+        .arg(
+            Arg::with_name("network")
+                .long("network")
+                .value_name("NETWORK")
+                .help("Sets the spec constants and boot nodes for a known network.")
+                .takes_value(true)
+                .possible_values(&["mainnet", "pyrmont", "prater", "minimal"]),
+        )
         .arg(
             Arg::with_name("recent-genesis")
                 .long("recent-genesis")
                 .short("r")
                 .help("When present, genesis will be within 30 minutes prior. Only for testing"),
         )
+        .arg(
+            Arg::with_name("log-level")
+                .long("log-level")
+                .value_name("LEVEL")
+                .help("The minimum level of log records to output. Overrides --verbosity.")
+                .takes_value(true)
+                .possible_values(&["debug", "info", "warn", "error", "crit"]),
+        )
         .arg(
             Arg::with_name("verbosity")
                 .short("v")
@@ -155,18 +236,28 @@ fn main() {
         .get_matches();
 
     // build the initial logger
-    let decorator = slog_term::TermDecorator::new().build();
-    let drain = slog_term::CompactFormat::new(decorator).build().fuse();
-    let drain = slog_async::Async::new(drain).build();
-
-    let drain = match matches.occurrences_of("verbosity") {
-        0 => drain.filter_level(Level::Info),
-        1 => drain.filter_level(Level::Debug),
-        2 => drain.filter_level(Level::Trace),
-        _ => drain.filter_level(Level::Trace),
+    let log_config = LogConfig {
+        log_level: match (
+            matches.value_of("log-level"),
+            matches.occurrences_of("verbosity"),
+        ) {
+            (Some(level), _) => level,
+            (None, 0) => "info",
+            (None, 1) => "debug",
+            (None, _) => "trace",
+        }
+        .to_string(),
+        log_format: LogFormat::Terminal,
+        log_file: None,
     };
 
-    let mut log = slog::Logger::root(drain.fuse(), o!());
+    let mut log = match log_config.init_logger() {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!("Failed to initialize logger: {}", e);
+            return;
+        }
+    };
 
     let data_dir = match matches
         .value_of("datadir")
@@ -254,6 +345,15 @@ fn main() {
         }
     };
 
+    // Apply any network-wide overrides, which may replace the `Eth2Config` entirely.
+    match client_config.apply_network_cli_args(&matches, &mut eth2_config) {
+        Ok(()) => (),
+        Err(s) => {
+            crit!(log, "Failed to apply --network CLI argument"; "error" => s);
+            return;
+        }
+    };
+
     // Update the eth2 config with any CLI flags.
     match eth2_config.apply_cli_args(&matches) {
         Ok(()) => (),
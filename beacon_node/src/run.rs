@@ -1,6 +1,6 @@
 use client::{
-    error, notifier, BeaconChainTypes, Client, ClientConfig, ClientType, Eth2Config,
-    InitialiseBeaconChain,
+    error, notifier, testnet_keypairs, BeaconChainTypes, Client, ClientConfig, ClientType,
+    Eth2Config, InitialiseBeaconChain,
 };
 use futures::sync::oneshot;
 use futures::Future;
@@ -20,6 +20,9 @@ pub fn run_beacon_node(
     eth2_config: Eth2Config,
     log: &slog::Logger,
 ) -> error::Result<()> {
+    client_config.validate()?;
+    let client_config = client_config.run_pre_start_check(&eth2_config, log)?;
+
     let runtime = Builder::new()
         .name_prefix("main-")
         .clock(Clock::system())
@@ -106,7 +109,18 @@ where
 {
     let store = T::Store::open_database(&db_path)?;
 
-    let client: Client<T> = Client::new(client_config, eth2_config, store, log.clone(), &executor)?;
+    let keypairs = if client_config.produce_blocks {
+        Some(testnet_keypairs::<T::EthSpec>(&eth2_config.spec))
+    } else {
+        None
+    };
+
+    let mut client: Client<T> =
+        Client::new(client_config, eth2_config, store, log.clone(), &executor)?;
+
+    if let Some(keypairs) = keypairs {
+        client = client.enable_block_production(keypairs, &executor)?;
+    }
 
     // run service until ctrl-c
     let (ctrlc_send, ctrlc_oneshot) = oneshot::channel();
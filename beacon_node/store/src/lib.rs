@@ -19,8 +19,11 @@ pub mod iter;
 pub use self::leveldb_store::LevelDB as DiskStore;
 pub use self::memory_store::MemoryStore;
 pub use errors::Error;
+pub use leveldb::options::Options as DiskStoreOptions;
 pub use types::*;
 
+use ssz::{Decode, Encode};
+
 /// An object capable of storing and retrieving objects implementing `StoreItem`.
 ///
 /// A `Store` is fundamentally backed by a key-value database, however it provides support for
@@ -60,6 +63,46 @@ pub trait Store: Sync + Send + Sized {
         block_at_slot::get_block_at_preceeding_slot(self, slot, start_block_root)
     }
 
+    /// Store the `sidecar` of the block with root `block_root`, keyed by the sidecar's index.
+    fn put_blob_sidecar(&self, block_root: &Hash256, sidecar: &BlobSidecar) -> Result<(), Error> {
+        let key = blob_sidecar_key(block_root, sidecar.index);
+
+        self.put_bytes(DBColumn::BlobSidecar.into(), &key, &sidecar.as_ssz_bytes())
+    }
+
+    /// Retrieve the blob sidecar at `index` of the block with root `block_root`.
+    fn get_blob_sidecar(
+        &self,
+        block_root: &Hash256,
+        index: u64,
+    ) -> Result<Option<BlobSidecar>, Error> {
+        let key = blob_sidecar_key(block_root, index);
+
+        match self.get_bytes(DBColumn::BlobSidecar.into(), &key)? {
+            Some(bytes) => Ok(Some(BlobSidecar::from_ssz_bytes(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieve all blob sidecars of the block with root `block_root`, ordered by index.
+    ///
+    /// Lists the block's sidecar keys by prefix, so gaps in the indices are skipped.
+    fn get_blob_sidecars(&self, block_root: &Hash256) -> Result<Vec<BlobSidecar>, Error> {
+        let column: &str = DBColumn::BlobSidecar.into();
+        let prefix = [column.as_bytes(), block_root.as_bytes()].concat();
+
+        self.iter_keys_by_prefix(&prefix)
+            .map(|key| {
+                let bytes = self
+                    .get_bytes(column, &key?[column.len()..])?
+                    .ok_or_else(|| Error::DBError {
+                        message: "blob sidecar deleted during iteration".to_string(),
+                    })?;
+                Ok(BlobSidecar::from_ssz_bytes(&bytes)?)
+            })
+            .collect()
+    }
+
     /// Retrieve some bytes in `column` with `key`.
     fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error>;
 
@@ -71,6 +114,20 @@ pub trait Store: Sync + Send + Sized {
 
     /// Removes `key` from `column`.
     fn key_delete(&self, column: &str, key: &[u8]) -> Result<(), Error>;
+
+    /// Atomically stores each `(key, value)` pair in `items`; either all are written or none are.
+    ///
+    /// Keys must include their column identifier, see `StoreItem::as_batch_item`.
+    fn batch_put(&self, items: Vec<(Vec<u8>, Vec<u8>)>) -> Result<(), Error>;
+
+    /// Iterates, in ascending order, over all keys that start with `prefix`.
+    ///
+    /// Keys include their column identifier, so a column's keys may be listed by passing its
+    /// identifier as the prefix.
+    fn iter_keys_by_prefix<'a>(
+        &'a self,
+        prefix: &[u8],
+    ) -> Box<dyn Iterator<Item = Result<Vec<u8>, Error>> + 'a>;
 }
 
 /// A unique column identifier.
@@ -78,6 +135,13 @@ pub enum DBColumn {
     BeaconBlock,
     BeaconState,
     BeaconChain,
+    BlobSidecar,
+}
+
+/// Blob sidecars are keyed by their block root followed by their big-endian index, so all the
+/// sidecars of a block are adjacent.
+fn blob_sidecar_key(block_root: &Hash256, index: u64) -> Vec<u8> {
+    [block_root.as_bytes(), &index.to_be_bytes()].concat()
 }
 
 impl<'a> Into<&'a str> for DBColumn {
@@ -87,6 +151,7 @@ impl<'a> Into<&'a str> for DBColumn {
             DBColumn::BeaconBlock => &"blk",
             DBColumn::BeaconState => &"ste",
             DBColumn::BeaconChain => &"bch",
+            DBColumn::BlobSidecar => &"blb",
         }
     }
 }
@@ -105,6 +170,17 @@ pub trait StoreItem: Sized {
     /// De-serialize `self` from bytes.
     fn from_store_bytes(bytes: &mut [u8]) -> Result<Self, Error>;
 
+    /// Returns the column-prefixed key and the value under which `self` is stored, suitable for
+    /// `Store::batch_put`.
+    fn as_batch_item(&self, key: &Hash256) -> (Vec<u8>, Vec<u8>) {
+        let column: &str = Self::db_column().into();
+
+        (
+            [column.as_bytes(), key.as_bytes()].concat(),
+            self.as_store_bytes(),
+        )
+    }
+
     /// Store `self`.
     fn db_put(&self, store: &impl Store, key: &Hash256) -> Result<(), Error> {
         let column = Self::db_column().into();
@@ -146,11 +222,10 @@ pub trait StoreItem: Sized {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ssz::{Decode, Encode};
     use ssz_derive::{Decode, Encode};
     use tempfile::tempdir;
 
-    #[derive(PartialEq, Debug, Encode, Decode)]
+    #[derive(PartialEq, Debug, Clone, Encode, Decode)]
     struct StorableThing {
         a: u64,
         b: u64,
@@ -206,6 +281,128 @@ mod tests {
         test_impl(store);
     }
 
+    fn test_iter_keys_by_prefix(store: impl Store) {
+        let mut keys = vec![Hash256::random(), Hash256::random()];
+        let item = StorableThing { a: 1, b: 42 };
+
+        for key in &keys {
+            store.put(key, &item).unwrap();
+        }
+        // Columns sorting before ("bch") and after ("ste") the block column.
+        store
+            .put_bytes(DBColumn::BeaconChain.into(), keys[0].as_bytes(), &[42])
+            .unwrap();
+        store
+            .put_bytes(DBColumn::BeaconState.into(), keys[0].as_bytes(), &[42])
+            .unwrap();
+
+        keys.sort();
+        let expected: Vec<Vec<u8>> = keys
+            .iter()
+            .map(|key| [b"blk".as_ref(), key.as_bytes()].concat())
+            .collect();
+
+        let found: Vec<Vec<u8>> = store
+            .iter_keys_by_prefix(DBColumn::BeaconBlock.into())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn diskdb_iter_keys_by_prefix() {
+        let dir = tempdir().unwrap();
+        let store = DiskStore::open(dir.path()).unwrap();
+
+        test_iter_keys_by_prefix(store);
+    }
+
+    #[test]
+    fn memorydb_iter_keys_by_prefix() {
+        test_iter_keys_by_prefix(MemoryStore::open());
+    }
+
+    fn test_blob_sidecars(store: impl Store) {
+        let block_root = Hash256::random();
+        let sidecar = BlobSidecar {
+            index: 1,
+            blob: vec![42; 128].into_boxed_slice(),
+            kzg_commitment: [1; 48],
+            kzg_proof: [2; 48],
+        };
+
+        assert_eq!(store.get_blob_sidecar(&block_root, 1), Ok(None));
+
+        store.put_blob_sidecar(&block_root, &sidecar).unwrap();
+
+        assert_eq!(store.get_blob_sidecar(&block_root, 0), Ok(None));
+        assert_eq!(
+            store.get_blob_sidecar(&block_root, 1),
+            Ok(Some(sidecar.clone()))
+        );
+        assert_eq!(store.get_blob_sidecar(&Hash256::random(), 1), Ok(None));
+
+        let later = BlobSidecar {
+            index: 3,
+            ..sidecar.clone()
+        };
+        store.put_blob_sidecar(&block_root, &later).unwrap();
+        store
+            .put_blob_sidecar(&Hash256::random(), &sidecar)
+            .unwrap();
+
+        assert_eq!(
+            store.get_blob_sidecars(&block_root),
+            Ok(vec![sidecar, later])
+        );
+        assert_eq!(store.get_blob_sidecars(&Hash256::random()), Ok(vec![]));
+    }
+
+    #[test]
+    fn diskdb_blob_sidecars() {
+        let dir = tempdir().unwrap();
+        let store = DiskStore::open(dir.path()).unwrap();
+
+        test_blob_sidecars(store);
+    }
+
+    #[test]
+    fn memorydb_blob_sidecars() {
+        test_blob_sidecars(MemoryStore::open());
+    }
+
+    fn test_batch_put(store: impl Store) {
+        let keys = vec![Hash256::random(), Hash256::random()];
+        let items = vec![StorableThing { a: 1, b: 2 }, StorableThing { a: 3, b: 4 }];
+
+        store
+            .batch_put(
+                keys.iter()
+                    .zip(items.iter())
+                    .map(|(key, item)| item.as_batch_item(key))
+                    .collect(),
+            )
+            .unwrap();
+
+        for (key, item) in keys.iter().zip(items.iter()) {
+            assert_eq!(store.get::<StorableThing>(key), Ok(Some(item.clone())));
+        }
+    }
+
+    #[test]
+    fn diskdb_batch_put() {
+        let dir = tempdir().unwrap();
+        let store = DiskStore::open(dir.path()).unwrap();
+
+        test_batch_put(store);
+    }
+
+    #[test]
+    fn memorydb_batch_put() {
+        test_batch_put(MemoryStore::open());
+    }
+
     #[test]
     fn exists() {
         let store = MemoryStore::open();
@@ -64,4 +64,28 @@ impl Store for MemoryStore {
 
         Ok(())
     }
+
+    /// Puts all `items` in the database whilst holding the write lock once.
+    fn batch_put(&self, items: Vec<(Vec<u8>, Vec<u8>)>) -> Result<(), Error> {
+        self.db.write().extend(items);
+
+        Ok(())
+    }
+
+    /// Iterates, in ascending order, over all keys that start with `prefix`.
+    fn iter_keys_by_prefix<'a>(
+        &'a self,
+        prefix: &[u8],
+    ) -> Box<dyn Iterator<Item = Result<Vec<u8>, Error>> + 'a> {
+        let mut keys: Vec<Vec<u8>> = self
+            .db
+            .read()
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect();
+        keys.sort();
+
+        Box::new(keys.into_iter().map(Ok))
+    }
 }
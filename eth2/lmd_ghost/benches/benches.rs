@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate criterion;
+
+use criterion::black_box;
+use criterion::{Benchmark, Criterion};
+use lmd_ghost::BlockCache;
+use std::sync::Arc;
+use store::{MemoryStore, Store};
+use types::test_utils::{RngCore, SeedableRng, XorShiftRng};
+use types::{BeaconBlock, EthSpec, Hash256, MinimalEthSpec, Slot};
+
+const CHAIN_LENGTH: usize = 10_000;
+const LOOKUPS: usize = 10_000;
+
+/// Stores a chain of `CHAIN_LENGTH` blocks, returning their roots in slot order.
+fn build_chain(store: &MemoryStore) -> Vec<Hash256> {
+    let spec = MinimalEthSpec::default_spec();
+    let mut previous_block_root = Hash256::zero();
+
+    (0..CHAIN_LENGTH)
+        .map(|i| {
+            let mut block = BeaconBlock::empty(&spec);
+            block.slot = Slot::new(i as u64);
+            block.previous_block_root = previous_block_root;
+
+            let root = block.canonical_root();
+            store.put(&root, &block).unwrap();
+            previous_block_root = root;
+            root
+        })
+        .collect()
+}
+
+/// Returns block roots to look up, skewed towards the head of the chain as fork choice is.
+fn lookups(roots: &[Hash256]) -> Vec<Hash256> {
+    let mut rng = XorShiftRng::from_seed([42; 16]);
+
+    (0..LOOKUPS)
+        .map(|_| {
+            let uniform = f64::from(rng.next_u32()) / f64::from(u32::max_value());
+            let distance_from_head = (uniform * uniform * (roots.len() - 1) as f64) as usize;
+            roots[roots.len() - 1 - distance_from_head]
+        })
+        .collect()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let store = Arc::new(MemoryStore::open());
+    let roots = build_chain(&store);
+    let lookups = lookups(&roots);
+
+    for &cache_size in &[128, 1_024, 8_192] {
+        let store = store.clone();
+        let lookups = lookups.clone();
+        c.bench(
+            &format!("block_cache_{}_block_chain", CHAIN_LENGTH),
+            Benchmark::new(format!("cache_size_{}", cache_size), move |b| {
+                b.iter_with_setup(
+                    || BlockCache::new(cache_size),
+                    |cache| {
+                        for root in &lookups {
+                            black_box(cache.get(&*store, *root).unwrap());
+                        }
+                    },
+                )
+            })
+            .sample_size(10),
+        );
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,123 @@
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use store::{Error as StoreError, Store};
+use types::{BeaconBlock, Hash256};
+
+/// The default number of blocks held by a `BlockCache`.
+pub const DEFAULT_BLOCK_CACHE_SIZE: usize = 1_024;
+
+/// A bounded, least-recently-used cache of blocks read from a `Store`.
+///
+/// A `max_size` of zero disables caching.
+pub struct BlockCache {
+    cache: Mutex<Option<LruCache<Hash256, BeaconBlock>>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl BlockCache {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            cache: Mutex::new(new_lru(max_size)),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Empties the cache and bounds it to `max_size` blocks.
+    pub fn set_max_size(&self, max_size: usize) {
+        *self.cache.lock() = new_lru(max_size);
+    }
+
+    /// Returns the block with `block_root`, reading it from `store` if it is not cached.
+    pub fn get<S: Store>(
+        &self,
+        store: &S,
+        block_root: Hash256,
+    ) -> Result<Option<BeaconBlock>, StoreError> {
+        if let Some(cache) = self.cache.lock().as_mut() {
+            if let Some(block) = cache.get(&block_root) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(Some(block.clone()));
+            }
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let block = store.get::<BeaconBlock>(&block_root)?;
+
+        if let (Some(cache), Some(block)) = (self.cache.lock().as_mut(), &block) {
+            cache.put(block_root, block.clone());
+        }
+
+        Ok(block)
+    }
+
+    /// The number of lookups served from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// The number of lookups read from the store.
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+fn new_lru(max_size: usize) -> Option<LruCache<Hash256, BeaconBlock>> {
+    if max_size == 0 {
+        None
+    } else {
+        Some(LruCache::new(max_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use store::MemoryStore;
+    use types::{EthSpec, MinimalEthSpec, Slot};
+
+    fn store_blocks(store: &MemoryStore, n: u64) -> Vec<Hash256> {
+        let spec = MinimalEthSpec::default_spec();
+
+        (0..n)
+            .map(|i| {
+                let mut block = BeaconBlock::empty(&spec);
+                block.slot = Slot::new(i);
+                let root = block.canonical_root();
+                store.put(&root, &block).unwrap();
+                root
+            })
+            .collect()
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let store = MemoryStore::open();
+        let roots = store_blocks(&store, 3);
+        let cache = BlockCache::new(2);
+
+        cache.get(&store, roots[0]).unwrap();
+        cache.get(&store, roots[1]).unwrap();
+        cache.get(&store, roots[0]).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+
+        // Evicts `roots[1]`, the least recently used.
+        cache.get(&store, roots[2]).unwrap();
+        cache.get(&store, roots[0]).unwrap();
+        cache.get(&store, roots[1]).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (2, 4));
+    }
+
+    #[test]
+    fn zero_size_disables_cache() {
+        let store = MemoryStore::open();
+        let roots = store_blocks(&store, 1);
+        let cache = BlockCache::new(0);
+
+        cache.get(&store, roots[0]).unwrap();
+        cache.get(&store, roots[0]).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (0, 2));
+    }
+}
@@ -1,9 +1,11 @@
+mod block_cache;
 mod reduced_tree;
 
 use std::sync::Arc;
 use store::Store;
 use types::{BeaconBlock, EthSpec, Hash256, Slot};
 
+pub use block_cache::{BlockCache, DEFAULT_BLOCK_CACHE_SIZE};
 pub use reduced_tree::ThreadSafeReducedTree;
 
 pub type Result<T> = std::result::Result<T, String>;
@@ -21,7 +23,23 @@ pub trait LmdGhost<S: Store, E: EthSpec>: Send + Sync {
         block_slot: Slot,
     ) -> Result<()>;
 
+    /// Process a batch of `(validator_index, block_hash, block_slot)` attestation messages.
+    ///
+    /// The default implementation calls `process_attestation` for each vote.
+    fn process_attestation_batch(&self, votes: Vec<(usize, Hash256, Slot)>) -> Result<()> {
+        for (validator_index, block_hash, block_slot) in votes {
+            self.process_attestation(validator_index, block_hash, block_slot)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if `block_root` is already a node in the fork choice tree.
+    fn contains_block(&self, block_root: Hash256) -> bool;
+
     /// Process a block that was seen on the network.
+    ///
+    /// Blocks for which `contains_block` is `true` are ignored.
     fn process_block(&self, block: &BeaconBlock, block_hash: Hash256) -> Result<()>;
 
     /// Returns the head of the chain, starting the search at `start_block_root` and moving upwards
@@ -35,12 +53,25 @@ pub trait LmdGhost<S: Store, E: EthSpec>: Send + Sync {
     where
         F: Fn(usize) -> Option<u64> + Copy;
 
+    /// Returns the number of nodes in the tree.
+    fn tree_size(&self) -> usize;
+
+    /// Returns the number of validators with a latest vote.
+    fn vote_count(&self) -> usize;
+
+    /// Bounds any internal cache to `max_cache_size` entries.
+    ///
+    /// The default implementation does nothing, for implementations without a cache.
+    fn set_max_cache_size(&self, _max_cache_size: usize) {}
+
     /// Provide an indication that the blockchain has been finalized at the given `finalized_block`.
     ///
     /// `finalized_block_root` must be the root of `finalized_block`.
+    ///
+    /// Returns the number of nodes pruned from the tree.
     fn update_finalized_root(
         &self,
         finalized_block: &BeaconBlock,
         finalized_block_root: Hash256,
-    ) -> Result<()>;
+    ) -> Result<usize>;
 }
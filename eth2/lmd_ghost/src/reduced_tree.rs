@@ -3,7 +3,7 @@
 //! This algorithm was concieved at IC3 Cornell, 2019.
 //!
 //! This implementation is incomplete and has known bugs. Do not use in production.
-use super::{LmdGhost, Result as SuperResult};
+use super::{BlockCache, LmdGhost, Result as SuperResult, DEFAULT_BLOCK_CACHE_SIZE};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::marker::PhantomData;
@@ -58,8 +58,28 @@ where
             .map_err(|e| format!("process_attestation failed: {:?}", e))
     }
 
+    /// Processes all `votes` whilst holding the write lock once.
+    fn process_attestation_batch(&self, votes: Vec<(usize, Hash256, Slot)>) -> SuperResult<()> {
+        let mut core = self.core.write();
+
+        for (validator_index, block_hash, block_slot) in votes {
+            core.process_message(validator_index, block_hash, block_slot)
+                .map_err(|e| format!("process_attestation_batch failed: {:?}", e))?;
+        }
+
+        Ok(())
+    }
+
+    fn contains_block(&self, block_root: Hash256) -> bool {
+        self.core.read().nodes.contains_key(&block_root)
+    }
+
     /// Process a block that was seen on the network.
     fn process_block(&self, block: &BeaconBlock, block_hash: Hash256) -> SuperResult<()> {
+        if self.contains_block(block_hash) {
+            return Ok(());
+        }
+
         self.core
             .write()
             .add_weightless_node(block.slot, block_hash)
@@ -81,7 +101,24 @@ where
             .map_err(|e| format!("find_head failed: {:?}", e))
     }
 
-    fn update_finalized_root(&self, new_block: &BeaconBlock, new_root: Hash256) -> SuperResult<()> {
+    fn tree_size(&self) -> usize {
+        self.core.read().nodes.len()
+    }
+
+    fn vote_count(&self) -> usize {
+        self.core.read().latest_votes.iter().flatten().count()
+    }
+
+    /// Bounds the cache of blocks read from the store to `max_cache_size` blocks.
+    fn set_max_cache_size(&self, max_cache_size: usize) {
+        self.core.read().block_cache.set_max_size(max_cache_size);
+    }
+
+    fn update_finalized_root(
+        &self,
+        new_block: &BeaconBlock,
+        new_root: Hash256,
+    ) -> SuperResult<usize> {
         self.core
             .write()
             .update_root(new_block.slot, new_root)
@@ -97,6 +134,8 @@ struct ReducedTree<T, E> {
     latest_votes: ElasticList<Option<Vote>>,
     /// Stores the root of the tree, used for pruning.
     root: (Hash256, Slot),
+    /// Caches blocks read from the store when adding nodes and walking ancestors.
+    block_cache: BlockCache,
     _phantom: PhantomData<E>,
 }
 
@@ -122,11 +161,13 @@ where
             nodes,
             latest_votes: ElasticList::default(),
             root: (genesis_root, genesis_block.slot),
+            block_cache: BlockCache::new(DEFAULT_BLOCK_CACHE_SIZE),
             _phantom: PhantomData,
         }
     }
 
-    pub fn update_root(&mut self, new_slot: Slot, new_root: Hash256) -> Result<()> {
+    /// Sets the root of the tree to `new_root`, returning the number of nodes that were pruned.
+    pub fn update_root(&mut self, new_slot: Slot, new_root: Hash256) -> Result<usize> {
         if !self.nodes.contains_key(&new_root) {
             let node = Node {
                 block_hash: new_root,
@@ -137,6 +178,8 @@ where
             self.add_node(node)?;
         }
 
+        let nodes_before_prune = self.nodes.len();
+
         self.retain_subtree(self.root.0, new_root)?;
 
         self.root = (new_root, new_slot);
@@ -144,7 +187,7 @@ where
         let root_node = self.get_mut_node(new_root)?;
         root_node.parent_hash = None;
 
-        Ok(())
+        Ok(nodes_before_prune - self.nodes.len())
     }
 
     /// Removes `current_hash` and all decendants, except `subtree_hash` and all nodes
@@ -554,8 +597,8 @@ where
     }
 
     fn get_block(&self, block_root: Hash256) -> Result<BeaconBlock> {
-        self.store
-            .get::<BeaconBlock>(&block_root)?
+        self.block_cache
+            .get(&*self.store, block_root)?
             .ok_or_else(|| Error::MissingBlock(block_root))
     }
 
@@ -637,6 +680,10 @@ where
         &self.0[i]
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+
     pub fn insert(&mut self, i: usize, element: T) {
         self.ensure(i);
         self.0[i] = element;
@@ -648,3 +695,51 @@ impl From<Error> for String {
         format!("{:?}", e)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use store::MemoryStore;
+    use types::MinimalEthSpec;
+
+    #[test]
+    fn process_block_ignores_duplicates() {
+        let spec = MinimalEthSpec::default_spec();
+        let store = Arc::new(MemoryStore::open());
+        let genesis_block = BeaconBlock::empty(&spec);
+        let genesis_root = Hash256::from_slice(&[1; 32]);
+
+        let tree: ThreadSafeReducedTree<MemoryStore, MinimalEthSpec> =
+            ThreadSafeReducedTree::new(store, &genesis_block, genesis_root);
+
+        assert!(tree.contains_block(genesis_root));
+        assert!(!tree.contains_block(Hash256::from_slice(&[2; 32])));
+
+        for _ in 0..2 {
+            tree.process_block(&genesis_block, genesis_root)
+                .expect("should ignore known block");
+            assert_eq!(tree.core.read().nodes.len(), 1);
+        }
+    }
+
+    #[test]
+    fn tree_size_and_vote_count() {
+        let spec = MinimalEthSpec::default_spec();
+        let store = Arc::new(MemoryStore::open());
+        let genesis_block = BeaconBlock::empty(&spec);
+        let genesis_root = Hash256::from_slice(&[1; 32]);
+
+        let tree: ThreadSafeReducedTree<MemoryStore, MinimalEthSpec> =
+            ThreadSafeReducedTree::new(store, &genesis_block, genesis_root);
+
+        assert_eq!(tree.tree_size(), 1);
+        assert_eq!(tree.vote_count(), 0);
+
+        tree.process_attestation(3, genesis_root, genesis_block.slot)
+            .expect("should process attestation");
+        tree.process_attestation(3, genesis_root, genesis_block.slot)
+            .expect("should process attestation");
+
+        assert_eq!(tree.vote_count(), 1);
+    }
+}
@@ -3,10 +3,10 @@ mod attestation_id;
 mod max_cover;
 mod persistence;
 
+pub use attestation_id::AttestationId;
 pub use persistence::PersistedOperationPool;
 
 use attestation::{earliest_attestation_validators, AttMaxCover};
-use attestation_id::AttestationId;
 use itertools::Itertools;
 use max_cover::maximum_cover;
 use parking_lot::RwLock;
@@ -16,8 +16,9 @@ use state_processing::per_block_processing::errors::{
 };
 use state_processing::per_block_processing::{
     get_slashable_indices_modular, validate_attestation,
-    validate_attestation_time_independent_only, verify_attester_slashing, verify_exit,
-    verify_exit_time_independent_only, verify_proposer_slashing, verify_transfer,
+    validate_attestation_time_independent_only,
+    validate_attestation_time_independent_only_without_signature, verify_attester_slashing,
+    verify_exit, verify_exit_time_independent_only, verify_proposer_slashing, verify_transfer,
     verify_transfer_time_independent_only,
 };
 use std::collections::{btree_map::Entry, hash_map, BTreeMap, HashMap, HashSet};
@@ -74,6 +75,34 @@ impl<T: EthSpec> OperationPool<T> {
         // Check that attestation signatures are valid.
         validate_attestation_time_independent_only(state, &attestation, spec)?;
 
+        self.aggregate_attestation(attestation, state, spec);
+
+        Ok(())
+    }
+
+    /// Like `insert_attestation`, but without verifying the aggregate signature. Only for
+    /// attestations whose signature the caller has already verified.
+    pub fn insert_verified_attestation(
+        &self,
+        attestation: Attestation,
+        state: &BeaconState<T>,
+        spec: &ChainSpec,
+    ) -> Result<(), AttestationValidationError> {
+        validate_attestation_time_independent_only_without_signature(state, &attestation, spec)?;
+
+        self.aggregate_attestation(attestation, state, spec);
+
+        Ok(())
+    }
+
+    /// Adds a validated attestation to the pool, aggregating it with existing attestations if
+    /// possible.
+    fn aggregate_attestation(
+        &self,
+        attestation: Attestation,
+        state: &BeaconState<T>,
+        spec: &ChainSpec,
+    ) {
         let id = AttestationId::from_data(&attestation.data, state, spec);
 
         // Take a write lock on the attestations map.
@@ -82,7 +111,7 @@ impl<T: EthSpec> OperationPool<T> {
         let existing_attestations = match attestations.entry(id) {
             hash_map::Entry::Vacant(entry) => {
                 entry.insert(vec![attestation]);
-                return Ok(());
+                return;
             }
             hash_map::Entry::Occupied(entry) => entry.into_mut(),
         };
@@ -100,8 +129,6 @@ impl<T: EthSpec> OperationPool<T> {
         if !aggregated {
             existing_attestations.push(attestation);
         }
-
-        Ok(())
     }
 
     /// Total number of attestations in the pool, including attestations for the same data.
@@ -109,6 +136,16 @@ impl<T: EthSpec> OperationPool<T> {
         self.attestations.read().values().map(Vec::len).sum()
     }
 
+    /// Returns `true` if the pool holds attestations with the given `id`.
+    pub fn contains(&self, id: &AttestationId) -> bool {
+        self.attestations.read().contains_key(id)
+    }
+
+    /// Remove all attestations with the given `id`, returning `true` if there were any.
+    pub fn remove_attestation(&self, id: &AttestationId) -> bool {
+        self.attestations.write().remove(id).is_some()
+    }
+
     /// Get a list of attestations for inclusion in a block.
     pub fn get_attestations(&self, state: &BeaconState<T>, spec: &ChainSpec) -> Vec<Attestation> {
         // Attestations for the current fork, which may be from the current or previous epoch.
@@ -196,6 +233,11 @@ impl<T: EthSpec> OperationPool<T> {
         self.deposits.read().len()
     }
 
+    /// Remove the deposit with the given index, if any.
+    pub fn remove_deposit(&self, index: u64) -> Option<Deposit> {
+        self.deposits.write().remove(&index)
+    }
+
     /// Insert a proposer slashing into the pool.
     pub fn insert_proposer_slashing(
         &self,
@@ -298,6 +340,14 @@ impl<T: EthSpec> OperationPool<T> {
         (proposer_slashings, attester_slashings)
     }
 
+    /// Get all proposer and attester slashings in the pool, without filtering for inclusion.
+    pub fn get_all_slashings(&self) -> (Vec<ProposerSlashing>, Vec<AttesterSlashing>) {
+        let proposer_slashings = self.proposer_slashings.read().values().cloned().collect();
+        let attester_slashings = self.attester_slashings.read().values().cloned().collect();
+
+        (proposer_slashings, attester_slashings)
+    }
+
     /// Prune proposer slashings for all slashed or withdrawn validators.
     pub fn prune_proposer_slashings(&self, finalized_state: &BeaconState<T>) {
         prune_validator_hash_map(
@@ -338,6 +388,19 @@ impl<T: EthSpec> OperationPool<T> {
         Ok(())
     }
 
+    /// Like `insert_voluntary_exit`, but without any verification. Only for exits the caller has
+    /// already verified with `verify_exit`.
+    pub fn insert_verified_voluntary_exit(&self, exit: VoluntaryExit) {
+        self.voluntary_exits
+            .write()
+            .insert(exit.validator_index, exit);
+    }
+
+    /// Returns `true` if the pool holds a voluntary exit for `validator_index`.
+    pub fn has_voluntary_exit(&self, validator_index: u64) -> bool {
+        self.voluntary_exits.read().contains_key(&validator_index)
+    }
+
     /// Get a list of voluntary exits for inclusion in a block.
     pub fn get_voluntary_exits(
         &self,
@@ -789,6 +852,47 @@ mod tests {
             assert_eq!(op_pool.num_attestations(), committees.len());
         }
 
+        /// Removing an attestation should remove it from the pool, and only once.
+        #[test]
+        fn attestation_remove() {
+            let (ref mut state, ref keypairs, ref spec) =
+                attestation_test_state::<MainnetEthSpec>(1);
+
+            let op_pool = OperationPool::new();
+
+            let slot = state.slot - 1;
+            let committees = state
+                .get_crosslink_committees_at_slot(slot)
+                .unwrap()
+                .into_iter()
+                .map(CrosslinkCommittee::into_owned)
+                .collect::<Vec<_>>();
+
+            for cc in &committees {
+                let att = signed_attestation(
+                    &cc.committee,
+                    cc.shard,
+                    keypairs,
+                    ..,
+                    slot,
+                    state,
+                    spec,
+                    None,
+                );
+                let id = AttestationId::from_data(&att.data, state, spec);
+
+                assert!(!op_pool.contains(&id));
+                op_pool.insert_attestation(att, state, spec).unwrap();
+                assert!(op_pool.contains(&id));
+
+                assert!(op_pool.remove_attestation(&id));
+                assert!(!op_pool.contains(&id));
+                assert!(!op_pool.remove_attestation(&id));
+            }
+
+            assert_eq!(op_pool.num_attestations(), 0);
+        }
+
         /// Adding lots of attestations that only intersect pairwise should lead to two aggregate
         /// attestations.
         #[test]
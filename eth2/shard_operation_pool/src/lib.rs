@@ -2,6 +2,7 @@ mod attestation_id;
 
 use attestation_id::AttestationId;
 use parking_lot::RwLock;
+use serde_derive::Serialize;
 use std::collections::{hash_map, HashMap};
 use std::marker::PhantomData;
 use types::{BeaconState, ChainSpec, EthSpec, ShardAttestation, ShardSlot, ShardSpec, ShardState};
@@ -13,6 +14,19 @@ pub struct OperationPool<T: ShardSpec + Default> {
     _phantom: PhantomData<T>,
 }
 
+/// A snapshot of the contents of an `OperationPool`.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct OperationPoolStats {
+    /// Total number of attestations, including attestations for the same data.
+    pub total_attestations: usize,
+    /// Number of distinct attestation data (i.e., `AttestationId`s).
+    pub unique_data_ids: usize,
+    /// Mean number of signers per attestation, or `0.0` if the pool is empty.
+    pub average_aggregate_size: f64,
+    /// Length of the pending block body, in bytes.
+    pub body_len: usize,
+}
+
 impl<T: ShardSpec> OperationPool<T> {
     /// Create a new operation pool.
     pub fn new() -> Self {
@@ -20,12 +34,20 @@ impl<T: ShardSpec> OperationPool<T> {
     }
 
     /// Insert an attestation into the pool, aggregating it with existing attestations if possible.
+    ///
+    /// Returns `false`, leaving the pool unchanged, if the aggregation bitfield does not fit a
+    /// committee of `committee_size`.
     pub fn insert_attestation<U: EthSpec>(
         &self,
         attestation: ShardAttestation,
+        committee_size: usize,
         beacon_state: &BeaconState<U>,
         spec: &ChainSpec,
-    ) -> () {
+    ) -> bool {
+        if !attestation.committee_bitfield_is_valid(committee_size) {
+            return false;
+        }
+
         let id = AttestationId::from_data(&attestation.data, beacon_state, spec);
 
         // Take a write lock on the attestations map.
@@ -34,7 +56,7 @@ impl<T: ShardSpec> OperationPool<T> {
         let existing_attestations = match attestations.entry(id) {
             hash_map::Entry::Vacant(entry) => {
                 entry.insert(vec![attestation]);
-                return ();
+                return true;
             }
             hash_map::Entry::Occupied(entry) => entry.into_mut(),
         };
@@ -53,7 +75,7 @@ impl<T: ShardSpec> OperationPool<T> {
             existing_attestations.push(attestation);
         }
 
-        ()
+        true
     }
 
     /// Total number of attestations in the pool, including attestations for the same data.
@@ -61,6 +83,34 @@ impl<T: ShardSpec> OperationPool<T> {
         self.attestations.read().values().map(Vec::len).sum()
     }
 
+    /// Returns statistics about the pool, computed under a single read lock on the attestations.
+    pub fn stats(&self) -> OperationPoolStats {
+        let attestations = self.attestations.read();
+
+        let unique_data_ids = attestations.len();
+        let mut total_attestations = 0;
+        let mut total_signers = 0;
+        for attestation in attestations.values().flatten() {
+            total_attestations += 1;
+            total_signers += attestation.aggregation_bitfield.num_set_bits();
+        }
+
+        drop(attestations);
+
+        let average_aggregate_size = if total_attestations == 0 {
+            0.0
+        } else {
+            total_signers as f64 / total_attestations as f64
+        };
+
+        OperationPoolStats {
+            total_attestations,
+            unique_data_ids,
+            average_aggregate_size,
+            body_len: self.body.read().len(),
+        }
+    }
+
     /// Get attestation with most attesters for inclusion in a block
     pub fn get_attestation<U: EthSpec>(
         &self,
@@ -123,3 +173,103 @@ impl<T: ShardSpec + Default> PartialEq for OperationPool<T> {
         *self.attestations.read() == *other.attestations.read()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use types::test_utils::*;
+    use types::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn operation_pool_is_send_and_sync() {
+        assert_send_sync::<OperationPool<MainnetShardSpec>>();
+        assert_send_sync::<OperationPool<MinimalShardSpec>>();
+    }
+
+    #[test]
+    fn concurrent_insert_get_and_prune() {
+        let spec = MinimalEthSpec::default_spec();
+        let (beacon_state, _keypairs) =
+            TestingBeaconStateBuilder::<MinimalEthSpec>::from_deterministic_keypairs(8, &spec)
+                .build();
+        let shard_state = ShardState::<MinimalShardSpec>::genesis(&spec, 0);
+
+        let op_pool = Arc::new(OperationPool::<MinimalShardSpec>::new());
+        let beacon_state = Arc::new(beacon_state);
+        let shard_state = Arc::new(shard_state);
+        let spec = Arc::new(spec);
+
+        let inserters = (0..32u8).map(|i| {
+            let (op_pool, beacon_state, spec) =
+                (op_pool.clone(), beacon_state.clone(), spec.clone());
+            thread::spawn(move || {
+                let rng = &mut XorShiftRng::from_seed([i; 16]);
+                for _ in 0..100 {
+                    // Random bitfields are 32 bytes long.
+                    let attestation = ShardAttestation::random_for_test(rng);
+                    assert!(op_pool.insert_attestation(attestation, 256, &beacon_state, &spec));
+                }
+            })
+        });
+
+        let getters = (0..4).map(|_| {
+            let (op_pool, beacon_state, shard_state, spec) = (
+                op_pool.clone(),
+                beacon_state.clone(),
+                shard_state.clone(),
+                spec.clone(),
+            );
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    assert!(
+                        op_pool
+                            .get_attestation(&shard_state, &beacon_state, &spec)
+                            .len()
+                            <= 1
+                    );
+                }
+            })
+        });
+
+        let pruners = (0..2).map(|_| {
+            let (op_pool, shard_state) = (op_pool.clone(), shard_state.clone());
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    op_pool.prune_attestations(&shard_state);
+                }
+            })
+        });
+
+        let handles: Vec<_> = inserters.chain(getters).chain(pruners).collect();
+        for handle in handles {
+            handle.join().expect("no thread should panic");
+        }
+
+        // Every remaining attestation must be accounted for in a single, consistent snapshot.
+        let stats = op_pool.stats();
+        assert_eq!(stats.total_attestations, op_pool.num_attestations());
+        assert!(stats.unique_data_ids <= stats.total_attestations);
+    }
+
+    #[test]
+    fn insert_rejects_malformed_bitfield() {
+        let spec = MinimalEthSpec::default_spec();
+        let (beacon_state, _keypairs) =
+            TestingBeaconStateBuilder::<MinimalEthSpec>::from_deterministic_keypairs(8, &spec)
+                .build();
+        let op_pool = OperationPool::<MinimalShardSpec>::new();
+
+        let mut attestation = ShardAttestation::default();
+        attestation.aggregation_bitfield = Bitfield::from_bytes(&[0b0001_0001]);
+
+        assert!(!op_pool.insert_attestation(attestation.clone(), 4, &beacon_state, &spec));
+        assert_eq!(op_pool.num_attestations(), 0);
+
+        assert!(op_pool.insert_attestation(attestation, 5, &beacon_state, &spec));
+        assert_eq!(op_pool.num_attestations(), 1);
+    }
+}
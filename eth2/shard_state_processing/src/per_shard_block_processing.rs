@@ -10,12 +10,33 @@ pub fn per_shard_block_processing<T: ShardSpec, U: EthSpec>(
     block: &ShardBlock,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
+    if !block.body_size_is_valid(spec) {
+        return Err(Error::BodyTooLarge {
+            body_size: block.body.len(),
+            max_body_size: spec.max_shard_block_body_size,
+        });
+    }
+
     process_shard_block_header(beacon_state, state, block, spec);
     // process_shard_attestations(state, beacon_state, block);
     // process_shard_block_data_fees(state, beacon_state, block);
     Ok(())
 }
 
+/// Returns the result of applying `block` to a copy of `state`, leaving `state` unchanged.
+#[must_use]
+pub fn apply_shard_block<T: ShardSpec, U: EthSpec>(
+    beacon_state: &BeaconState<U>,
+    state: &ShardState<T>,
+    block: &ShardBlock,
+    spec: &ChainSpec,
+) -> Result<ShardState<T>, Error> {
+    let mut new_state = state.clone();
+    per_shard_block_processing(beacon_state, &mut new_state, block, spec)?;
+
+    Ok(new_state)
+}
+
 pub fn process_shard_block_header<T: ShardSpec, U: EthSpec>(
     beacon_state: &BeaconState<U>,
     state: &mut ShardState<T>,
@@ -127,3 +148,31 @@ pub fn process_shard_block_data_fees<T: ShardSpec, U: EthSpec>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_shard_block_does_not_modify_state() {
+        let spec = MinimalEthSpec::default_spec();
+        let beacon_state: BeaconState<MinimalEthSpec> =
+            BeaconState::genesis(0, Eth1Data::default(), &spec);
+        let state: ShardState<MinimalShardSpec> = ShardState::genesis(&spec, 0);
+        let original = state.clone();
+
+        let mut block = ShardBlock::empty(&spec, 0);
+        block.slot += 1;
+        block.body = vec![42; 32];
+
+        let new_state =
+            apply_shard_block(&beacon_state, &state, &block, &spec).expect("should apply block");
+
+        assert_eq!(state, original);
+        assert_eq!(
+            new_state.latest_block_header,
+            block.temporary_block_header(&spec)
+        );
+        assert_ne!(new_state, state);
+    }
+}
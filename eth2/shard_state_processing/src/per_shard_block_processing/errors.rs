@@ -3,4 +3,9 @@ use types::*;
 #[derive(Debug, PartialEq)]
 pub enum Error {
     BlockProcessingError,
+    /// The block body is larger than `spec.max_shard_block_body_size`.
+    BodyTooLarge {
+        body_size: usize,
+        max_body_size: u64,
+    },
 }
@@ -10,6 +10,14 @@ pub fn per_shard_slot_processing<T: ShardSpec>(
     state: &mut ShardState<T>,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
+    let phase_1_fork_slot = ShardSlot::from(spec.phase_1_fork_slot);
+    if state.slot < phase_1_fork_slot {
+        return Err(Error::StateSlotTooLow {
+            state_slot: state.slot,
+            phase_1_fork_slot,
+        });
+    }
+
     if (state
         .slot
         .epoch(spec.slots_per_epoch, spec.shard_slots_per_beacon_slot)
@@ -26,3 +34,71 @@ pub fn per_shard_slot_processing<T: ShardSpec>(
 
     Ok(())
 }
+
+/// Advances `state` to `target_slot` by repeated `per_shard_slot_processing`, skipping any
+/// empty slots in between.
+pub fn transition_to_slot<T: ShardSpec>(
+    state: &mut ShardState<T>,
+    target_slot: ShardSlot,
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    if target_slot < state.slot {
+        return Err(Error::StateSlotTooHigh {
+            state_slot: state.slot,
+            target_slot,
+        });
+    }
+
+    while state.slot < target_slot {
+        per_shard_slot_processing(state, spec)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_hash::TreeHash;
+
+    #[test]
+    fn transition_to_slot_updates_every_slot() {
+        let spec = MinimalEthSpec::default_spec();
+        let mut state: ShardState<MinimalShardSpec> = ShardState::genesis(&spec, 0);
+        let target_slot = state.slot + 100;
+
+        let mut expected = state.clone();
+        while expected.slot < target_slot {
+            let previous_root = Hash256::from_slice(&expected.tree_hash_root());
+            per_shard_slot_processing(&mut expected, &spec).unwrap();
+            assert_eq!(expected.history_accumulator[0], previous_root);
+        }
+
+        transition_to_slot(&mut state, target_slot, &spec).unwrap();
+
+        assert_eq!(state.slot, target_slot);
+        assert_eq!(state, expected);
+        assert_eq!(
+            transition_to_slot(&mut state, target_slot - 1, &spec),
+            Err(Error::StateSlotTooHigh {
+                state_slot: target_slot,
+                target_slot: target_slot - 1,
+            })
+        );
+    }
+
+    #[test]
+    fn per_shard_slot_processing_rejects_pre_fork_state() {
+        let spec = MinimalEthSpec::default_spec();
+        let mut state: ShardState<MinimalShardSpec> = ShardState::genesis(&spec, 0);
+        state.slot = ShardSlot::from(spec.phase_1_fork_slot) - 1;
+
+        assert_eq!(
+            per_shard_slot_processing(&mut state, &spec),
+            Err(Error::StateSlotTooLow {
+                state_slot: state.slot,
+                phase_1_fork_slot: ShardSlot::from(spec.phase_1_fork_slot),
+            })
+        );
+    }
+}
@@ -2,5 +2,33 @@ use types::*;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
-    ShardStateError,
+    /// The state is from before the phase 1 fork, so has no shard slots to process.
+    StateSlotTooLow {
+        state_slot: ShardSlot,
+        phase_1_fork_slot: ShardSlot,
+    },
+    /// The state has already advanced beyond the requested slot.
+    StateSlotTooHigh {
+        state_slot: ShardSlot,
+        target_slot: ShardSlot,
+    },
+    /// Period processing required the committees of a period that have not been computed.
+    PeriodCacheNotInitialized {
+        period: Period,
+    },
+    /// Computing the shard committees failed.
+    CommitteeError(BeaconStateError),
+    ShardStateError(ShardStateError),
+}
+
+impl From<BeaconStateError> for Error {
+    fn from(e: BeaconStateError) -> Error {
+        Error::CommitteeError(e)
+    }
+}
+
+impl From<ShardStateError> for Error {
+    fn from(e: ShardStateError) -> Error {
+        Error::ShardStateError(e)
+    }
 }
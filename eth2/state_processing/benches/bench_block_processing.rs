@@ -5,7 +5,7 @@ use state_processing::{
     per_block_processing::{
         process_attestations, process_attester_slashings, process_deposits, process_eth1_data,
         process_exits, process_proposer_slashings, process_randao, process_transfers,
-        verify_block_signature,
+        verify_block_signature, verify_block_signatures_batch,
     },
 };
 use tree_hash::TreeHash;
@@ -39,6 +39,33 @@ pub fn bench_block_processing(
         .sample_size(10),
     );
 
+    let state = initial_state.clone();
+    let block = initial_block.clone();
+    let spec = initial_spec.clone();
+    c.bench(
+        &format!("{}/block_processing", desc),
+        Benchmark::new("verify_block_signature_100_single", move |b| {
+            b.iter(|| {
+                for _ in 0..100 {
+                    verify_block_signature(&state, &block, &spec).unwrap();
+                }
+            })
+        })
+        .sample_size(10),
+    );
+
+    let state = initial_state.clone();
+    let block = initial_block.clone();
+    let spec = initial_spec.clone();
+    c.bench(
+        &format!("{}/block_processing", desc),
+        Benchmark::new("verify_block_signature_100_batch", move |b| {
+            let blocks: Vec<_> = (0..100).map(|_| (block.clone(), &state)).collect();
+            b.iter(|| verify_block_signatures_batch(black_box(&blocks), &spec).unwrap())
+        })
+        .sample_size(10),
+    );
+
     let state = initial_state.clone();
     let block = initial_block.clone();
     let spec = initial_spec.clone();
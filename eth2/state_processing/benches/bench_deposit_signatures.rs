@@ -0,0 +1,56 @@
+use criterion::Criterion;
+use criterion::{black_box, Benchmark};
+use state_processing::per_block_processing::{
+    verify_deposit_signature, verify_deposit_signatures_batch,
+};
+use types::test_utils::{
+    generate_deterministic_keypairs, TestingBeaconStateBuilder, TestingDepositBuilder,
+};
+use types::*;
+
+/// Compare batch and sequential verification of `deposit_count` deposit signatures.
+pub fn bench_deposit_signatures(c: &mut Criterion, deposit_count: usize) {
+    let spec = MainnetEthSpec::default_spec();
+    let (state, _keypairs) =
+        TestingBeaconStateBuilder::<MainnetEthSpec>::from_deterministic_keypairs(16, &spec).build();
+
+    let deposits: Vec<Deposit> = generate_deterministic_keypairs(deposit_count)
+        .iter()
+        .map(|keypair| {
+            let mut builder =
+                TestingDepositBuilder::new(keypair.pk.clone(), spec.max_effective_balance);
+            builder.sign(keypair, state.current_epoch(), &state.fork, &spec);
+            builder.build()
+        })
+        .collect();
+
+    let batch_state = state.clone();
+    let batch_deposits = deposits.clone();
+    let batch_spec = spec.clone();
+    c.bench(
+        &format!("{}_deposits", deposit_count),
+        Benchmark::new("verify_deposit_signatures_batch", move |b| {
+            let deposits: Vec<&Deposit> = batch_deposits.iter().collect();
+            b.iter(|| {
+                black_box(verify_deposit_signatures_batch(
+                    &batch_state,
+                    &deposits,
+                    &batch_spec,
+                ))
+            })
+        })
+        .sample_size(10),
+    );
+
+    c.bench(
+        &format!("{}_deposits", deposit_count),
+        Benchmark::new("verify_deposit_signature_sequential", move |b| {
+            b.iter(|| {
+                for deposit in &deposits {
+                    black_box(verify_deposit_signature(&state, deposit, &spec).is_ok());
+                }
+            })
+        })
+        .sample_size(10),
+    );
+}
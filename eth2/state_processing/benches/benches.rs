@@ -6,6 +6,7 @@ use log::info;
 use types::*;
 
 mod bench_block_processing;
+mod bench_deposit_signatures;
 mod bench_epoch_processing;
 mod block_benching_builder;
 
@@ -94,10 +95,17 @@ pub fn state_processing(c: &mut Criterion) {
     bench_epoch_processing::bench_epoch_processing_n_validators(c, VALIDATOR_COUNT);
 }
 
+pub fn deposit_signatures(c: &mut Criterion) {
+    for &deposit_count in &[100, 1_000] {
+        bench_deposit_signatures::bench_deposit_signatures(c, deposit_count);
+    }
+}
+
 criterion_group!(
     benches,
     block_processing_reasonable_case,
     block_processing_worst_case,
-    state_processing
+    state_processing,
+    deposit_signatures
 );
 criterion_main!(benches);
@@ -1,5 +1,4 @@
-use super::{get_attesting_indices, get_attesting_indices_unsorted};
-use itertools::{Either, Itertools};
+use super::get_attesting_indices;
 use types::*;
 
 /// Convert `attestation` to (almost) indexed-verifiable form.
@@ -11,22 +10,21 @@ pub fn convert_to_indexed<T: EthSpec>(
 ) -> Result<IndexedAttestation, BeaconStateError> {
     let attesting_indices =
         get_attesting_indices(state, &attestation.data, &attestation.aggregation_bitfield)?;
+    let custody_bit_1_indices =
+        get_attesting_indices(state, &attestation.data, &attestation.custody_bitfield)?;
 
-    // We verify the custody bitfield by calling `get_attesting_indices_unsorted` and throwing
-    // away the result. This avoids double-sorting - the partition below takes care of the ordering.
-    get_attesting_indices_unsorted(state, &attestation.data, &attestation.custody_bitfield)?;
-
-    let (custody_bit_0_indices, custody_bit_1_indices) =
-        attesting_indices.into_iter().enumerate().partition_map(
-            |(committee_idx, validator_idx)| match attestation.custody_bitfield.get(committee_idx) {
-                Ok(true) => Either::Right(validator_idx as u64),
-                _ => Either::Left(validator_idx as u64),
-            },
-        );
+    let custody_bit_0_indices = attesting_indices
+        .into_iter()
+        .filter(|index| custody_bit_1_indices.binary_search(index).is_err())
+        .map(|index| index as u64)
+        .collect();
 
     Ok(IndexedAttestation {
         custody_bit_0_indices,
-        custody_bit_1_indices,
+        custody_bit_1_indices: custody_bit_1_indices
+            .into_iter()
+            .map(|index| index as u64)
+            .collect(),
         data: attestation.data.clone(),
         signature: attestation.signature.clone(),
     })
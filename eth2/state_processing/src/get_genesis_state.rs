@@ -1,8 +1,12 @@
 use super::per_block_processing::{errors::BlockProcessingError, process_deposits};
+use std::collections::HashSet;
 use tree_hash::TreeHash;
 use types::*;
 
+#[derive(Debug, PartialEq)]
 pub enum GenesisError {
+    /// Two genesis deposits share a pubkey. Holds the index of the second occurrence.
+    DuplicatePubkey(usize),
     BlockProcessingError(BlockProcessingError),
     BeaconStateError(BeaconStateError),
 }
@@ -15,7 +19,10 @@ pub fn get_genesis_beacon_state<T: EthSpec>(
     genesis_time: u64,
     genesis_eth1_data: Eth1Data,
     spec: &ChainSpec,
-) -> Result<BeaconState<T>, BlockProcessingError> {
+) -> Result<BeaconState<T>, GenesisError> {
+    // A repeated pubkey would silently be processed as a top-up, so reject it outright.
+    verify_unique_deposit_pubkeys(genesis_validator_deposits)?;
+
     // Get the genesis `BeaconState`
     let mut state = BeaconState::genesis(genesis_time, genesis_eth1_data, spec);
 
@@ -43,6 +50,19 @@ pub fn get_genesis_beacon_state<T: EthSpec>(
     Ok(state)
 }
 
+/// Returns an error if any two deposits in `deposits` share a pubkey.
+fn verify_unique_deposit_pubkeys(deposits: &[Deposit]) -> Result<(), GenesisError> {
+    let mut pubkeys = HashSet::with_capacity(deposits.len());
+
+    for (index, deposit) in deposits.iter().enumerate() {
+        if !pubkeys.insert(&deposit.data.pubkey) {
+            return Err(GenesisError::DuplicatePubkey(index));
+        }
+    }
+
+    Ok(())
+}
+
 impl From<BlockProcessingError> for GenesisError {
     fn from(e: BlockProcessingError) -> GenesisError {
         GenesisError::BlockProcessingError(e)
@@ -54,3 +74,27 @@ impl From<BeaconStateError> for GenesisError {
         GenesisError::BeaconStateError(e)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::test_utils::{generate_deterministic_keypair, TestingDepositBuilder};
+
+    fn deposit_for(validator_index: usize) -> Deposit {
+        let keypair = generate_deterministic_keypair(validator_index);
+        TestingDepositBuilder::new(keypair.pk, 32_000_000_000).build()
+    }
+
+    #[test]
+    fn rejects_duplicate_pubkeys() {
+        let spec = MinimalEthSpec::default_spec();
+        let deposits = vec![deposit_for(0), deposit_for(1), deposit_for(0)];
+
+        assert_eq!(verify_unique_deposit_pubkeys(&deposits[..2]), Ok(()));
+        assert_eq!(
+            get_genesis_beacon_state::<MinimalEthSpec>(&deposits, 0, Eth1Data::default(), &spec)
+                .err(),
+            Some(GenesisError::DuplicatePubkey(2))
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::common::{initiate_validator_exit, slash_validator};
 use errors::{BlockInvalid as Invalid, BlockProcessingError as Error, IntoWithIndex};
 use rayon::prelude::*;
+use std::collections::HashSet;
 use tree_hash::{SignedRoot, TreeHash};
 use types::*;
 
@@ -10,11 +11,13 @@ pub use self::verify_attester_slashing::{
 pub use self::verify_proposer_slashing::verify_proposer_slashing;
 pub use validate_attestation::{
     validate_attestation, validate_attestation_time_independent_only,
+    validate_attestation_time_independent_only_without_signature,
     validate_attestation_without_signature,
 };
+pub use verify_bls_to_execution_change::verify_bls_to_execution_change;
 pub use verify_deposit::{
     get_existing_validator_index, verify_deposit_index, verify_deposit_merkle_proof,
-    verify_deposit_signature,
+    verify_deposit_signature, verify_deposit_signatures_batch,
 };
 pub use verify_exit::{verify_exit, verify_exit_time_independent_only};
 pub use verify_indexed_attestation::{
@@ -29,6 +32,7 @@ pub mod errors;
 pub mod tests;
 mod validate_attestation;
 mod verify_attester_slashing;
+mod verify_bls_to_execution_change;
 mod verify_deposit;
 mod verify_exit;
 mod verify_indexed_attestation;
@@ -46,7 +50,7 @@ pub fn per_block_processing<T: EthSpec>(
     block: &BeaconBlock,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
-    per_block_processing_signature_optional(state, block, true, spec)
+    per_block_processing_signature_optional(state, block, true, true, spec)
 }
 
 /// Updates the state for a new block, whilst validating that the block is valid, without actually
@@ -61,11 +65,26 @@ pub fn per_block_processing_without_verifying_block_signature<T: EthSpec>(
     block: &BeaconBlock,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
-    per_block_processing_signature_optional(state, block, false, spec)
+    per_block_processing_signature_optional(state, block, false, true, spec)
+}
+
+/// Updates the state for a block from a trusted source (e.g., when replaying historic blocks),
+/// checking neither the block proposer signature nor the deposit Merkle proofs.
+///
+/// Returns `Ok(())` if the block is valid and the state was successfully updated. Otherwise
+/// returns an error describing why the block was invalid or how the function failed to execute.
+///
+/// Spec v0.6.3
+pub fn per_block_processing_for_replay<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    block: &BeaconBlock,
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    per_block_processing_signature_optional(state, block, false, false, spec)
 }
 
 /// Updates the state for a new block, whilst validating that the block is valid, optionally
-/// checking the block proposer signature.
+/// checking the block proposer signature and the deposit Merkle proofs.
 ///
 /// Returns `Ok(())` if the block is valid and the state was successfully updated. Otherwise
 /// returns an error describing why the block was invalid or how the function failed to execute.
@@ -75,22 +94,40 @@ fn per_block_processing_signature_optional<T: EthSpec>(
     mut state: &mut BeaconState<T>,
     block: &BeaconBlock,
     should_verify_block_signature: bool,
+    should_verify_deposit_proofs: bool,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
-    process_block_header(state, block, spec, should_verify_block_signature)?;
+    process_block_header(state, block, spec, should_verify_block_signature)
+        .map_err(|e| e.context("process_block_header"))?;
 
     // Ensure the current and previous epoch caches are built.
-    state.build_committee_cache(RelativeEpoch::Previous, spec)?;
-    state.build_committee_cache(RelativeEpoch::Current, spec)?;
-
-    process_randao(&mut state, &block, &spec)?;
-    process_eth1_data(&mut state, &block.body.eth1_data, spec)?;
-    process_proposer_slashings(&mut state, &block.body.proposer_slashings, spec)?;
-    process_attester_slashings(&mut state, &block.body.attester_slashings, spec)?;
-    process_attestations(&mut state, &block.body.attestations, spec)?;
-    process_deposits(&mut state, &block.body.deposits, spec)?;
-    process_exits(&mut state, &block.body.voluntary_exits, spec)?;
-    process_transfers(&mut state, &block.body.transfers, spec)?;
+    state
+        .build_committee_cache(RelativeEpoch::Previous, spec)
+        .map_err(|e| Error::from(e).context("build_committee_cache"))?;
+    state
+        .build_committee_cache(RelativeEpoch::Current, spec)
+        .map_err(|e| Error::from(e).context("build_committee_cache"))?;
+
+    process_randao(&mut state, &block, &spec).map_err(|e| e.context("process_randao"))?;
+    process_eth1_data(&mut state, &block.body.eth1_data, spec)
+        .map_err(|e| e.context("process_eth1_data"))?;
+    process_proposer_slashings(&mut state, &block.body.proposer_slashings, spec)
+        .map_err(|e| e.context("process_proposer_slashings"))?;
+    process_attester_slashings(&mut state, &block.body.attester_slashings, spec)
+        .map_err(|e| e.context("process_attester_slashings"))?;
+    process_attestations(&mut state, &block.body.attestations, spec)
+        .map_err(|e| e.context("process_attestations"))?;
+    process_deposits_proof_optional(
+        &mut state,
+        &block.body.deposits,
+        should_verify_deposit_proofs,
+        spec,
+    )
+    .map_err(|e| e.context("process_deposits"))?;
+    process_exits(&mut state, &block.body.voluntary_exits, spec)
+        .map_err(|e| e.context("process_exits"))?;
+    process_transfers(&mut state, &block.body.transfers, spec)
+        .map_err(|e| e.context("process_transfers"))?;
 
     Ok(())
 }
@@ -157,6 +194,24 @@ pub fn verify_block_signature<T: EthSpec>(
     Ok(())
 }
 
+/// Verifies the proposer signature of each block.
+///
+/// Each block is verified against the state it is paired with, which must be at the same slot as
+/// the block. Signatures are verified individually, as an unweighted aggregate of them would
+/// allow invalid signatures to cancel each other out.
+///
+/// Returns `Invalid::BadSignature` if any signature is invalid, without indicating which one.
+pub fn verify_block_signatures_batch<T: EthSpec>(
+    blocks: &[(BeaconBlock, &BeaconState<T>)],
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    for (block, state) in blocks {
+        verify_block_signature(state, block, spec)?;
+    }
+
+    Ok(())
+}
+
 /// Verifies the `randao_reveal` against the block's proposer pubkey and updates
 /// `state.latest_randao_mixes`.
 ///
@@ -212,6 +267,18 @@ pub fn process_eth1_data<T: EthSpec>(
     Ok(())
 }
 
+/// Verifies the `ExecutionPayload` of a post-Merge block against the state.
+///
+/// The state in this version of the spec has no `latest_execution_payload_header` and no Merge
+/// fork is scheduled, so every block is pre-Merge and this is a no-op.
+pub fn process_execution_payload<T: EthSpec>(
+    _state: &mut BeaconState<T>,
+    _payload: &ExecutionPayload,
+    _spec: &ChainSpec,
+) -> Result<(), Error> {
+    Ok(())
+}
+
 /// Validates each `ProposerSlashing` and updates the state, short-circuiting on an invalid object.
 ///
 /// Returns `Ok(())` if the validation and state updates completed successfully, otherwise returns
@@ -362,6 +429,31 @@ pub fn process_deposits<T: EthSpec>(
     state: &mut BeaconState<T>,
     deposits: &[Deposit],
     spec: &ChainSpec,
+) -> Result<(), Error> {
+    process_deposits_proof_optional(state, deposits, true, spec)
+}
+
+/// As per `process_deposits`, but without verifying the deposit Merkle proofs.
+///
+/// Only for deposits from a trusted source (e.g., when replaying historic blocks), where proof
+/// verification is redundant.
+pub fn apply_deposit_with_proof_skip<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    deposits: &[Deposit],
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    process_deposits_proof_optional(state, deposits, false, spec)
+}
+
+/// Validates each `Deposit` and updates the state, optionally verifying the deposit Merkle
+/// proofs.
+///
+/// Spec v0.6.3
+fn process_deposits_proof_optional<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    deposits: &[Deposit],
+    should_verify_deposit_proofs: bool,
+    spec: &ChainSpec,
 ) -> Result<(), Error> {
     verify!(
         deposits.len() as u64
@@ -373,12 +465,41 @@ pub fn process_deposits<T: EthSpec>(
     );
 
     // Verify deposits in parallel.
-    deposits
-        .par_iter()
-        .enumerate()
-        .try_for_each(|(i, deposit)| {
-            verify_deposit_merkle_proof(state, deposit, spec).map_err(|e| e.into_with_index(i))
-        })?;
+    if should_verify_deposit_proofs {
+        deposits
+            .par_iter()
+            .enumerate()
+            .try_for_each(|(i, deposit)| {
+                verify_deposit_merkle_proof(state, deposit, spec).map_err(|e| e.into_with_index(i))
+            })?;
+    }
+
+    // Signatures are only required for deposits that create a new validator: the first deposit
+    // for each pubkey that is not already in the registry.
+    state.update_pubkey_cache()?;
+    let mut new_pubkeys = HashSet::new();
+    let mut new_validator_indices = vec![];
+    for (i, deposit) in deposits.iter().enumerate() {
+        let validator_index =
+            get_existing_validator_index(state, deposit).map_err(|e| e.into_with_index(i))?;
+
+        if validator_index.is_none() && new_pubkeys.insert(&deposit.data.pubkey) {
+            new_validator_indices.push(i);
+        }
+    }
+
+    // Verify the signatures of new validators at once.
+    let new_validator_deposits: Vec<&Deposit> = new_validator_indices
+        .iter()
+        .map(|&i| &deposits[i])
+        .collect();
+    let new_validator_signatures =
+        verify_deposit_signatures_batch(state, &new_validator_deposits, spec);
+
+    let mut signatures_valid = vec![true; deposits.len()];
+    for (&i, valid) in new_validator_indices.iter().zip(new_validator_signatures) {
+        signatures_valid[i] = valid;
+    }
 
     // Check `state.deposit_index` and update the state in series.
     for (i, deposit) in deposits.iter().enumerate() {
@@ -403,7 +524,7 @@ pub fn process_deposits<T: EthSpec>(
         } else {
             // The signature should be checked for new validators. Return early for a bad
             // signature.
-            if verify_deposit_signature(state, deposit, spec).is_err() {
+            if !signatures_valid[i] {
                 return Ok(());
             }
 
@@ -490,3 +611,39 @@ pub fn process_transfers<T: EthSpec>(
 
     Ok(())
 }
+
+/// Validates each `SignedBLSToExecutionChange` and sets the withdrawal credentials of its validator
+/// to the new execution address, short-circuiting on an invalid object.
+///
+/// Returns `Ok(())` if the validation and state updates completed successfully, otherwise returns
+/// an `Err` describing the invalid object or cause of failure.
+///
+/// This is not part of `per_block_processing`, as the spec v0.6.3 `BeaconBlockBody` does not
+/// carry BLS-to-execution changes.
+pub fn process_bls_to_execution_changes<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    changes: &[SignedBLSToExecutionChange],
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    verify!(
+        changes.len() as u64 <= spec.max_bls_to_execution_changes,
+        Invalid::MaxBLSToExecutionChangesExceeded
+    );
+
+    // Changes are verified and applied in series, as a block may hold two changes for the same
+    // validator and only the first can succeed.
+    for (i, signed_change) in changes.iter().enumerate() {
+        verify_bls_to_execution_change(&state, signed_change, spec)
+            .map_err(|e| e.into_with_index(i))?;
+
+        let change = &signed_change.message;
+        let mut withdrawal_credentials = [0; 32];
+        withdrawal_credentials[0] = spec.eth1_address_withdrawal_prefix_byte;
+        withdrawal_credentials[12..].copy_from_slice(change.to_execution_address.as_bytes());
+
+        state.validator_registry[change.validator_index as usize].withdrawal_credentials =
+            Hash256::from_slice(&withdrawal_credentials);
+    }
+
+    Ok(())
+}
@@ -59,10 +59,56 @@ pub enum BlockProcessingError {
     Invalid(BlockInvalid),
     /// Encountered a `BeaconStateError` whilst attempting to determine validity.
     BeaconStateError(BeaconStateError),
+    /// The `inner` error occurred during the processing phase described by `context`.
+    Contextual {
+        inner: Box<BlockProcessingError>,
+        context: String,
+    },
 }
 
 impl_from_beacon_state_error!(BlockProcessingError);
 
+impl BlockProcessingError {
+    /// Wraps `self`, recording that it occurred during `context`.
+    pub fn context(self, context: impl Into<String>) -> Self {
+        BlockProcessingError::Contextual {
+            inner: Box::new(self),
+            context: context.into(),
+        }
+    }
+
+    /// Returns the error beneath any `Contextual` wrappers.
+    pub fn root_cause(&self) -> &Self {
+        match self {
+            BlockProcessingError::Contextual { inner, .. } => inner.root_cause(),
+            e => e,
+        }
+    }
+
+    /// Returns `Ok` with the underlying error if the root cause is a `BeaconStateError`,
+    /// otherwise returns `Err(self)`.
+    pub fn into_beacon_state_error(self) -> Result<BeaconStateError, Self> {
+        match self {
+            BlockProcessingError::BeaconStateError(e) => Ok(e),
+            BlockProcessingError::Contextual { inner, context } => inner
+                .into_beacon_state_error()
+                .map_err(|inner| inner.context(context)),
+            e => Err(e),
+        }
+    }
+}
+
+impl std::fmt::Display for BlockProcessingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BlockProcessingError::Contextual { inner, context } => {
+                write!(f, "{}: {}", context, inner)
+            }
+            e => write!(f, "{:?}", e),
+        }
+    }
+}
+
 /// Describes why an object is invalid.
 #[derive(Debug, PartialEq)]
 pub enum BlockInvalid {
@@ -80,6 +126,7 @@ pub enum BlockInvalid {
     DepositCountInvalid,
     MaxExitsExceeded,
     MaxTransfersExceed,
+    MaxBLSToExecutionChangesExceeded,
     AttestationInvalid(usize, AttestationInvalid),
     /// A `IndexedAttestation` inside an `AttesterSlashing` was invalid.
     ///
@@ -92,6 +139,7 @@ pub enum BlockInvalid {
     DepositProcessingFailed(usize),
     ExitInvalid(usize, ExitInvalid),
     TransferInvalid(usize, TransferInvalid),
+    BLSToExecutionChangeInvalid(usize, BLSToExecutionChangeInvalid),
 }
 
 impl Into<BlockProcessingError> for BlockInvalid {
@@ -432,3 +480,35 @@ pub enum TransferInvalid {
 
 impl_from_beacon_state_error!(TransferValidationError);
 impl_into_with_index_with_beacon_error!(TransferValidationError, TransferInvalid);
+
+/*
+ * `BLSToExecutionChange` Validation
+ */
+
+/// The object is invalid or validation failed.
+#[derive(Debug, PartialEq)]
+pub enum BLSToExecutionChangeValidationError {
+    /// Validation completed successfully and the object is invalid.
+    Invalid(BLSToExecutionChangeInvalid),
+}
+
+/// Describes why an object is invalid.
+#[derive(Debug, PartialEq)]
+pub enum BLSToExecutionChangeInvalid {
+    /// The specified validator is not in the state's validator registry.
+    ValidatorUnknown(u64),
+    /// The validator's withdrawal credentials are not BLS credentials, e.g., they already name an
+    /// execution address.
+    NonBLSWithdrawalCredentials(u64),
+    /// The validator's withdrawal credentials do not commit to `from_bls_pubkey`.
+    ///
+    /// (state_credentials, change_pubkey_credentials)
+    WithdrawalCredentialsMismatch(Hash256, Hash256),
+    /// The change was not signed by `from_bls_pubkey`.
+    BadSignature,
+}
+
+impl_into_with_index_without_beacon_error!(
+    BLSToExecutionChangeValidationError,
+    BLSToExecutionChangeInvalid
+);
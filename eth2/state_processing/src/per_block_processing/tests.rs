@@ -2,7 +2,17 @@
 use super::block_processing_builder::BlockProcessingBuilder;
 use super::errors::*;
 use crate::per_block_processing;
-use tree_hash::SignedRoot;
+use crate::per_block_processing::{
+    apply_deposit_with_proof_skip, process_bls_to_execution_changes, process_deposits,
+    verify_deposit_signatures_batch, verify_exit, verify_exit_time_independent_only,
+};
+use bls::get_withdrawal_credentials;
+use merkle_proof::merkle_branch;
+use tree_hash::{SignedRoot, TreeHash};
+use types::test_utils::{
+    generate_deterministic_keypairs, TestingBeaconStateBuilder, TestingDepositBuilder,
+    TestingVoluntaryExitBuilder,
+};
 use types::*;
 
 pub const VALIDATOR_COUNT: usize = 10;
@@ -30,10 +40,8 @@ fn invalid_block_header_state_slot() {
     let result = per_block_processing(&mut state, &block, &spec);
 
     assert_eq!(
-        result,
-        Err(BlockProcessingError::Invalid(
-            BlockInvalid::StateSlotMismatch
-        ))
+        result.unwrap_err().root_cause(),
+        &BlockProcessingError::Invalid(BlockInvalid::StateSlotMismatch)
     );
 }
 
@@ -47,13 +55,11 @@ fn invalid_parent_block_root() {
     let result = per_block_processing(&mut state, &block, &spec);
 
     assert_eq!(
-        result,
-        Err(BlockProcessingError::Invalid(
-            BlockInvalid::ParentBlockRootMismatch {
-                state: Hash256::from_slice(&state.latest_block_header.signed_root()),
-                block: block.previous_block_root
-            }
-        ))
+        result.unwrap_err().root_cause(),
+        &BlockProcessingError::Invalid(BlockInvalid::ParentBlockRootMismatch {
+            state: Hash256::from_slice(&state.latest_block_header.signed_root()),
+            block: block.previous_block_root
+        })
     );
 }
 
@@ -75,8 +81,8 @@ fn invalid_block_signature() {
 
     // should get a BadSignature error
     assert_eq!(
-        result,
-        Err(BlockProcessingError::Invalid(BlockInvalid::BadSignature))
+        result.unwrap_err().root_cause(),
+        &BlockProcessingError::Invalid(BlockInvalid::BadSignature)
     );
 }
 
@@ -92,10 +98,320 @@ fn invalid_randao_reveal_signature() {
     let result = per_block_processing(&mut state, &block, &spec);
 
     // should get a BadRandaoSignature error
+    assert_eq!(
+        result.unwrap_err().root_cause(),
+        &BlockProcessingError::Invalid(BlockInvalid::BadRandaoSignature)
+    );
+}
+
+#[test]
+fn invalid_block_error_has_context() {
+    let spec = MainnetEthSpec::default_spec();
+    let builder = get_builder(&spec);
+    let keypair = Keypair::random();
+    let (block, mut state) = builder.build(Some(keypair.sk), None, &spec);
+
+    let result = per_block_processing(&mut state, &block, &spec);
+
     assert_eq!(
         result,
+        Err(
+            BlockProcessingError::Invalid(BlockInvalid::BadRandaoSignature)
+                .context("process_randao")
+        )
+    );
+    assert_eq!(
+        format!("{}", result.unwrap_err()),
+        "process_randao: Invalid(BadRandaoSignature)"
+    );
+}
+
+#[test]
+fn future_exit_passes_time_independent_checks() {
+    let spec = MainnetEthSpec::default_spec();
+    let mut state_builder =
+        TestingBeaconStateBuilder::<MainnetEthSpec>::from_default_keypairs_file_if_exists(
+            VALIDATOR_COUNT,
+            &spec,
+        );
+    // Validators must have been active for `persistent_committee_period` epochs to exit.
+    let epoch = MainnetEthSpec::genesis_epoch() + spec.persistent_committee_period;
+    state_builder.teleport_to_slot(epoch.start_slot(MainnetEthSpec::slots_per_epoch()));
+    let (state, keypairs) = state_builder.build();
+
+    let mut exit_builder = TestingVoluntaryExitBuilder::new(state.current_epoch() + 1, 0);
+    exit_builder.sign(&keypairs[0].sk, &state.fork, &spec);
+    let exit = exit_builder.build();
+
+    assert_eq!(
+        verify_exit(&state, &exit, &spec),
+        Err(ExitValidationError::Invalid(ExitInvalid::FutureEpoch {
+            state: state.current_epoch(),
+            exit: exit.epoch,
+        }))
+    );
+    assert_eq!(
+        verify_exit_time_independent_only(&state, &exit, &spec),
+        Ok(())
+    );
+}
+
+#[test]
+fn batch_deposit_signatures_find_invalid() {
+    let spec = MainnetEthSpec::default_spec();
+    let builder = get_builder(&spec);
+    let (_block, state) = builder.build(None, None, &spec);
+
+    let mut deposits: Vec<Deposit> = generate_deterministic_keypairs(3)
+        .iter()
+        .map(|keypair| {
+            let mut builder = TestingDepositBuilder::new(keypair.pk.clone(), 32);
+            builder.sign(keypair, state.current_epoch(), &state.fork, &spec);
+            builder.build()
+        })
+        .collect();
+
+    assert_eq!(
+        verify_deposit_signatures_batch(&state, &deposits.iter().collect::<Vec<_>>(), &spec),
+        vec![true, true, true]
+    );
+
+    deposits[1].data.amount += 1;
+
+    assert_eq!(
+        verify_deposit_signatures_batch(&state, &deposits.iter().collect::<Vec<_>>(), &spec),
+        vec![true, false, true]
+    );
+}
+
+#[test]
+fn batch_deposit_signatures_reject_cancelling_signatures() {
+    let spec = MainnetEthSpec::default_spec();
+    let builder = get_builder(&spec);
+    let (_block, state) = builder.build(None, None, &spec);
+
+    let mut deposits: Vec<Deposit> = generate_deterministic_keypairs(3)
+        .iter()
+        .map(|keypair| {
+            let mut builder = TestingDepositBuilder::new(keypair.pk.clone(), 32);
+            builder.sign(keypair, state.current_epoch(), &state.fork, &spec);
+            builder.build()
+        })
+        .collect();
+
+    // Swapping two signatures leaves their aggregate unchanged, but both are invalid.
+    let signature_0 = deposits[0].data.signature.clone();
+    deposits[0].data.signature = deposits[1].data.signature.clone();
+    deposits[1].data.signature = signature_0;
+
+    assert_eq!(
+        verify_deposit_signatures_batch(&state, &deposits.iter().collect::<Vec<_>>(), &spec),
+        vec![false, false, true]
+    );
+}
+
+#[test]
+fn deposit_proof_skip_matches_verified_processing() {
+    let spec = MainnetEthSpec::default_spec();
+    let builder = get_builder(&spec);
+    let (_block, mut state) = builder.build(None, None, &spec);
+
+    // A top-up for an existing validator and two new validators.
+    let keypairs = generate_deterministic_keypairs(VALIDATOR_COUNT + 2);
+    let mut deposits: Vec<Deposit> = [0, VALIDATOR_COUNT, VALIDATOR_COUNT + 1]
+        .iter()
+        .enumerate()
+        .map(|(i, &keypair_index)| {
+            let keypair = &keypairs[keypair_index];
+            let mut builder = TestingDepositBuilder::new(keypair.pk.clone(), 32_000_000_000);
+            builder.set_index(state.deposit_index + i as u64);
+            builder.sign(keypair, state.current_epoch(), &state.fork, &spec);
+            builder.build()
+        })
+        .collect();
+
+    // Deposits prior to `state.deposit_index` are irrelevant, leave them zeroed.
+    let mut leaves = vec![Hash256::zero(); state.deposit_index as usize];
+    leaves.extend(
+        deposits
+            .iter()
+            .map(|deposit| Hash256::from_slice(&deposit.data.tree_hash_root())),
+    );
+
+    let depth = spec.deposit_contract_tree_depth as usize;
+    for deposit in &mut deposits {
+        let (branch, root) = merkle_branch(&leaves, deposit.index as usize, depth);
+        deposit.proof = branch.into();
+        state.latest_eth1_data.deposit_root = root;
+    }
+    state.latest_eth1_data.deposit_count = leaves.len() as u64;
+
+    let mut verified_state = state.clone();
+    assert_eq!(
+        process_deposits(&mut verified_state, &deposits, &spec),
+        Ok(())
+    );
+
+    let mut skipped_state = state.clone();
+    assert_eq!(
+        apply_deposit_with_proof_skip(&mut skipped_state, &deposits, &spec),
+        Ok(())
+    );
+
+    assert_eq!(verified_state, skipped_state);
+    assert_eq!(
+        skipped_state.validator_registry.len(),
+        state.validator_registry.len() + 2
+    );
+
+    // Only the verifying path rejects a bad proof.
+    deposits[0].proof = vec![Hash256::zero(); depth].into();
+    assert!(process_deposits(&mut state.clone(), &deposits, &spec).is_err());
+    assert_eq!(
+        apply_deposit_with_proof_skip(&mut state.clone(), &deposits, &spec),
+        Ok(())
+    );
+}
+
+#[test]
+fn deposit_signatures_only_required_for_new_validators() {
+    let spec = MainnetEthSpec::default_spec();
+    let builder = get_builder(&spec);
+    let (_block, mut state) = builder.build(None, None, &spec);
+
+    // A top-up for an existing validator, then two new validators, all but the last with a bad
+    // signature.
+    let keypairs = generate_deterministic_keypairs(VALIDATOR_COUNT + 2);
+    let mut deposits: Vec<Deposit> = [0, VALIDATOR_COUNT, VALIDATOR_COUNT + 1]
+        .iter()
+        .enumerate()
+        .map(|(i, &keypair_index)| {
+            let keypair = &keypairs[keypair_index];
+            let mut builder = TestingDepositBuilder::new(keypair.pk.clone(), 32_000_000_000);
+            builder.set_index(state.deposit_index + i as u64);
+            builder.sign(keypair, state.current_epoch(), &state.fork, &spec);
+            builder.build()
+        })
+        .collect();
+    deposits[0].data.amount += 1;
+    deposits[1].data.amount += 1;
+    state.latest_eth1_data.deposit_count = state.deposit_index + deposits.len() as u64;
+
+    let mut processed = state.clone();
+    assert_eq!(
+        apply_deposit_with_proof_skip(&mut processed, &deposits, &spec),
+        Ok(())
+    );
+
+    // The top-up is applied, then processing stops at the first bad new validator.
+    assert_eq!(
+        processed.balances[0],
+        state.balances[0] + deposits[0].data.amount
+    );
+    assert_eq!(
+        processed.validator_registry.len(),
+        state.validator_registry.len()
+    );
+    assert_eq!(processed.deposit_index, state.deposit_index + 2);
+}
+
+#[test]
+fn bls_to_execution_change_sets_execution_address() {
+    let spec = MainnetEthSpec::default_spec();
+    let builder = get_builder(&spec);
+    let (_block, mut state) = builder.build(None, None, &spec);
+    let keypairs = generate_deterministic_keypairs(VALIDATOR_COUNT);
+
+    // Changes are always signed with the genesis fork version.
+    let sign = |message: BLSToExecutionChange, sk: &SecretKey| {
+        let genesis_epoch = MainnetEthSpec::genesis_epoch();
+        let domain = spec.get_domain(
+            genesis_epoch,
+            Domain::BlsToExecutionChange,
+            &Fork::genesis(genesis_epoch),
+        );
+        let signature = Signature::new(&message.tree_hash_root(), domain, sk);
+        SignedBLSToExecutionChange { message, signature }
+    };
+
+    // A fork since genesis must not invalidate the change.
+    state.fork = Fork {
+        previous_version: state.fork.current_version,
+        current_version: [1, 0, 0, 0],
+        epoch: state.current_epoch(),
+    };
+
+    let address = Address::from_slice(&[42; 20]);
+    let change = sign(
+        BLSToExecutionChange {
+            validator_index: 1,
+            from_bls_pubkey: keypairs[1].pk.clone(),
+            to_execution_address: address,
+        },
+        &keypairs[1].sk,
+    );
+
+    // Signed by a key other than `from_bls_pubkey`.
+    let forged = sign(change.message.clone(), &keypairs[2].sk);
+    assert_eq!(
+        process_bls_to_execution_changes(&mut state.clone(), &[forged], &spec),
+        Err(BlockProcessingError::Invalid(
+            BlockInvalid::BLSToExecutionChangeInvalid(0, BLSToExecutionChangeInvalid::BadSignature)
+        ))
+    );
+
+    // Names a key other than the one committed to by the withdrawal credentials.
+    let mismatched = sign(
+        BLSToExecutionChange {
+            from_bls_pubkey: keypairs[2].pk.clone(),
+            ..change.message.clone()
+        },
+        &keypairs[2].sk,
+    );
+    let expected_credentials = Hash256::from_slice(
+        &get_withdrawal_credentials(&keypairs[2].pk, spec.bls_withdrawal_prefix_byte)[..],
+    );
+    assert_eq!(
+        process_bls_to_execution_changes(&mut state.clone(), &[mismatched], &spec),
+        Err(BlockProcessingError::Invalid(
+            BlockInvalid::BLSToExecutionChangeInvalid(
+                0,
+                BLSToExecutionChangeInvalid::WithdrawalCredentialsMismatch(
+                    state.validator_registry[1].withdrawal_credentials,
+                    expected_credentials
+                )
+            )
+        ))
+    );
+
+    assert_eq!(
+        process_bls_to_execution_changes(&mut state, &[change.clone()], &spec),
+        Ok(())
+    );
+
+    let credentials = state.validator_registry[1].withdrawal_credentials;
+    assert_eq!(
+        credentials.as_bytes()[0],
+        spec.eth1_address_withdrawal_prefix_byte
+    );
+    assert_eq!(&credentials.as_bytes()[12..], address.as_bytes());
+
+    // The credentials have already changed, so the change cannot be applied twice.
+    assert_eq!(
+        process_bls_to_execution_changes(&mut state, &[change.clone()], &spec),
+        Err(BlockProcessingError::Invalid(
+            BlockInvalid::BLSToExecutionChangeInvalid(
+                0,
+                BLSToExecutionChangeInvalid::NonBLSWithdrawalCredentials(1)
+            )
+        ))
+    );
+
+    let too_many = vec![change; spec.max_bls_to_execution_changes as usize + 1];
+    assert_eq!(
+        process_bls_to_execution_changes(&mut state, &too_many, &spec),
         Err(BlockProcessingError::Invalid(
-            BlockInvalid::BadRandaoSignature
+            BlockInvalid::MaxBLSToExecutionChangesExceeded
         ))
     );
 }
@@ -29,6 +29,16 @@ pub fn validate_attestation_time_independent_only<T: EthSpec>(
     validate_attestation_parametric(state, attestation, spec, true, true)
 }
 
+/// Like `validate_attestation_time_independent_only` but doesn't validate the aggregate
+/// signature. Only for attestations whose signature has already been verified.
+pub fn validate_attestation_time_independent_only_without_signature<T: EthSpec>(
+    state: &BeaconState<T>,
+    attestation: &Attestation,
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    validate_attestation_parametric(state, attestation, spec, false, true)
+}
+
 /// Indicates if an `Attestation` is valid to be included in a block in the current epoch of the
 /// given state, without validating the aggregate signature.
 ///
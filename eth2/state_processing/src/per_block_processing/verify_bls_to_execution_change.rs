@@ -0,0 +1,58 @@
+use super::errors::{
+    BLSToExecutionChangeInvalid as Invalid, BLSToExecutionChangeValidationError as Error,
+};
+use bls::get_withdrawal_credentials;
+use tree_hash::TreeHash;
+use types::*;
+
+/// Indicates if a `SignedBLSToExecutionChange` is valid to be included in a block in the current
+/// epoch of the given state.
+///
+/// Returns `Ok(())` if the change is valid, otherwise indicates the reason for invalidity.
+pub fn verify_bls_to_execution_change<T: EthSpec>(
+    state: &BeaconState<T>,
+    signed_change: &SignedBLSToExecutionChange,
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    let change = &signed_change.message;
+
+    let validator = state
+        .validator_registry
+        .get(change.validator_index as usize)
+        .ok_or_else(|| Error::Invalid(Invalid::ValidatorUnknown(change.validator_index)))?;
+
+    // Verify the validator still has BLS withdrawal credentials.
+    verify!(
+        validator.withdrawal_credentials.as_bytes()[0] == spec.bls_withdrawal_prefix_byte,
+        Invalid::NonBLSWithdrawalCredentials(change.validator_index)
+    );
+
+    // Verify the credentials commit to the key that signed the change.
+    let change_withdrawal_credentials = Hash256::from_slice(
+        &get_withdrawal_credentials(&change.from_bls_pubkey, spec.bls_withdrawal_prefix_byte)[..],
+    );
+    verify!(
+        validator.withdrawal_credentials == change_withdrawal_credentials,
+        Invalid::WithdrawalCredentialsMismatch(
+            validator.withdrawal_credentials,
+            change_withdrawal_credentials
+        )
+    );
+
+    // Verify signature. Changes are signed with the genesis fork version so that they remain valid
+    // across forks.
+    let message = change.tree_hash_root();
+    let domain = spec.get_domain(
+        T::genesis_epoch(),
+        Domain::BlsToExecutionChange,
+        &Fork::genesis(T::genesis_epoch()),
+    );
+    verify!(
+        signed_change
+            .signature
+            .verify(&message[..], domain, &change.from_bls_pubkey),
+        Invalid::BadSignature
+    );
+
+    Ok(())
+}
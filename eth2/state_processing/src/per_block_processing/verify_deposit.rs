@@ -1,5 +1,6 @@
 use super::errors::{DepositInvalid as Invalid, DepositValidationError as Error};
 use merkle_proof::verify_merkle_proof;
+use rayon::prelude::*;
 use tree_hash::{SignedRoot, TreeHash};
 use types::*;
 
@@ -23,6 +24,28 @@ pub fn verify_deposit_signature<T: EthSpec>(
     Ok(())
 }
 
+/// Verify the signatures of `deposits` in parallel, returning whether each is valid.
+///
+/// Each signature is checked on its own: an unweighted aggregate check would accept invalid
+/// signatures that cancel each other out.
+pub fn verify_deposit_signatures_batch<T: EthSpec>(
+    state: &BeaconState<T>,
+    deposits: &[&Deposit],
+    spec: &ChainSpec,
+) -> Vec<bool> {
+    let domain = spec.get_domain(state.current_epoch(), Domain::Deposit, &state.fork);
+
+    deposits
+        .par_iter()
+        .map(|deposit| {
+            deposit
+                .data
+                .signature
+                .verify(&deposit.data.signed_root(), domain, &deposit.data.pubkey)
+        })
+        .collect()
+}
+
 /// Verify that the `Deposit` index is correct.
 ///
 /// Spec v0.6.3
@@ -18,6 +18,10 @@ pub fn verify_exit<T: EthSpec>(
 
 /// Like `verify_exit` but doesn't run checks which may become true in future states.
 ///
+/// Checks that the validator is active, has not initiated an exit, has been active for at least
+/// `persistent_committee_period` epochs and has signed the exit. Does not check that the exit
+/// epoch has been reached.
+///
 /// Spec v0.6.3
 pub fn verify_exit_time_independent_only<T: EthSpec>(
     state: &BeaconState<T>,
@@ -85,6 +85,30 @@ fn get_proposer_deltas<T: EthSpec>(
     // Update statuses with the information from winning roots.
     validator_statuses.process_winning_roots(state, winning_root_for_shards, spec)?;
 
+    let proposer_rewards = get_proposer_rewards(state, validator_statuses, spec)?;
+
+    if proposer_rewards.len() != deltas.len() {
+        return Err(Error::ValidatorStatusesInconsistent);
+    }
+
+    for (delta, reward) in deltas.iter_mut().zip(proposer_rewards) {
+        delta.reward(reward);
+    }
+
+    Ok(())
+}
+
+/// Returns the reward of each validator for proposing blocks that were first to include the
+/// attestations of the previous epoch.
+///
+/// Spec v0.6.3
+pub fn get_proposer_rewards<T: EthSpec>(
+    state: &BeaconState<T>,
+    validator_statuses: &ValidatorStatuses,
+    spec: &ChainSpec,
+) -> Result<Vec<u64>, Error> {
+    let mut rewards = vec![0; validator_statuses.statuses.len()];
+
     for validator in &validator_statuses.statuses {
         if validator.is_previous_epoch_attester {
             let inclusion = validator
@@ -98,15 +122,15 @@ fn get_proposer_deltas<T: EthSpec>(
                 spec,
             )?;
 
-            if inclusion.proposer_index >= deltas.len() {
+            if inclusion.proposer_index >= rewards.len() {
                 return Err(Error::ValidatorStatusesInconsistent);
             }
 
-            deltas[inclusion.proposer_index].reward(base_reward / spec.proposer_reward_quotient);
+            rewards[inclusion.proposer_index] += base_reward / spec.proposer_reward_quotient;
         }
     }
 
-    Ok(())
+    Ok(rewards)
 }
 
 /// Apply rewards for participation in attestations during the previous epoch.
@@ -152,7 +176,53 @@ fn get_attestation_delta(
     finality_delay: u64,
     spec: &ChainSpec,
 ) -> Delta {
+    let components = get_attestation_delta_components(
+        validator,
+        total_balances,
+        base_reward,
+        finality_delay,
+        spec,
+    );
+
     let mut delta = Delta::default();
+    for component in &[
+        components.source,
+        components.inclusion_delay,
+        components.target,
+        components.head,
+        components.inactivity,
+    ] {
+        if *component >= 0 {
+            delta.reward(*component as u64);
+        } else {
+            delta.penalize(component.abs() as u64);
+        }
+    }
+
+    delta
+}
+
+/// The components of a validator's attestation delta, in signed gwei.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AttestationDeltaComponents {
+    pub source: i64,
+    pub inclusion_delay: i64,
+    pub target: i64,
+    pub head: i64,
+    pub inactivity: i64,
+}
+
+/// Determine the components of the delta for a single validator, sans proposer rewards.
+///
+/// Spec v0.6.3
+pub fn get_attestation_delta_components(
+    validator: &ValidatorStatus,
+    total_balances: &TotalBalances,
+    base_reward: u64,
+    finality_delay: u64,
+    spec: &ChainSpec,
+) -> AttestationDeltaComponents {
+    let mut delta = AttestationDeltaComponents::default();
 
     // Is this validator eligible to be rewarded or penalized?
     // Spec: validator index in `eligible_validator_indices`
@@ -172,45 +242,44 @@ fn get_attestation_delta(
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_source_attestations)`
     if validator.is_previous_epoch_attester && !validator.is_slashed {
-        delta.reward(base_reward * total_attesting_balance / total_balance);
+        delta.source = (base_reward * total_attesting_balance / total_balance) as i64;
         // Inclusion speed bonus
         let inclusion = validator
             .inclusion_info
             .expect("It is a logic error for an attester not to have an inclusion distance.");
-        delta.reward(base_reward * spec.min_attestation_inclusion_delay / inclusion.distance);
+        delta.inclusion_delay =
+            (base_reward * spec.min_attestation_inclusion_delay / inclusion.distance) as i64;
     } else {
-        delta.penalize(base_reward);
+        delta.source = -(base_reward as i64);
     }
 
     // Expected FFG target.
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_target_attestations)`
     if validator.is_previous_epoch_target_attester && !validator.is_slashed {
-        delta.reward(base_reward * matching_target_balance / total_balance);
+        delta.target = (base_reward * matching_target_balance / total_balance) as i64;
     } else {
-        delta.penalize(base_reward);
+        delta.target = -(base_reward as i64);
     }
 
     // Expected head.
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_head_attestations)`
     if validator.is_previous_epoch_head_attester && !validator.is_slashed {
-        delta.reward(base_reward * matching_head_balance / total_balance);
+        delta.head = (base_reward * matching_head_balance / total_balance) as i64;
     } else {
-        delta.penalize(base_reward);
+        delta.head = -(base_reward as i64);
     }
 
     // Inactivity penalty
     if finality_delay > spec.min_epochs_to_inactivity_penalty {
         // All eligible validators are penalized
-        delta.penalize(spec.base_rewards_per_epoch * base_reward);
+        delta.inactivity -= (spec.base_rewards_per_epoch * base_reward) as i64;
 
         // Additionally, all validators whose FFG target didn't match are penalized extra
         if !validator.is_previous_epoch_target_attester {
-            delta.penalize(
-                validator.current_epoch_effective_balance * finality_delay
-                    / spec.inactivity_penalty_quotient,
-            );
+            delta.inactivity -= (validator.current_epoch_effective_balance * finality_delay
+                / spec.inactivity_penalty_quotient) as i64;
         }
     }
 
@@ -259,7 +328,7 @@ fn get_crosslink_deltas<T: EthSpec>(
 /// Returns the base reward for some validator.
 ///
 /// Spec v0.6.3
-fn get_base_reward<T: EthSpec>(
+pub fn get_base_reward<T: EthSpec>(
     state: &BeaconState<T>,
     index: usize,
     // Should be == get_total_active_balance(state, spec)
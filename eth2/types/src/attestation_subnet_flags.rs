@@ -0,0 +1,70 @@
+use crate::Shard;
+use serde_derive::{Deserialize, Serialize};
+
+/// The number of gossip subnets that attestations are divided between.
+pub const ATTESTATION_SUBNET_COUNT: u64 = 64;
+
+/// A bitmask of attestation subnets, where bit `n` is set if subnet `n` is included.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct AttestationSubnetFlags(u64);
+
+impl AttestationSubnetFlags {
+    /// Returns the subnet that attestations for `shard` are published on.
+    pub fn subnet_for_shard(shard: Shard) -> u64 {
+        shard % ATTESTATION_SUBNET_COUNT
+    }
+
+    /// Sets the bit for the subnet of `shard`.
+    pub fn insert_shard(&mut self, shard: Shard) {
+        self.0 |= 1 << Self::subnet_for_shard(shard);
+    }
+
+    /// Returns `true` if `subnet` is set.
+    pub fn contains(&self, subnet: u64) -> bool {
+        subnet < ATTESTATION_SUBNET_COUNT && self.0 & (1 << subnet) != 0
+    }
+
+    /// Returns `true` if no subnets are set.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the flags set in either `self` or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        AttestationSubnetFlags(self.0 | other.0)
+    }
+
+    /// Returns the set subnets, in ascending order.
+    pub fn subnets(&self) -> Vec<u64> {
+        (0..ATTESTATION_SUBNET_COUNT)
+            .filter(|&subnet| self.contains(subnet))
+            .collect()
+    }
+
+    /// Returns the raw bitmask.
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shards_wrap_onto_subnets() {
+        let mut flags = AttestationSubnetFlags::default();
+        assert!(flags.is_empty());
+
+        flags.insert_shard(3);
+        flags.insert_shard(ATTESTATION_SUBNET_COUNT + 5);
+
+        assert_eq!(flags.subnets(), vec![3, 5]);
+        assert_eq!(flags.as_u64(), (1 << 3) | (1 << 5));
+        assert!(!flags.contains(ATTESTATION_SUBNET_COUNT + 3));
+
+        let mut other = AttestationSubnetFlags::default();
+        other.insert_shard(63);
+        assert_eq!(flags.union(&other).subnets(), vec![3, 5, 63]);
+    }
+}
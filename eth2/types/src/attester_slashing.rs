@@ -1,4 +1,5 @@
 use crate::{test_utils::TestRandom, IndexedAttestation};
+use std::collections::HashSet;
 
 use serde_derive::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
@@ -25,9 +26,107 @@ pub struct AttesterSlashing {
     pub attestation_2: IndexedAttestation,
 }
 
+impl AttesterSlashing {
+    /// Builds a slashing from two attestations if they are a double vote or a surround vote and
+    /// share at least one attesting validator, otherwise returns `None`.
+    ///
+    /// The attestations are ordered so that a surrounding vote is `attestation_1`. Signatures are
+    /// not checked.
+    pub fn from_conflicting_attestations(
+        a: IndexedAttestation,
+        b: IndexedAttestation,
+    ) -> Option<Self> {
+        let attesting_indices = |attestation: &IndexedAttestation| {
+            attestation
+                .custody_bit_0_indices
+                .iter()
+                .chain(attestation.custody_bit_1_indices.iter())
+                .cloned()
+                .collect::<HashSet<u64>>()
+        };
+
+        if attesting_indices(&a).is_disjoint(&attesting_indices(&b)) {
+            return None;
+        }
+
+        if a.is_double_vote(&b) || a.is_surround_vote(&b) {
+            Some(AttesterSlashing {
+                attestation_1: a,
+                attestation_2: b,
+            })
+        } else if b.is_surround_vote(&a) {
+            Some(AttesterSlashing {
+                attestation_1: b,
+                attestation_2: a,
+            })
+        } else {
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{SeedableRng, XorShiftRng};
+    use crate::{Epoch, Hash256};
+
+    #[test]
+    fn from_conflicting_attestations_double_vote() {
+        let a = create_indexed_attestation(3, 1, vec![1, 2]);
+        let mut b = create_indexed_attestation(3, 1, vec![2, 3]);
+        b.data.beacon_block_root = Hash256::from_slice(&[1; 32]);
+
+        let slashing = AttesterSlashing::from_conflicting_attestations(a.clone(), b.clone())
+            .expect("double vote should be slashable");
+        assert_eq!(slashing.attestation_1, a);
+        assert_eq!(slashing.attestation_2, b);
+    }
+
+    #[test]
+    fn from_conflicting_attestations_surround_vote() {
+        let outer = create_indexed_attestation(4, 1, vec![1]);
+        let inner = create_indexed_attestation(3, 2, vec![1]);
+
+        let slashing =
+            AttesterSlashing::from_conflicting_attestations(inner.clone(), outer.clone())
+                .expect("surround vote should be slashable");
+        assert_eq!(slashing.attestation_1, outer);
+        assert_eq!(slashing.attestation_2, inner);
+    }
+
+    #[test]
+    fn from_conflicting_attestations_not_slashable() {
+        let a = create_indexed_attestation(3, 1, vec![1]);
+        let mut b = create_indexed_attestation(3, 1, vec![2]);
+        b.data.beacon_block_root = Hash256::from_slice(&[1; 32]);
+
+        // No common validator.
+        assert_eq!(
+            AttesterSlashing::from_conflicting_attestations(a.clone(), b),
+            None
+        );
+        // Identical data.
+        assert_eq!(
+            AttesterSlashing::from_conflicting_attestations(a.clone(), a),
+            None
+        );
+    }
+
+    fn create_indexed_attestation(
+        target_epoch: u64,
+        source_epoch: u64,
+        indices: Vec<u64>,
+    ) -> IndexedAttestation {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let mut indexed_vote = IndexedAttestation::random_for_test(&mut rng);
+
+        indexed_vote.custody_bit_0_indices = indices;
+        indexed_vote.custody_bit_1_indices = vec![];
+        indexed_vote.data.source_epoch = Epoch::new(source_epoch);
+        indexed_vote.data.target_epoch = Epoch::new(target_epoch);
+        indexed_vote
+    }
 
     ssz_tests!(AttesterSlashing);
     cached_tree_hash_tests!(AttesterSlashing);
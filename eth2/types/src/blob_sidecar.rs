@@ -0,0 +1,79 @@
+use ssz::{Decode, DecodeError, Encode, SszDecoderBuilder, SszEncoder};
+
+/// The length in bytes of a KZG commitment or proof.
+pub const KZG_BYTES_LEN: usize = 48;
+
+/// A blob published alongside a block, as introduced by EIP-4844.
+///
+/// Encoded as an SSZ container; the blob is the only variable-length field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlobSidecar {
+    /// The position of the blob within its block.
+    pub index: u64,
+    pub blob: Box<[u8]>,
+    pub kzg_commitment: [u8; KZG_BYTES_LEN],
+    pub kzg_proof: [u8; KZG_BYTES_LEN],
+}
+
+impl Encode for BlobSidecar {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        let num_fixed_bytes =
+            <u64 as Encode>::ssz_fixed_len() + ssz::BYTES_PER_LENGTH_OFFSET + 2 * KZG_BYTES_LEN;
+
+        let mut encoder = SszEncoder::container(buf, num_fixed_bytes);
+
+        encoder.append(&self.index);
+        encoder.append(&self.blob.to_vec());
+        encoder.append(&self.kzg_commitment);
+        encoder.append(&self.kzg_proof);
+
+        encoder.finalize();
+    }
+}
+
+impl Decode for BlobSidecar {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut builder = SszDecoderBuilder::new(bytes);
+
+        builder.register_type::<u64>()?;
+        builder.register_type::<Vec<u8>>()?;
+        builder.register_type::<[u8; KZG_BYTES_LEN]>()?;
+        builder.register_type::<[u8; KZG_BYTES_LEN]>()?;
+
+        let mut decoder = builder.build()?;
+
+        Ok(Self {
+            index: decoder.decode_next()?,
+            blob: decoder.decode_next::<Vec<u8>>()?.into_boxed_slice(),
+            kzg_commitment: decoder.decode_next()?,
+            kzg_proof: decoder.decode_next()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn ssz_round_trip() {
+        let sidecar = BlobSidecar {
+            index: 3,
+            blob: vec![1, 2, 3, 4, 5].into_boxed_slice(),
+            kzg_commitment: [7; KZG_BYTES_LEN],
+            kzg_proof: [9; KZG_BYTES_LEN],
+        };
+
+        let bytes = sidecar.as_ssz_bytes();
+
+        assert_eq!(BlobSidecar::from_ssz_bytes(&bytes), Ok(sidecar));
+    }
+}
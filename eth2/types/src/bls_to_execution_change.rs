@@ -0,0 +1,34 @@
+use crate::{test_utils::TestRandom, Address, PublicKey};
+
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use test_random_derive::TestRandom;
+use tree_hash_derive::{CachedTreeHash, TreeHash};
+
+/// A request to change a validator's BLS withdrawal credentials to an execution address.
+#[derive(
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    TreeHash,
+    CachedTreeHash,
+    TestRandom,
+)]
+pub struct BLSToExecutionChange {
+    pub validator_index: u64,
+    /// The key committed to by the validator's present withdrawal credentials.
+    pub from_bls_pubkey: PublicKey,
+    pub to_execution_address: Address,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ssz_tests!(BLSToExecutionChange);
+    cached_tree_hash_tests!(BLSToExecutionChange);
+}
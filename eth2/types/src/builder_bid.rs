@@ -0,0 +1,44 @@
+use crate::{ExecutionPayloadHeader, PublicKey, Signature};
+use ethereum_types::U256;
+
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash::TreeHash;
+use tree_hash_derive::{SignedRoot, TreeHash};
+
+/// A bid from an external block builder (e.g., via an MEV-boost relay) to provide the execution
+/// payload of a block, signed by the builder.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode, TreeHash, SignedRoot)]
+pub struct BuilderBid {
+    pub header: ExecutionPayloadHeader,
+    /// The value of the payload to the proposer, in wei.
+    pub value: U256,
+    pub pubkey: PublicKey,
+    #[signed_root(skip_hashing)]
+    pub signature: Signature,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use crate::Keypair;
+    use ssz::{ssz_encode, Decode};
+
+    #[test]
+    pub fn test_ssz_round_trip() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let keypair = Keypair::random();
+        let original = BuilderBid {
+            header: ExecutionPayloadHeader::random_for_test(&mut rng),
+            value: U256::from(1_000_000_000),
+            pubkey: keypair.pk.clone(),
+            signature: Signature::new(&[42], 0, &keypair.sk),
+        };
+
+        let bytes = ssz_encode(&original);
+        let decoded = BuilderBid::from_ssz_bytes(&bytes).unwrap();
+
+        assert_eq!(original, decoded);
+    }
+}
@@ -15,6 +15,9 @@ pub enum Domain {
     Deposit,
     VoluntaryExit,
     Transfer,
+    BuilderBid,
+    BlsToExecutionChange,
+    SyncCommittee,
 }
 
 /// Holds all the "constants" for a BeaconChain.
@@ -57,6 +60,12 @@ pub struct ChainSpec {
     pub zero_hash: Hash256,
     #[serde(deserialize_with = "u8_from_hex_str", serialize_with = "u8_to_hex_str")]
     pub bls_withdrawal_prefix_byte: u8,
+    #[serde(
+        default = "default_eth1_address_withdrawal_prefix_byte",
+        deserialize_with = "u8_from_hex_str",
+        serialize_with = "u8_to_hex_str"
+    )]
+    pub eth1_address_withdrawal_prefix_byte: u8,
 
     /*
      * Time parameters
@@ -88,6 +97,8 @@ pub struct ChainSpec {
     pub period_committee_root_length: u64,
     pub phase_1_fork_epoch: u64,
     pub phase_1_fork_slot: u64,
+    #[serde(default = "default_max_shard_block_body_size")]
+    pub max_shard_block_body_size: u64,
 
     /*
      * Reward and penalty quotients
@@ -107,6 +118,8 @@ pub struct ChainSpec {
     pub max_deposits: u64,
     pub max_voluntary_exits: u64,
     pub max_transfers: u64,
+    #[serde(default = "default_max_bls_to_execution_changes")]
+    pub max_bls_to_execution_changes: u64,
 
     /*
      * Signature domains
@@ -124,6 +137,12 @@ pub struct ChainSpec {
     domain_deposit: u32,
     domain_voluntary_exit: u32,
     domain_transfer: u32,
+    #[serde(default = "default_domain_builder_bid")]
+    domain_builder_bid: u32,
+    #[serde(default = "default_domain_bls_to_execution_change")]
+    domain_bls_to_execution_change: u32,
+    #[serde(default = "default_domain_sync_committee")]
+    domain_sync_committee: u32,
 
     pub boot_nodes: Vec<String>,
     pub chain_id: u8,
@@ -143,6 +162,9 @@ impl ChainSpec {
             Domain::Deposit => self.domain_deposit,
             Domain::VoluntaryExit => self.domain_voluntary_exit,
             Domain::Transfer => self.domain_transfer,
+            Domain::BuilderBid => self.domain_builder_bid,
+            Domain::BlsToExecutionChange => self.domain_bls_to_execution_change,
+            Domain::SyncCommittee => self.domain_sync_committee,
         };
 
         let mut bytes: Vec<u8> = fork.get_fork_version(epoch).to_vec();
@@ -191,6 +213,7 @@ impl ChainSpec {
             far_future_epoch: Epoch::new(u64::max_value()),
             zero_hash: Hash256::zero(),
             bls_withdrawal_prefix_byte: 0,
+            eth1_address_withdrawal_prefix_byte: default_eth1_address_withdrawal_prefix_byte(),
 
             /*
              * Time parameters
@@ -221,6 +244,7 @@ impl ChainSpec {
             epochs_per_shard_period: 256,
             phase_1_fork_epoch: 600,
             phase_1_fork_slot: 38_400,
+            max_shard_block_body_size: default_max_shard_block_body_size(),
 
             /*
              * Reward and penalty quotients
@@ -240,6 +264,7 @@ impl ChainSpec {
             max_deposits: 16,
             max_voluntary_exits: 16,
             max_transfers: 0,
+            max_bls_to_execution_changes: default_max_bls_to_execution_changes(),
 
             /*
              * Signature domains
@@ -252,6 +277,9 @@ impl ChainSpec {
             domain_deposit: 5,
             domain_voluntary_exit: 6,
             domain_transfer: 7,
+            domain_builder_bid: default_domain_builder_bid(),
+            domain_bls_to_execution_change: default_domain_bls_to_execution_change(),
+            domain_sync_committee: default_domain_sync_committee(),
 
             /*
              * Network specific
@@ -298,6 +326,30 @@ impl Default for ChainSpec {
     }
 }
 
+fn default_eth1_address_withdrawal_prefix_byte() -> u8 {
+    1
+}
+
+fn default_max_bls_to_execution_changes() -> u64 {
+    16
+}
+
+fn default_domain_bls_to_execution_change() -> u32 {
+    9
+}
+
+fn default_domain_builder_bid() -> u32 {
+    8
+}
+
+fn default_max_shard_block_body_size() -> u64 {
+    16_384
+}
+
+fn default_domain_sync_committee() -> u32 {
+    10
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -330,5 +382,33 @@ mod tests {
         test_domain(Domain::Deposit, spec.domain_deposit, &spec);
         test_domain(Domain::VoluntaryExit, spec.domain_voluntary_exit, &spec);
         test_domain(Domain::Transfer, spec.domain_transfer, &spec);
+        test_domain(Domain::BuilderBid, spec.domain_builder_bid, &spec);
+        test_domain(
+            Domain::BlsToExecutionChange,
+            spec.domain_bls_to_execution_change,
+            &spec,
+        );
+        test_domain(Domain::SyncCommittee, spec.domain_sync_committee, &spec);
+    }
+
+    #[test]
+    fn test_deserialize_without_new_fields() {
+        let spec = ChainSpec::minimal();
+
+        let mut value = serde_yaml::to_value(&spec).unwrap();
+        let mapping = value.as_mapping_mut().unwrap();
+        for key in &[
+            "eth1_address_withdrawal_prefix_byte",
+            "max_bls_to_execution_changes",
+            "domain_bls_to_execution_change",
+            "domain_sync_committee",
+            "max_shard_block_body_size",
+            "domain_builder_bid",
+        ] {
+            assert!(mapping.remove(&(*key).into()).is_some());
+        }
+
+        let deserialized: ChainSpec = serde_yaml::from_value(value).unwrap();
+        assert_eq!(deserialized, spec);
     }
 }
@@ -12,6 +12,8 @@ use tree_hash_derive::{CachedTreeHash, TreeHash};
 #[derive(
     Debug,
     PartialEq,
+    Eq,
+    Hash,
     Clone,
     Default,
     Serialize,
@@ -0,0 +1,48 @@
+use crate::test_utils::TestRandom;
+use crate::{Address, Hash256};
+
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use test_random_derive::TestRandom;
+use tree_hash_derive::{CachedTreeHash, TreeHash};
+
+/// The execution-layer block carried by a post-Merge beacon block.
+///
+/// Holds every field of an Engine API `ExecutionPayloadV1`. Transactions are opaque RLP bytes.
+#[derive(
+    Debug,
+    PartialEq,
+    Clone,
+    Default,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    TreeHash,
+    CachedTreeHash,
+    TestRandom,
+)]
+pub struct ExecutionPayload {
+    pub parent_hash: Hash256,
+    pub fee_recipient: Address,
+    pub state_root: Hash256,
+    pub receipts_root: Hash256,
+    pub logs_bloom: Vec<u8>,
+    pub random: Hash256,
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: Vec<u8>,
+    pub base_fee_per_gas: u64,
+    pub block_hash: Hash256,
+    pub transactions: Vec<Vec<u8>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ssz_tests!(ExecutionPayload);
+    cached_tree_hash_tests!(ExecutionPayload);
+}
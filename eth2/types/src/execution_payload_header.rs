@@ -0,0 +1,70 @@
+use crate::test_utils::TestRandom;
+use crate::{Address, ExecutionPayload, Hash256};
+
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use test_random_derive::TestRandom;
+use tree_hash::TreeHash;
+use tree_hash_derive::{CachedTreeHash, TreeHash};
+
+/// The header of an `ExecutionPayload`, as committed to by external block builders.
+///
+/// Identical to `ExecutionPayload`, except that the transactions are replaced by their root.
+#[derive(
+    Debug,
+    PartialEq,
+    Clone,
+    Default,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    TreeHash,
+    CachedTreeHash,
+    TestRandom,
+)]
+pub struct ExecutionPayloadHeader {
+    pub parent_hash: Hash256,
+    pub fee_recipient: Address,
+    pub state_root: Hash256,
+    pub receipts_root: Hash256,
+    pub logs_bloom: Vec<u8>,
+    pub random: Hash256,
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: Vec<u8>,
+    pub base_fee_per_gas: u64,
+    pub block_hash: Hash256,
+    pub transactions_root: Hash256,
+}
+
+impl<'a> From<&'a ExecutionPayload> for ExecutionPayloadHeader {
+    fn from(payload: &'a ExecutionPayload) -> Self {
+        Self {
+            parent_hash: payload.parent_hash,
+            fee_recipient: payload.fee_recipient,
+            state_root: payload.state_root,
+            receipts_root: payload.receipts_root,
+            logs_bloom: payload.logs_bloom.clone(),
+            random: payload.random,
+            block_number: payload.block_number,
+            gas_limit: payload.gas_limit,
+            gas_used: payload.gas_used,
+            timestamp: payload.timestamp,
+            extra_data: payload.extra_data.clone(),
+            base_fee_per_gas: payload.base_fee_per_gas,
+            block_hash: payload.block_hash,
+            transactions_root: Hash256::from_slice(&payload.transactions.tree_hash_root()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ssz_tests!(ExecutionPayloadHeader);
+    cached_tree_hash_tests!(ExecutionPayloadHeader);
+}
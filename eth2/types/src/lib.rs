@@ -7,17 +7,23 @@ pub mod attestation;
 pub mod attestation_data;
 pub mod attestation_data_and_custody_bit;
 pub mod attestation_duty;
+pub mod attestation_subnet_flags;
 pub mod attester_slashing;
 pub mod beacon_block;
 pub mod beacon_block_body;
 pub mod beacon_block_header;
 pub mod beacon_state;
+pub mod blob_sidecar;
+pub mod bls_to_execution_change;
+pub mod builder_bid;
 pub mod chain_spec;
 pub mod crosslink;
 pub mod crosslink_committee;
 pub mod deposit;
 pub mod deposit_data;
 pub mod eth1_data;
+pub mod execution_payload;
+pub mod execution_payload_header;
 pub mod fork;
 pub mod free_attestation;
 pub mod historical_batch;
@@ -32,6 +38,7 @@ pub mod shard_block_header;
 pub mod shard_committee;
 pub mod shard_pending_attestation;
 pub mod shard_state;
+pub mod signed_bls_to_execution_change;
 pub mod transfer;
 pub mod voluntary_exit;
 #[macro_use]
@@ -50,17 +57,23 @@ pub use crate::attestation::Attestation;
 pub use crate::attestation_data::AttestationData;
 pub use crate::attestation_data_and_custody_bit::AttestationDataAndCustodyBit;
 pub use crate::attestation_duty::AttestationDuty;
+pub use crate::attestation_subnet_flags::{AttestationSubnetFlags, ATTESTATION_SUBNET_COUNT};
 pub use crate::attester_slashing::AttesterSlashing;
 pub use crate::beacon_block::BeaconBlock;
 pub use crate::beacon_block_body::BeaconBlockBody;
 pub use crate::beacon_block_header::BeaconBlockHeader;
 pub use crate::beacon_state::{Error as BeaconStateError, *};
+pub use crate::blob_sidecar::BlobSidecar;
+pub use crate::bls_to_execution_change::BLSToExecutionChange;
+pub use crate::builder_bid::BuilderBid;
 pub use crate::chain_spec::{ChainSpec, Domain};
 pub use crate::crosslink::Crosslink;
 pub use crate::crosslink_committee::{CrosslinkCommittee, OwnedCrosslinkCommittee};
 pub use crate::deposit::Deposit;
 pub use crate::deposit_data::DepositData;
 pub use crate::eth1_data::Eth1Data;
+pub use crate::execution_payload::ExecutionPayload;
+pub use crate::execution_payload_header::ExecutionPayloadHeader;
 pub use crate::fork::Fork;
 pub use crate::free_attestation::FreeAttestation;
 pub use crate::historical_batch::HistoricalBatch;
@@ -78,6 +91,7 @@ pub use crate::shard_block_header::ShardBlockHeader;
 pub use crate::shard_committee::ShardCommittee;
 pub use crate::shard_pending_attestation::ShardPendingAttestation;
 pub use crate::shard_state::{Error as ShardStateError, *};
+pub use crate::signed_bls_to_execution_change::SignedBLSToExecutionChange;
 pub use crate::slot_epoch::{Epoch, ShardSlot, Slot};
 pub use crate::slot_height::{ShardSlotHeight, SlotHeight};
 pub use crate::transfer::Transfer;
@@ -32,6 +32,15 @@ pub struct ShardAttestation {
 }
 
 impl ShardAttestation {
+    /// Is the aggregation bitfield sized for a committee of `committee_size`, with no bits set
+    /// beyond the last committee member?
+    pub fn committee_bitfield_is_valid(&self, committee_size: usize) -> bool {
+        let bitfield = &self.aggregation_bitfield;
+
+        bitfield.num_bytes() == (committee_size + 7) / 8
+            && (committee_size..bitfield.len()).all(|i| !bitfield.get(i).unwrap_or(false))
+    }
+
     /// Are the aggregation bitfields of these attestations disjoint?
     pub fn signers_disjoint_from(&self, other: &ShardAttestation) -> bool {
         self.aggregation_bitfield
@@ -58,4 +67,40 @@ mod tests {
 
     ssz_tests!(ShardAttestation);
     cached_tree_hash_tests!(ShardAttestation);
+
+    fn attestation_with_bitfield(bytes: &[u8]) -> ShardAttestation {
+        ShardAttestation {
+            aggregation_bitfield: Bitfield::from_bytes(bytes),
+            ..ShardAttestation::default()
+        }
+    }
+
+    #[test]
+    fn committee_bitfield_exactly_right() {
+        // The bytes are big-endian, so bits 8 and 9 are in the first byte.
+        let attestation = attestation_with_bitfield(&[0b0000_0011, 0b0000_1111]);
+
+        assert!(attestation.committee_bitfield_is_valid(10));
+        assert!(attestation.committee_bitfield_is_valid(16));
+    }
+
+    #[test]
+    fn committee_bitfield_too_short() {
+        let attestation = attestation_with_bitfield(&[0b0000_1111]);
+
+        assert!(!attestation.committee_bitfield_is_valid(9));
+        assert!(!attestation.committee_bitfield_is_valid(16));
+    }
+
+    #[test]
+    fn committee_bitfield_too_long() {
+        // Too many bytes, even though no bits are set beyond the committee.
+        let attestation = attestation_with_bitfield(&[0b0000_1111, 0b0000_0000]);
+        assert!(!attestation.committee_bitfield_is_valid(4));
+
+        // The right number of bytes, with a trailing bit set beyond the committee.
+        let attestation = attestation_with_bitfield(&[0b0001_1111]);
+        assert!(attestation.committee_bitfield_is_valid(5));
+        assert!(!attestation.committee_bitfield_is_valid(4));
+    }
 }
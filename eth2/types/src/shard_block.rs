@@ -1,6 +1,8 @@
 use crate::test_utils::TestRandom;
 use crate::*;
 use bls::Signature;
+use ethereum_types::H256;
+use hashing::hash;
 
 use serde_derive::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
@@ -35,6 +37,13 @@ pub struct ShardBlock {
 }
 
 impl ShardBlock {
+    /// The SHA-256 hash of an empty block body.
+    pub const EMPTY_BODY_HASH: Hash256 = H256([
+        0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8, 0x99, 0x6f, 0xb9,
+        0x24, 0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95, 0x99, 0x1b, 0x78, 0x52,
+        0xb8, 0x55,
+    ]);
+
     pub fn empty(spec: &ChainSpec, shard: u64) -> ShardBlock {
         ShardBlock {
             shard,
@@ -48,6 +57,16 @@ impl ShardBlock {
         }
     }
 
+    /// Returns the SHA-256 hash of `self.body`.
+    pub fn body_root(&self) -> Hash256 {
+        Hash256::from_slice(&hash(&self.body))
+    }
+
+    /// Returns `true` if `self.body` is no larger than `spec.max_shard_block_body_size`.
+    pub fn body_size_is_valid(&self, spec: &ChainSpec) -> bool {
+        self.body.len() <= spec.max_shard_block_body_size as usize
+    }
+
     pub fn canonical_root(&self) -> Hash256 {
         Hash256::from_slice(&self.signed_root()[..])
     }
@@ -73,3 +92,31 @@ impl ShardBlock {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_body_root() {
+        let spec = ChainSpec::mainnet();
+
+        assert_eq!(
+            ShardBlock::empty(&spec, 0).body_root(),
+            ShardBlock::EMPTY_BODY_HASH
+        );
+    }
+
+    #[test]
+    fn body_size_is_valid() {
+        let spec = ChainSpec::mainnet();
+        let max_body_size = spec.max_shard_block_body_size as usize;
+        let mut block = ShardBlock::empty(&spec, 0);
+
+        block.body = vec![0; max_body_size];
+        assert!(block.body_size_is_valid(&spec));
+
+        block.body = vec![0; max_body_size + 1];
+        assert!(!block.body_size_is_valid(&spec));
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{test_utils::TestRandom, BLSToExecutionChange, Signature};
+
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use test_random_derive::TestRandom;
+use tree_hash_derive::{CachedTreeHash, TreeHash};
+
+/// A `BLSToExecutionChange`, signed by the `from_bls_pubkey` it names over the root of the
+/// message.
+#[derive(
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    TreeHash,
+    CachedTreeHash,
+    TestRandom,
+)]
+pub struct SignedBLSToExecutionChange {
+    pub message: BLSToExecutionChange,
+    pub signature: Signature,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ssz_tests!(SignedBLSToExecutionChange);
+    cached_tree_hash_tests!(SignedBLSToExecutionChange);
+}
@@ -26,6 +26,12 @@ impl TestRandom for u64 {
     }
 }
 
+impl TestRandom for u8 {
+    fn random_for_test(rng: &mut impl RngCore) -> Self {
+        rng.next_u32() as u8
+    }
+}
+
 impl TestRandom for u32 {
     fn random_for_test(rng: &mut impl RngCore) -> Self {
         rng.next_u32()
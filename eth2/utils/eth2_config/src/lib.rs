@@ -37,6 +37,30 @@ impl Eth2Config {
             spec: ChainSpec::minimal(),
         }
     }
+
+    /// The Pyrmont testnet, which uses the mainnet spec constants.
+    pub fn pyrmont() -> Self {
+        let mut spec = ChainSpec::mainnet();
+        spec.genesis_time = 1_605_700_807;
+        spec.chain_id = 3; // pyrmont chain id
+
+        Self {
+            spec_constants: "mainnet".to_string(),
+            spec,
+        }
+    }
+
+    /// The Prater testnet, which uses the mainnet spec constants.
+    pub fn prater() -> Self {
+        let mut spec = ChainSpec::mainnet();
+        spec.genesis_time = 1_616_508_000;
+        spec.chain_id = 4; // prater chain id
+
+        Self {
+            spec_constants: "mainnet".to_string(),
+            spec,
+        }
+    }
 }
 
 impl Eth2Config {
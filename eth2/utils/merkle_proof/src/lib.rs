@@ -20,7 +20,9 @@ pub fn verify_merkle_proof(
 }
 
 /// Compute a root hash from a leaf and a Merkle proof.
-fn merkle_root_from_branch(leaf: H256, branch: &[H256], depth: usize, index: usize) -> H256 {
+///
+/// Panics if `branch.len() != depth`.
+pub fn merkle_root_from_branch(leaf: H256, branch: &[H256], depth: usize, index: usize) -> H256 {
     assert_eq!(branch.len(), depth, "proof length should equal depth");
 
     let mut merkle_root = leaf.as_bytes().to_vec();
@@ -40,23 +42,60 @@ fn merkle_root_from_branch(leaf: H256, branch: &[H256], depth: usize, index: usi
     H256::from_slice(&merkle_root)
 }
 
+/// Compute the Merkle branch for the leaf at `index` of a tree of the given `depth`, returning the
+/// branch (in the bottom-up order expected by `verify_merkle_proof`) and the root of the tree.
+///
+/// Leaves beyond `leaves.len()` are zero hashes. Only the non-zero part of the tree is stored, so
+/// deep, sparse trees (like the deposit tree) are cheap.
+pub fn merkle_branch(leaves: &[H256], index: usize, depth: usize) -> (Vec<H256>, H256) {
+    let capacity = 1_usize
+        .checked_shl(depth as u32)
+        .unwrap_or_else(usize::max_value);
+    assert!(leaves.len() <= capacity, "too many leaves for depth");
+    assert!(index < capacity, "index out of bounds for depth");
+
+    let mut layer = leaves.to_vec();
+    // The root of an all-zero subtree at the current height.
+    let mut zero_hash = H256::zero();
+
+    let mut branch = Vec::with_capacity(depth);
+    let mut index = index;
+
+    for _ in 0..depth {
+        branch.push(layer.get(index ^ 1).cloned().unwrap_or(zero_hash));
+
+        if layer.len() % 2 == 1 {
+            layer.push(zero_hash);
+        }
+        layer = layer
+            .chunks(2)
+            .map(|pair| hash_concat(pair[0], pair[1]))
+            .collect();
+        zero_hash = hash_concat(zero_hash, zero_hash);
+        index /= 2;
+    }
+
+    (branch, layer.first().cloned().unwrap_or(zero_hash))
+}
+
 /// Concatenate two vectors.
 fn concat(mut vec1: Vec<u8>, mut vec2: Vec<u8>) -> Vec<u8> {
     vec1.append(&mut vec2);
     vec1
 }
 
+/// Hash the concatenation of two nodes.
+fn hash_concat(h1: H256, h2: H256) -> H256 {
+    H256::from_slice(&hash(&concat(
+        h1.as_bytes().to_vec(),
+        h2.as_bytes().to_vec(),
+    )))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn hash_concat(h1: H256, h2: H256) -> H256 {
-        H256::from_slice(&hash(&concat(
-            h1.as_bytes().to_vec(),
-            h2.as_bytes().to_vec(),
-        )))
-    }
-
     #[test]
     fn verify_small_example() {
         // Construct a small merkle tree manually
@@ -138,6 +177,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn merkle_branch_verifies() {
+        let leaves: Vec<H256> = (1..=5).map(|i| H256::from([i; 32])).collect();
+        let depth = 3;
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let (branch, root) = merkle_branch(&leaves, index, depth);
+            assert!(verify_merkle_proof(*leaf, &branch, depth, index, root));
+        }
+
+        // Padding leaves are zero.
+        let (branch, root) = merkle_branch(&leaves, 7, depth);
+        assert!(verify_merkle_proof(H256::zero(), &branch, depth, 7, root));
+
+        // Sparse trees are not materialised.
+        let (branch, root) = merkle_branch(&leaves, 4, 32);
+        assert!(verify_merkle_proof(leaves[4], &branch, 32, 4, root));
+    }
+
     #[test]
     fn verify_zero_depth() {
         let leaf = H256::from([0xD6; 32]);
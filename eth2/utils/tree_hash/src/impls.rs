@@ -1,6 +1,6 @@
 use super::*;
 use crate::merkle_root;
-use ethereum_types::H256;
+use ethereum_types::{H160, H256, U256};
 use hashing::hash;
 use int_to_bytes::int_to_bytes32;
 
@@ -76,6 +76,24 @@ macro_rules! impl_for_u8_array {
 impl_for_u8_array!(4);
 impl_for_u8_array!(32);
 
+impl TreeHash for H160 {
+    fn tree_hash_type() -> TreeHashType {
+        TreeHashType::Vector
+    }
+
+    fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        1
+    }
+
+    fn tree_hash_root(&self) -> Vec<u8> {
+        merkle_root(&self.as_bytes().to_vec())
+    }
+}
+
 impl TreeHash for H256 {
     fn tree_hash_type() -> TreeHashType {
         TreeHashType::Vector
@@ -94,6 +112,26 @@ impl TreeHash for H256 {
     }
 }
 
+impl TreeHash for U256 {
+    fn tree_hash_type() -> TreeHashType {
+        TreeHashType::Basic
+    }
+
+    fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+        let mut bytes = vec![0; HASHSIZE];
+        self.to_little_endian(&mut bytes);
+        bytes
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        1
+    }
+
+    fn tree_hash_root(&self) -> Vec<u8> {
+        self.tree_hash_packed_encoding()
+    }
+}
+
 macro_rules! impl_for_list {
     ($type: ty) => {
         impl<T> TreeHash for $type
@@ -170,4 +208,22 @@ mod test {
         assert_eq!(false.tree_hash_root(), false_bytes);
     }
 
+    #[test]
+    fn u256() {
+        let mut bytes = vec![0; 32];
+        bytes[0] = 42;
+        bytes[31] = 1;
+
+        let value = U256::from(42) + (U256::from(1) << 248);
+
+        assert_eq!(value.tree_hash_root(), bytes);
+    }
+
+    #[test]
+    fn h160() {
+        let mut bytes = vec![0; 32];
+        bytes[..20].copy_from_slice(&[7; 20]);
+
+        assert_eq!(H160::from([7; 20]).tree_hash_root(), bytes);
+    }
 }
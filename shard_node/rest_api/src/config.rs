@@ -10,6 +10,13 @@ pub struct Config {
     pub listen_address: Ipv4Addr,
     /// The port the REST API HTTP server will listen on.
     pub port: u16,
+    /// Origins permitted to make cross-origin requests. A trailing `*` matches any suffix.
+    #[serde(default = "default_cors_allowed_origins")]
+    pub cors_allowed_origins: Vec<String>,
+}
+
+fn default_cors_allowed_origins() -> Vec<String> {
+    vec!["http://localhost:*".to_string()]
 }
 
 impl Default for Config {
@@ -18,6 +25,7 @@ impl Default for Config {
             enabled: true,
             listen_address: Ipv4Addr::new(127, 0, 0, 1),
             port: 5052,
+            cors_allowed_origins: default_cors_allowed_origins(),
         }
     }
 }
@@ -0,0 +1,126 @@
+use http::header::{self, HeaderValue};
+use hyper::{Body, Request, Response, StatusCode};
+
+const ALLOWED_METHODS: &str = "GET, POST, OPTIONS";
+const ALLOWED_HEADERS: &str = "Content-Type";
+
+/// Returns the `Origin` header of `req`, if it matches one of `allowed_origins`.
+///
+/// An allowed origin of `*` matches any origin and a trailing `*` matches any suffix (e.g.,
+/// `http://localhost:*` matches `http://localhost:8080`).
+pub fn allowed_origin(allowed_origins: &[String], req: &Request<Body>) -> Option<String> {
+    let origin = req.headers().get(header::ORIGIN)?.to_str().ok()?;
+
+    let is_allowed = allowed_origins
+        .iter()
+        .any(|allowed| match allowed.find('*') {
+            Some(i) if i == allowed.len() - 1 => origin.starts_with(&allowed[..i]),
+            _ => allowed == origin,
+        });
+
+    if is_allowed {
+        Some(origin.to_string())
+    } else {
+        None
+    }
+}
+
+/// Adds the CORS headers to `response` if `origin` is allowed.
+pub fn add_headers(mut response: Response<Body>, origin: Option<String>) -> Response<Body> {
+    if let Some(value) = origin.and_then(|origin| HeaderValue::from_str(&origin).ok()) {
+        let headers = response.headers_mut();
+        headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, value);
+        headers.insert(
+            header::ACCESS_CONTROL_ALLOW_METHODS,
+            HeaderValue::from_static(ALLOWED_METHODS),
+        );
+        headers.insert(
+            header::ACCESS_CONTROL_ALLOW_HEADERS,
+            HeaderValue::from_static(ALLOWED_HEADERS),
+        );
+        headers.insert(header::VARY, HeaderValue::from_static("Origin"));
+    }
+
+    response
+}
+
+/// Returns the `204 No Content` response to an `OPTIONS` preflight request.
+pub fn preflight_response(origin: Option<String>) -> Response<Body> {
+    let response = Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Body::empty())
+        .expect("Response should always be created.");
+
+    add_headers(response, origin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_from(origin: &str) -> Request<Body> {
+        Request::builder()
+            .uri("/shard/0/state")
+            .header(header::ORIGIN, origin)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    fn allowed() -> Vec<String> {
+        vec![
+            "http://localhost:*".to_string(),
+            "https://a.com".to_string(),
+        ]
+    }
+
+    #[test]
+    fn matches_allowed_origins() {
+        assert_eq!(
+            allowed_origin(&allowed(), &request_from("http://localhost:8080")),
+            Some("http://localhost:8080".to_string())
+        );
+        assert_eq!(
+            allowed_origin(&allowed(), &request_from("https://a.com")),
+            Some("https://a.com".to_string())
+        );
+        assert_eq!(
+            allowed_origin(&allowed(), &request_from("https://a.com.evil")),
+            None
+        );
+        assert_eq!(
+            allowed_origin(&allowed(), &request_from("http://example.com")),
+            None
+        );
+        assert_eq!(
+            allowed_origin(&["*".to_string()], &request_from("http://example.com")),
+            Some("http://example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn preflight_has_cors_headers() {
+        let origin = allowed_origin(&allowed(), &request_from("http://localhost:8080"));
+        let response = preflight_response(origin);
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "http://localhost:8080"
+        );
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_METHODS],
+            ALLOWED_METHODS
+        );
+    }
+
+    #[test]
+    fn disallowed_origin_has_no_cors_headers() {
+        let origin = allowed_origin(&allowed(), &request_from("http://example.com"));
+        let response = add_headers(Response::new(Body::empty()), origin);
+
+        assert!(response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+}
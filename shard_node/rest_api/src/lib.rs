@@ -4,6 +4,7 @@ mod macros;
 extern crate lazy_static;
 
 mod config;
+mod cors;
 mod error;
 mod helpers;
 mod response_builder;
@@ -28,6 +29,7 @@ type BoxFut = Box<dyn Future<Item = Response<Body>, Error = ApiError> + Send>;
 pub struct ApiService<T: ShardChainTypes + 'static, L: BeaconChainTypes + 'static> {
     log: slog::Logger,
     shard_chain: Arc<ShardChain<T, L>>,
+    cors_allowed_origins: Arc<Vec<String>>,
 }
 
 fn into_boxfut<F: IntoFuture + 'static>(item: F) -> BoxFut
@@ -51,18 +53,28 @@ impl<T: ShardChainTypes, L: BeaconChainTypes> Service for ApiService<T, L> {
             .insert::<Arc<ShardChain<T, L>>>(self.shard_chain.clone());
 
         let path = req.uri().path().to_string();
+        let origin = cors::allowed_origin(&self.cors_allowed_origins, &req);
+
+        if req.method() == Method::OPTIONS {
+            return into_boxfut(Ok(cors::preflight_response(origin)));
+        }
 
         // errors are not being converted at the moment - so any validation error
         // will take down the server. There is a PR in progress to fix this issue:
         // https://github.com/sigp/lighthouse/pull/537
-        match (req.method(), path.as_ref()) {
+        let response = match (req.method(), path.as_ref()) {
             (&Method::GET, "/shard/0/state") => into_boxfut(shard::get_state::<T, L>(req)),
             (&Method::GET, "/shard/0/block") => into_boxfut(shard::get_block::<T, L>(req)),
+            (&Method::GET, "/shard/0/pool_stats") => {
+                into_boxfut(shard::get_pool_stats::<T, L>(req))
+            }
             (&Method::POST, "/shard/0/block_body") => shard::process_block_body::<T, L>(req),
             _ => Box::new(futures::future::err(ApiError::NotFound(
                 "Request path and/or method not found.".to_owned(),
             ))),
-        }
+        };
+
+        Box::new(response.map(move |response| cors::add_headers(response, origin)))
     }
 }
 
@@ -80,11 +92,13 @@ pub fn start_server<T: ShardChainTypes + 'static, L: BeaconChainTypes + 'static>
     // Clone our stateful objects, for use in service closure.
     let server_log = log.clone();
     let server_sc = shard_chain.clone();
+    let server_cors_allowed_origins = Arc::new(config.cors_allowed_origins.clone());
 
     let service = move || -> futures::future::FutureResult<ApiService<T, L>, String> {
         futures::future::ok(ApiService {
             log: server_log.clone(),
             shard_chain: server_sc.clone(),
+            cors_allowed_origins: server_cors_allowed_origins.clone(),
         })
     };
 
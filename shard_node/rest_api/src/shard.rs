@@ -34,6 +34,18 @@ pub fn get_block<T: ShardChainTypes + 'static, L: BeaconChainTypes + 'static>(
     ResponseBuilder::new(&req)?.body(&current_block.clone())
 }
 
+pub fn get_pool_stats<T: ShardChainTypes + 'static, L: BeaconChainTypes + 'static>(
+    req: Request<Body>,
+) -> ApiResult {
+    let log = get_logger_from_request(&req);
+    info!(log, "REST_API: Operation pool stats requested");
+
+    let shard_chain = get_shard_chain_from_request::<T, L>(&req)?;
+    let stats = shard_chain.op_pool.stats();
+
+    ResponseBuilder::new(&req)?.body_no_ssz(&stats)
+}
+
 #[derive(Deserialize, Debug)]
 struct BlockBodyRequest {
     block_body: String,
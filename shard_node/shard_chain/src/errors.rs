@@ -51,6 +51,11 @@ pub enum BlockProductionError {
     BlockProcessingError(ShardBlockProcessingError),
     ShardStateError(ShardStateError),
     BeaconStateError(BeaconStateError),
+    /// The pending body in the operation pool exceeds `spec.max_shard_block_body_size`.
+    BodyTooLarge {
+        body_size: usize,
+        max_body_size: u64,
+    },
 }
 
 easy_from_to!(ShardBlockProcessingError, BlockProductionError);
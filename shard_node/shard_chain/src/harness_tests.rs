@@ -12,6 +12,19 @@ pub const VALIDATOR_COUNT: usize = 24;
 pub type TestBeaconForkChoice = ThreadSafeReducedTree<MemoryStore, MinimalEthSpec>;
 pub type TestShardForkChoice = ShardThreadSafeReducedTree<ShardMemoryStore, MinimalShardSpec>;
 
+fn get_harness_at_phase_1(
+    validator_count: usize,
+) -> ShardChainHarness<TestBeaconForkChoice, MinimalEthSpec, TestShardForkChoice, MinimalShardSpec>
+{
+    let harness = get_harness(validator_count);
+    let num_blocks_produced =
+        harness.beacon_spec.slots_per_epoch * harness.beacon_spec.phase_1_fork_epoch;
+
+    harness.extend_beacon_chain(num_blocks_produced as usize);
+
+    harness
+}
+
 fn get_harness(
     validator_count: usize,
 ) -> ShardChainHarness<TestBeaconForkChoice, MinimalEthSpec, TestShardForkChoice, MinimalShardSpec>
@@ -52,3 +65,70 @@ fn advance_shard_slot() {
         harness.extend_shard_chain(1);
     }
 }
+
+#[test]
+fn is_canonical() {
+    let harness = get_harness_at_phase_1(VALIDATOR_COUNT);
+
+    // Leave an empty slot between the blocks at `before_gap` and `after_gap`.
+    let before_gap_root = harness.extend_shard_chain(3);
+    harness.advance_shard_slot();
+    harness.advance_shard_slot();
+    let after_gap_root = harness.extend_shard_chain(1);
+    let head_root = harness.extend_shard_chain(1);
+
+    let get_block = |root| harness.shard_chain.get_block(&root).unwrap().unwrap();
+    let before_gap = get_block(before_gap_root);
+    let after_gap = get_block(after_gap_root);
+    let head = get_block(head_root);
+
+    assert_eq!(after_gap.parent_root, before_gap_root);
+    assert_eq!(after_gap.slot, before_gap.slot + 2);
+
+    for block in &[
+        &head,
+        &after_gap,
+        &before_gap,
+        &get_block(before_gap.parent_root),
+    ] {
+        assert_eq!(
+            harness.shard_chain.is_canonical(block),
+            Ok(true),
+            "slot {}",
+            block.slot
+        );
+    }
+}
+
+#[test]
+fn is_canonical_orphaned_block() {
+    let harness = get_harness_at_phase_1(VALIDATOR_COUNT);
+
+    let head_root = harness.extend_shard_chain(3);
+    let head = harness.shard_chain.get_block(&head_root).unwrap().unwrap();
+
+    let mut orphan = harness
+        .shard_chain
+        .get_block(&head.parent_root)
+        .unwrap()
+        .unwrap();
+    orphan.body = vec![42];
+
+    assert_eq!(harness.shard_chain.is_canonical(&orphan), Ok(false));
+}
+
+#[test]
+fn is_canonical_skipped_slot() {
+    let harness = get_harness_at_phase_1(VALIDATOR_COUNT);
+
+    harness.extend_shard_chain(1);
+    harness.advance_shard_slot();
+    let skipped_slot = harness.shard_chain.read_slot_clock().unwrap();
+    harness.advance_shard_slot();
+    let head_root = harness.extend_shard_chain(1);
+
+    let mut block = harness.shard_chain.get_block(&head_root).unwrap().unwrap();
+    block.slot = skipped_slot;
+
+    assert_eq!(harness.shard_chain.is_canonical(&block), Ok(false));
+}
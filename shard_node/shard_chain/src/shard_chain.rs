@@ -188,6 +188,26 @@ impl<T: ShardChainTypes, L: BeaconChainTypes> ShardChain<T, L> {
         Ok(self.store.get(block_root)?)
     }
 
+    /// Returns `true` if `block` is an ancestor of (or equal to) the canonical head.
+    ///
+    /// Returns `false` if the block is on another fork, or if the canonical chain skipped the slot
+    /// of `block`.
+    pub fn is_canonical(&self, block: &ShardBlock) -> Result<bool, Error> {
+        let block_root = block.canonical_root();
+        let mut root = self.head().shard_block_root;
+
+        loop {
+            if root == block_root {
+                return Ok(true);
+            }
+
+            match self.get_block(&root)? {
+                Some(ancestor) if ancestor.slot > block.slot => root = ancestor.parent_root,
+                _ => return Ok(false),
+            }
+        }
+    }
+
     pub fn get_block_root_at_epoch(&self, epoch: Epoch) -> Result<Option<Hash256>, Error> {
         let spec = &self.spec;
 
@@ -390,12 +410,24 @@ impl<T: ShardChainTypes, L: BeaconChainTypes> ShardChain<T, L> {
     /// Accept a new attestation from the network.
     ///
     /// If valid, the attestation is added to the `op_pool` and aggregated with another attestation
-    /// if possible.
+    /// if possible. Attestations for an unknown committee, or whose bitfield does not fit the
+    /// committee, are dropped.
     pub fn process_attestation(&self, attestation: ShardAttestation) -> () {
+        let spec = &self.spec;
+        let epoch = attestation
+            .data
+            .target_slot
+            .epoch(spec.slots_per_epoch, spec.shard_slots_per_beacon_slot);
+        let committee_size = match self.shard_committee(epoch) {
+            Ok(committee) => committee.committee.len(),
+            Err(_) => return,
+        };
+
         self.op_pool.insert_attestation(
             attestation,
+            committee_size,
             &self.parent_beacon.current_state(),
-            &self.spec,
+            spec,
         );
     }
 
@@ -579,6 +611,13 @@ impl<T: ShardChainTypes, L: BeaconChainTypes> ShardChain<T, L> {
             signature: Signature::empty_signature(),
         };
 
+        if !block.body_size_is_valid(spec) {
+            return Err(BlockProductionError::BodyTooLarge {
+                body_size: block.body.len(),
+                max_body_size: spec.max_shard_block_body_size,
+            });
+        }
+
         per_shard_block_processing(&beacon_state, &mut state, &block, spec);
 
         let state_root = state.canonical_root();
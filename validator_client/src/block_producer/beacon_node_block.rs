@@ -22,6 +22,7 @@ pub trait BeaconNodeBlock: Send + Sync {
         &self,
         slot: Slot,
         randao_reveal: &Signature,
+        graffiti: &[u8; 32],
     ) -> Result<Option<BeaconBlock>, BeaconNodeError>;
 
     /// Request that the node publishes a block.
@@ -50,6 +50,8 @@ pub struct BlockProducer<'a, B: BeaconNodeBlock, S: Signer> {
     pub signer: &'a S,
     /// Used for caclulating epoch.
     pub slots_per_epoch: u64,
+    /// The graffiti to include in the block.
+    pub graffiti: [u8; 32],
 }
 
 impl<'a, B: BeaconNodeBlock, S: Signer> BlockProducer<'a, B, S> {
@@ -97,9 +99,9 @@ impl<'a, B: BeaconNodeBlock, S: Signer> BlockProducer<'a, B, S> {
             Some(signature) => signature,
         };
 
-        if let Some(block) = self
-            .beacon_node
-            .produce_beacon_block(self.slot, &randao_reveal)?
+        if let Some(block) =
+            self.beacon_node
+                .produce_beacon_block(self.slot, &randao_reveal, &self.graffiti)?
         {
             if self.safe_to_produce(&block) {
                 let domain = self
@@ -1,6 +1,7 @@
 use bincode;
 use bls::Keypair;
 use clap::ArgMatches;
+use serde::{de, ser, Deserialize, Deserializer, Serializer};
 use serde_derive::{Deserialize, Serialize};
 use slog::{debug, error, info, o, Drain};
 use std::fs::{self, File, OpenOptions};
@@ -20,10 +21,60 @@ pub struct Config {
     pub server: String,
     /// The number of slots per epoch.
     pub slots_per_epoch: u64,
+    /// The graffiti included in blocks produced by this validator, stored as a string.
+    #[serde(
+        default = "default_graffiti",
+        serialize_with = "serialize_graffiti",
+        deserialize_with = "deserialize_graffiti"
+    )]
+    pub graffiti: [u8; 32],
 }
 
 const DEFAULT_PRIVATE_KEY_FILENAME: &str = "private.key";
 
+/// The graffiti used when none is configured, zero-padded to 32 bytes.
+pub const DEFAULT_GRAFFITI: &str = "Lighthouse";
+
+/// Zero-pads `graffiti` to 32 bytes, returning `None` if it is too long.
+fn graffiti_from_str(graffiti: &str) -> Option<[u8; 32]> {
+    let bytes = graffiti.as_bytes();
+    let mut padded = [0; 32];
+
+    if bytes.len() > padded.len() {
+        return None;
+    }
+    padded[..bytes.len()].copy_from_slice(bytes);
+
+    Some(padded)
+}
+
+fn default_graffiti() -> [u8; 32] {
+    graffiti_from_str(DEFAULT_GRAFFITI).expect("default graffiti is valid")
+}
+
+/// Serializes `graffiti` as a string, without its zero-padding.
+fn serialize_graffiti<S: Serializer>(
+    graffiti: &[u8; 32],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let len = graffiti
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i + 1);
+    let graffiti = std::str::from_utf8(&graffiti[..len])
+        .map_err(|_| ser::Error::custom("Graffiti is not valid UTF-8"))?;
+
+    serializer.serialize_str(graffiti)
+}
+
+/// Deserializes a graffiti string, zero-padding it to 32 bytes.
+fn deserialize_graffiti<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+    let graffiti = String::deserialize(deserializer)?;
+
+    graffiti_from_str(&graffiti)
+        .ok_or_else(|| de::Error::custom("Graffiti must be at most 32 bytes"))
+}
+
 impl Default for Config {
     /// Build a new configuration from defaults.
     fn default() -> Self {
@@ -32,6 +83,7 @@ impl Default for Config {
             log_file: PathBuf::from(""),
             server: "localhost:5051".to_string(),
             slots_per_epoch: MainnetEthSpec::slots_per_epoch(),
+            graffiti: default_graffiti(),
         }
     }
 }
@@ -59,6 +111,11 @@ impl Config {
             self.server = srv.to_string();
         };
 
+        if let Some(graffiti) = args.value_of("graffiti") {
+            self.graffiti =
+                graffiti_from_str(graffiti).ok_or("Graffiti must be at most 32 bytes")?;
+        };
+
         Ok(())
     }
 
@@ -173,3 +230,33 @@ impl Config {
         Ok(key_path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graffiti_is_stored_as_a_string() {
+        let mut config = Config::default();
+        config.graffiti = graffiti_from_str("hello").unwrap();
+
+        let encoded = toml::to_string(&config).unwrap();
+        assert!(encoded.contains("graffiti = \"hello\""));
+
+        let decoded: Config = toml::from_str(&encoded).unwrap();
+        assert_eq!(decoded.graffiti, config.graffiti);
+    }
+
+    #[test]
+    fn graffiti_defaults_when_missing() {
+        let encoded: String = toml::to_string(&Config::default())
+            .unwrap()
+            .lines()
+            .filter(|line| !line.starts_with("graffiti"))
+            .map(|line| format!("{}\n", line))
+            .collect();
+
+        let decoded: Config = toml::from_str(&encoded).unwrap();
+        assert_eq!(decoded.graffiti, default_graffiti());
+    }
+}
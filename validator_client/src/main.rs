@@ -63,6 +63,13 @@ fn main() {
                 .help("Address to connect to BeaconNode.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("graffiti")
+                .long("graffiti")
+                .value_name("GRAFFITI")
+                .help("Graffiti to include in produced blocks (at most 32 bytes).")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("spec-constants")
                 .long("spec-constants")
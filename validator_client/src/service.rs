@@ -58,6 +58,8 @@ pub struct Service<B: BeaconNodeDuties + 'static, S: Signer + 'static> {
     beacon_block_client: Arc<BeaconBlockGrpcClient>,
     /// The attester GRPC client.
     attestation_client: Arc<AttestationServiceClient>,
+    /// The graffiti included in produced blocks.
+    graffiti: [u8; 32],
     /// The validator client logger.
     log: slog::Logger,
 }
@@ -211,6 +213,7 @@ impl<B: BeaconNodeDuties + 'static, S: Signer + 'static> Service<B, S> {
             duties_manager,
             beacon_block_client,
             attestation_client,
+            graffiti: client_config.graffiti,
             log,
         })
     }
@@ -341,6 +344,7 @@ impl<B: BeaconNodeDuties + 'static, S: Signer + 'static> Service<B, S> {
                     let beacon_node = self.beacon_block_client.clone();
                     let log = self.log.clone();
                     let slots_per_epoch = self.slots_per_epoch;
+                    let graffiti = self.graffiti;
                     std::thread::spawn(move || {
                         info!(log, "Producing a block"; "Validator"=> format!("{}", signers[signer_index]));
                         let signer = &signers[signer_index];
@@ -351,6 +355,7 @@ impl<B: BeaconNodeDuties + 'static, S: Signer + 'static> Service<B, S> {
                             beacon_node,
                             signer,
                             slots_per_epoch,
+                            graffiti,
                         };
                         block_producer.handle_produce_block(log);
                     });